@@ -0,0 +1,290 @@
+use anchor_lang::prelude::*;
+
+use crate::error::PredictionMarketError;
+use crate::state::PRICE_PRECISION;
+
+/// Shares received for `collateral` (after fees) on the side whose reserve is
+/// `reserve_out`. The collateral mints complete sets into both reserves and
+/// the pool releases enough `reserve_out` shares to restore
+/// `reserve_out * reserve_other`, rounding in the pool's favour.
+pub fn calculate_buy_shares(reserve_out: u64, reserve_other: u64, collateral: u64) -> Result<u64> {
+    let invariant = (reserve_out as u128)
+        .checked_mul(reserve_other as u128)
+        .ok_or(PredictionMarketError::ArithmeticOverflow)?;
+    let new_reserve_other = (reserve_other as u128)
+        .checked_add(collateral as u128)
+        .ok_or(PredictionMarketError::ArithmeticOverflow)?;
+    require!(new_reserve_other > 0, PredictionMarketError::InsufficientLiquidity);
+
+    let new_reserve_out = invariant
+        .checked_add(new_reserve_other - 1)
+        .ok_or(PredictionMarketError::ArithmeticOverflow)?
+        / new_reserve_other;
+    let shares = (reserve_out as u128)
+        .checked_add(collateral as u128)
+        .ok_or(PredictionMarketError::ArithmeticOverflow)?
+        .checked_sub(new_reserve_out)
+        .ok_or(PredictionMarketError::ArithmeticUnderflow)?;
+
+    u64::try_from(shares).map_err(|_| PredictionMarketError::ArithmeticOverflow.into())
+}
+
+/// Collateral (before fees) released when `shares` are sold into the side
+/// whose reserve is `reserve_in`. Solves
+/// `(reserve_in + shares - c) * (reserve_other - c) = reserve_in * reserve_other`
+/// for `c`, rounding in the pool's favour.
+pub fn calculate_sell_collateral(reserve_in: u64, reserve_other: u64, shares: u64) -> Result<u64> {
+    let sum = (reserve_in as u128)
+        .checked_add(shares as u128)
+        .and_then(|total| total.checked_add(reserve_other as u128))
+        .ok_or(PredictionMarketError::ArithmeticOverflow)?;
+    let discriminant = sum
+        .checked_mul(sum)
+        .and_then(|squared| {
+            squared.checked_sub((reserve_other as u128).checked_mul(shares as u128)?.checked_mul(4)?)
+        })
+        .ok_or(PredictionMarketError::ArithmeticOverflow)?;
+
+    let collateral = sum
+        .checked_sub(sqrt_ceil(discriminant))
+        .ok_or(PredictionMarketError::ArithmeticUnderflow)?
+        / 2;
+
+    u64::try_from(collateral).map_err(|_| PredictionMarketError::ArithmeticOverflow.into())
+}
+
+/// Spot prices of outcome A and B implied by the reserves, scaled by
+/// `PRICE_PRECISION`. The scarcer side is the more expensive one.
+pub fn spot_prices(reserve_a: u64, reserve_b: u64) -> Result<(u64, u64)> {
+    let total = (reserve_a as u128)
+        .checked_add(reserve_b as u128)
+        .ok_or(PredictionMarketError::ArithmeticOverflow)?;
+    require!(total > 0, PredictionMarketError::InsufficientLiquidity);
+
+    let price_a = (reserve_b as u128 * PRICE_PRECISION as u128 / total) as u64;
+    Ok((price_a, PRICE_PRECISION - price_a))
+}
+
+/// Splits a deposit of `amount` collateral between the reserves without
+/// moving the price. The deposit mints `amount` complete sets; each reserve
+/// keeps the fraction of them matching its size relative to the larger
+/// reserve and the rest goes back to the depositor as outcome shares. LP
+/// tokens are minted against the larger reserve. Returns
+/// `(added_a, added_b, lp_tokens)`.
+pub fn calculate_add_liquidity(
+    reserve_a: u64,
+    reserve_b: u64,
+    lp_supply: u64,
+    amount: u64,
+) -> Result<(u64, u64, u64)> {
+    let pool_weight = reserve_a.max(reserve_b);
+    if pool_weight == 0 {
+        return Ok((amount, amount, amount));
+    }
+
+    let added_a = pro_rata(amount, reserve_a, pool_weight)?;
+    let added_b = pro_rata(amount, reserve_b, pool_weight)?;
+    let lp_tokens = if lp_supply == 0 {
+        amount
+    } else {
+        pro_rata(amount, lp_supply, pool_weight)?
+    };
+
+    Ok((added_a, added_b, lp_tokens))
+}
+
+/// `amount * numerator / denominator`, rounded down.
+pub fn pro_rata(amount: u64, numerator: u64, denominator: u64) -> Result<u64> {
+    let value = (amount as u128)
+        .checked_mul(numerator as u128)
+        .ok_or(PredictionMarketError::ArithmeticOverflow)?
+        .checked_div(denominator as u128)
+        .ok_or(PredictionMarketError::DivisionByZero)?;
+    u64::try_from(value).map_err(|_| PredictionMarketError::ArithmeticOverflow.into())
+}
+
+/// Executed price of a trade in collateral per share, scaled by
+/// `PRICE_PRECISION`.
+pub fn execution_price(collateral: u64, shares: u64) -> Result<u64> {
+    require!(shares > 0, PredictionMarketError::InvalidAmount);
+
+    let price = (collateral as u128)
+        .checked_mul(PRICE_PRECISION as u128)
+        .ok_or(PredictionMarketError::ArithmeticOverflow)?
+        / shares as u128;
+    u64::try_from(price).map_err(|_| PredictionMarketError::ArithmeticOverflow.into())
+}
+
+/// Fails with `SlippageExceeded` when `executed_price` is worse for the
+/// trader than `quoted_price` by more than `max_slippage` basis points.
+pub fn check_slippage(
+    quoted_price: u64,
+    executed_price: u64,
+    max_slippage: u16,
+    is_buy: bool,
+) -> Result<()> {
+    require!(max_slippage <= 10000, PredictionMarketError::InvalidSlippage);
+
+    let tolerance = (quoted_price as u128)
+        .checked_mul(max_slippage as u128)
+        .ok_or(PredictionMarketError::ArithmeticOverflow)?
+        / 10000;
+    let within_tolerance = if is_buy {
+        executed_price as u128 <= quoted_price as u128 + tolerance
+    } else {
+        executed_price as u128 + tolerance >= quoted_price as u128
+    };
+    require!(within_tolerance, PredictionMarketError::SlippageExceeded);
+
+    Ok(())
+}
+
+fn sqrt_ceil(value: u128) -> u128 {
+    if value < 2 {
+        return value;
+    }
+
+    // Newton's method converges to floor(sqrt(value)) from above; starting
+    // at the power of two just above the root keeps `root + value / root`
+    // from overflowing at the top of the range
+    let mut root = 1u128 << ((129 - value.leading_zeros()) / 2);
+    let mut next = (root + value / root) / 2;
+    while next < root {
+        root = next;
+        next = (root + value / root) / 2;
+    }
+
+    if root * root < value {
+        root + 1
+    } else {
+        root
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn buy_shares_keep_the_invariant() {
+        // 100 collateral into a 100/100 pool: the other side grows to 200 so
+        // the bought side drops to 50, releasing 100 minted + 50 pooled shares
+        assert_eq!(calculate_buy_shares(100, 100, 100).unwrap(), 150);
+
+        let (reserve_out, reserve_other, collateral) = (3u64, 7u64, 1u64);
+        let shares = calculate_buy_shares(reserve_out, reserve_other, collateral).unwrap();
+        let new_out = (reserve_out + collateral - shares) as u128;
+        let new_other = (reserve_other + collateral) as u128;
+        assert!(new_out * new_other >= reserve_out as u128 * reserve_other as u128);
+    }
+
+    #[test]
+    fn buy_shares_round_in_pool_favour() {
+        // k = 21, other side 8, so the bought side keeps ceil(21 / 8) = 3
+        assert_eq!(calculate_buy_shares(3, 7, 1).unwrap(), 1);
+    }
+
+    #[test]
+    fn buy_with_no_collateral_yields_no_shares() {
+        assert_eq!(calculate_buy_shares(100, 100, 0).unwrap(), 0);
+        assert!(calculate_buy_shares(0, 0, 0).is_err());
+    }
+
+    #[test]
+    fn buy_shares_reject_overflowing_output() {
+        assert!(calculate_buy_shares(u64::MAX, 1, u64::MAX).is_err());
+        assert!(calculate_buy_shares(u64::MAX / 2, u64::MAX / 2, u64::MAX / 4).is_ok());
+    }
+
+    #[test]
+    fn sell_collateral_inverts_buy() {
+        // Selling the 150 shares bought above restores the 100/100 pool
+        assert_eq!(calculate_sell_collateral(50, 200, 150).unwrap(), 100);
+        assert_eq!(calculate_sell_collateral(100, 100, 0).unwrap(), 0);
+    }
+
+    #[test]
+    fn sell_collateral_rounds_in_pool_favour() {
+        for collateral in [1u64, 7, 999, 123_456] {
+            let shares = calculate_buy_shares(1_000_000, 2_000_000, collateral).unwrap();
+            let reserve_in = 1_000_000 + collateral - shares;
+            let reserve_other = 2_000_000 + collateral;
+            let returned = calculate_sell_collateral(reserve_in, reserve_other, shares).unwrap();
+            assert!(returned <= collateral);
+        }
+    }
+
+    #[test]
+    fn sell_collateral_handles_max_reserves() {
+        let collateral = calculate_sell_collateral(u64::MAX / 4, u64::MAX / 4, u64::MAX / 4).unwrap();
+        assert!(collateral < u64::MAX / 4);
+    }
+
+    #[test]
+    fn spot_prices_follow_reserves() {
+        assert_eq!(spot_prices(100, 100).unwrap(), (PRICE_PRECISION / 2, PRICE_PRECISION / 2));
+        assert_eq!(spot_prices(100, 300).unwrap(), (PRICE_PRECISION * 3 / 4, PRICE_PRECISION / 4));
+        assert_eq!(spot_prices(0, 1).unwrap(), (PRICE_PRECISION, 0));
+        assert!(spot_prices(0, 0).is_err());
+    }
+
+    #[test]
+    fn add_liquidity_to_empty_pool_mints_one_for_one() {
+        assert_eq!(calculate_add_liquidity(0, 0, 0, 500).unwrap(), (500, 500, 500));
+    }
+
+    #[test]
+    fn add_liquidity_keeps_pool_ratio() {
+        assert_eq!(calculate_add_liquidity(100, 100, 100, 50).unwrap(), (50, 50, 50));
+        // The smaller reserve only keeps its share of the deposit
+        assert_eq!(calculate_add_liquidity(50, 200, 100, 40).unwrap(), (10, 40, 20));
+        assert_eq!(calculate_add_liquidity(100, 100, 100, 0).unwrap(), (0, 0, 0));
+    }
+
+    #[test]
+    fn add_liquidity_rounds_down() {
+        assert_eq!(calculate_add_liquidity(1, 3, 3, 2).unwrap(), (0, 2, 2));
+        assert_eq!(calculate_add_liquidity(3, 3, 1, 2).unwrap(), (2, 2, 0));
+    }
+
+    #[test]
+    fn pro_rata_rounds_down_and_rejects_zero_denominator() {
+        assert_eq!(pro_rata(10, 1, 3).unwrap(), 3);
+        assert_eq!(pro_rata(u64::MAX, u64::MAX, u64::MAX).unwrap(), u64::MAX);
+        assert!(pro_rata(u64::MAX, 2, 1).is_err());
+        assert!(pro_rata(1, 1, 0).is_err());
+    }
+
+    #[test]
+    fn sqrt_ceil_boundaries() {
+        assert_eq!(sqrt_ceil(0), 0);
+        assert_eq!(sqrt_ceil(1), 1);
+        assert_eq!(sqrt_ceil(2), 2);
+        assert_eq!(sqrt_ceil(4), 2);
+        assert_eq!(sqrt_ceil(5), 3);
+        assert_eq!(sqrt_ceil(u64::MAX as u128 * u64::MAX as u128), u64::MAX as u128);
+        assert_eq!(sqrt_ceil(u128::MAX), 1u128 << 64);
+    }
+
+    #[test]
+    fn slippage_tolerance_is_inclusive() {
+        // 1% of 1_000_000 either way
+        assert!(check_slippage(1_000_000, 1_010_000, 100, true).is_ok());
+        assert!(check_slippage(1_000_000, 1_010_001, 100, true).is_err());
+        assert!(check_slippage(1_000_000, 990_000, 100, false).is_ok());
+        assert!(check_slippage(1_000_000, 989_999, 100, false).is_err());
+    }
+
+    #[test]
+    fn slippage_favourable_fills_always_pass() {
+        assert!(check_slippage(1_000_000, 1, 0, true).is_ok());
+        assert!(check_slippage(1_000_000, u64::MAX, 0, false).is_ok());
+        assert!(check_slippage(1_000_000, 1_000_001, 0, true).is_err());
+    }
+
+    #[test]
+    fn slippage_rejects_out_of_range_tolerance() {
+        assert!(check_slippage(1_000_000, 1_000_000, 10001, true).is_err());
+        assert!(check_slippage(u64::MAX, u64::MAX, 10000, true).is_ok());
+    }
+}
@@ -1,4 +1,3 @@
-```rust
 use anchor_lang::prelude::*;
 
 #[error_code]
@@ -98,5 +97,253 @@ pub enum PredictionMarketError {
     
     #[msg("Feature not implemented")]
     NotImplemented,
+    
+    #[msg("Amount must be greater than zero")]
+    InvalidAmount,
+    
+    #[msg("Bet outcome does not match existing bet")]
+    OutcomeMismatch,
+    
+    #[msg("Invalid resolution time")]
+    InvalidResolutionTime,
+    
+    #[msg("Market category too long")]
+    MarketCategoryTooLong,
+    
+    #[msg("Market has not been resolved")]
+    MarketNotResolved,
+    
+    #[msg("Signer is not the market oracle")]
+    InvalidOracle,
+    
+    #[msg("Oracle data is empty")]
+    InvalidOracleData,
+    
+    #[msg("Oracle data too large")]
+    OracleDataTooLarge,
+    
+    #[msg("Bet did not back the winning outcome")]
+    NotAWinner,
+    
+    #[msg("Winning pool is empty")]
+    InvalidWinningPool,
+    
+    #[msg("Market vault has insufficient funds")]
+    InsufficientVaultFunds,
+    
+    #[msg("Invalid user for this account")]
+    InvalidUser,
+    
+    #[msg("Liquidity pool is empty")]
+    NoLiquidityInPool,
+    
+    #[msg("Withdrawal amount rounds to zero")]
+    InvalidWithdrawalAmount,
+    
+    #[msg("Liquidity pool has insufficient balance")]
+    InsufficientPoolBalance,
+    
+    #[msg("Price data is stale")]
+    StalePrice,
+    
+    #[msg("Invalid price confidence")]
+    InvalidPriceConfidence,
+    
+    #[msg("Market duration too short")]
+    MarketDurationTooShort,
+    
+    #[msg("Market duration too long")]
+    MarketDurationTooLong,
+    
+    #[msg("Invalid number of outcomes for market type")]
+    InvalidOutcomeCount,
+    
+    #[msg("Outcome label is empty or too long")]
+    InvalidOutcomeLabel,
+    
+    #[msg("Scalar market lower bound must be below upper bound")]
+    InvalidScalarBounds,
+    
+    #[msg("Instruction not supported by this market's pricing model")]
+    InvalidPricingModel,
+    
+    #[msg("Invalid LMSR liquidity parameter")]
+    InvalidLiquidityParameter,
+    
+    #[msg("Insufficient outcome shares")]
+    InsufficientShares,
+    
+    #[msg("Trade cost or proceeds outside the caller's limit")]
+    TradeLimitExceeded,
+    
+    #[msg("Slippage tolerance must be at most 10000 basis points")]
+    InvalidSlippage,
+    
+    #[msg("Executed price deviates from quote by more than max slippage")]
+    SlippageExceeded,
+    
+    #[msg("Sell amount exceeds the position")]
+    InsufficientPosition,
+    
+    #[msg("Order book has no free slots")]
+    OrderBookFull,
+    
+    #[msg("Order not found")]
+    OrderNotFound,
+    
+    #[msg("Order price must be between zero and one")]
+    InvalidOrderPrice,
+    
+    #[msg("Order expiry must be in the future and before trading ends")]
+    InvalidOrderExpiry,
+    
+    #[msg("Order is not open")]
+    OrderNotOpen,
+    
+    #[msg("Nothing to settle for this order")]
+    NothingToSettle,
+    
+    #[msg("Market is not cancelled")]
+    MarketNotCancelled,
+    
+    #[msg("Nothing to refund")]
+    NothingToRefund,
+    
+    #[msg("Dispute period must be between zero and thirty days")]
+    InvalidDisputePeriod,
+    
+    #[msg("Dispute window has closed")]
+    DisputeWindowClosed,
+    
+    #[msg("Market is still within its dispute window")]
+    DisputeWindowOpen,
+    
+    #[msg("Only the arbitration authority can rule on disputes")]
+    UnauthorizedArbitrator,
+    
+    #[msg("Dispute has already been ruled on")]
+    DisputeAlreadyResolved,
+    
+    #[msg("Challenge must propose a different result")]
+    DisputeMatchesResolution,
+    
+    #[msg("Oracle name too long")]
+    OracleNameTooLong,
+    
+    #[msg("Oracle description too long")]
+    OracleDescriptionTooLong,
+    
+    #[msg("Oracle is not active")]
+    OracleInactive,
+    
+    #[msg("Committee needs 1 to 10 distinct members and a threshold no larger than the committee")]
+    InvalidCommittee,
+    
+    #[msg("Signer is not a member of the market's oracle committee")]
+    NotCommitteeMember,
+    
+    #[msg("Market is resolved by an oracle committee")]
+    CommitteeResolutionRequired,
+    
+    #[msg("Price feed account is not a supported feed")]
+    InvalidPriceFeed,
+    
+    #[msg("Invalid price feed configuration")]
+    InvalidPriceFeedConfig,
+    
+    #[msg("Market is resolved from its price feed")]
+    PriceFeedResolutionRequired,
+    
+    #[msg("Market already has a resolution source other than its oracle")]
+    ResolutionSourceAlreadySet,
+    
+    #[msg("Invalid optimistic oracle configuration")]
+    InvalidOptimisticConfig,
+    
+    #[msg("Market is resolved by optimistic proposals")]
+    OptimisticResolutionRequired,
+    
+    #[msg("Proposal is not pending")]
+    ProposalNotPending,
+    
+    #[msg("Proposal liveness window has not ended")]
+    LivenessNotExpired,
+    
+    #[msg("Proposal liveness window has ended")]
+    LivenessExpired,
+    
+    #[msg("Only the program admin can perform this action")]
+    UnauthorizedAdmin,
+    
+    #[msg("Unknown pause flags")]
+    InvalidPauseFlags,
+    
+    #[msg("Creator fee rate exceeds the program maximum")]
+    CreatorFeeTooHigh,
+    
+    #[msg("No creator fees to claim")]
+    NoCreatorFeesToClaim,
+    
+    #[msg("Users cannot refer themselves")]
+    SelfReferral,
+    
+    #[msg("Referrer does not match the one this position is attributed to")]
+    ReferrerMismatch,
+    
+    #[msg("Referred positions must pass their referrer and referral vault")]
+    ReferrerRequired,
+    
+    #[msg("No referral rewards to claim")]
+    NoReferralRewards,
+    
+    #[msg("Position still has a payout or refund outstanding")]
+    PositionNotSettled,
+    
+    #[msg("Market still has outstanding claims and the claim deadline has not passed")]
+    MarketNotSettled,
+    
+    #[msg("No liquidity provider fees to claim")]
+    NoFeesToClaim,
+    
+    #[msg("Liquidity is locked after the trading cutoff")]
+    LiquidityLocked,
+    
+    #[msg("Withdrawal fee terms cannot change once other providers have joined")]
+    WithdrawalFeeLocked,
+    
+    #[msg("Invalid withdrawal fee configuration")]
+    InvalidWithdrawalFeeConfig,
+    
+    #[msg("Invalid liquidity rewards schedule")]
+    InvalidRewardSchedule,
+    
+    #[msg("Only the rewards sponsor or program admin can stop liquidity rewards")]
+    UnauthorizedSponsor,
+    
+    #[msg("No liquidity rewards available")]
+    NoLiquidityRewards,
+    
+    #[msg("Liquidity rewards account is required for this pool")]
+    LiquidityRewardsRequired,
+    
+    #[msg("The arbitration deadline has not passed yet")]
+    ArbitrationPending,
+    
+    #[msg("The pending dispute or proposal and its bond accounts are required")]
+    EscrowAccountsRequired,
+    
+    #[msg("Only market participants can dispute a resolution")]
+    NotAMarketParticipant,
+    
+    #[msg("The arbitration deadline has passed; the market can only be cancelled")]
+    ArbitrationExpired,
+    
+    #[msg("The resolution source cannot change once the market has positions")]
+    MarketHasPositions,
+    
+    #[msg("Mock price feeds are only available in builds with the mock-feed feature")]
+    MockPriceFeedDisabled,
+    
+    #[msg("Rent must go back to the account that paid it")]
+    InvalidRentRecipient,
 }
-```
\ No newline at end of file
@@ -0,0 +1,41 @@
+use anchor_lang::prelude::*;
+
+use crate::state::*;
+use crate::error::*;
+
+#[derive(Accounts)]
+pub struct AcceptAdmin<'info> {
+    #[account(
+        mut,
+        seeds = [b"global_state"],
+        bump = global_state.bump,
+        constraint = global_state.pending_authority == pending_authority.key() @ PredictionMarketError::UnauthorizedAdmin,
+    )]
+    pub global_state: Box<Account<'info, GlobalState>>,
+
+    pub pending_authority: Signer<'info>,
+}
+
+/// Second step of an admin handover, signed by the nominated key.
+pub fn accept_admin(ctx: Context<AcceptAdmin>) -> Result<()> {
+    let global_state = &mut ctx.accounts.global_state;
+    let previous_authority = global_state.authority;
+
+    global_state.authority = ctx.accounts.pending_authority.key();
+    global_state.pending_authority = Pubkey::default();
+
+    emit!(AdminTransferredEvent {
+        previous_authority,
+        authority: global_state.authority,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    Ok(())
+}
+
+#[event]
+pub struct AdminTransferredEvent {
+    pub previous_authority: Pubkey,
+    pub authority: Pubkey,
+    pub timestamp: i64,
+}
@@ -1,8 +1,9 @@
-```rust
 use anchor_lang::prelude::*;
-use anchor_spl::token::{self, Token, TokenAccount, Transfer};
+use anchor_spl::token::{self, Mint, Token, TokenAccount, Transfer};
+use crate::amm;
 use crate::state::*;
-use crate::errors::*;
+use crate::error::*;
+use crate::instructions::claim_liquidity_rewards::settle_liquidity_rewards;
 
 #[derive(Accounts)]
 pub struct AddLiquidity<'info> {
@@ -10,122 +11,126 @@ pub struct AddLiquidity<'info> {
         mut,
         seeds = [b"market", market.market_id.to_le_bytes().as_ref()],
         bump = market.bump,
-        has_one = outcome_a_pool,
-        has_one = outcome_b_pool,
+        constraint = !market.is_halted(PAUSE_LIQUIDITY) @ PredictionMarketError::MarketPaused,
+        has_one = lp_mint,
+        constraint = market.status == MarketStatus::Active @ PredictionMarketError::MarketNotActive,
+        constraint = market.pricing_model == PricingModel::ConstantProduct @ PredictionMarketError::InvalidPricingModel,
     )]
-    pub market: Account<'info, Market>,
+    pub market: Box<Account<'info, PredictionMarket>>,
+
+    #[account(
+        mut,
+        seeds = [b"pool", market.key().as_ref()],
+        bump = pool.bump,
+    )]
+    pub pool: Box<Account<'info, LiquidityPool>>,
 
     #[account(
         init_if_needed,
         payer = user,
-        space = 8 + LiquidityProvider::INIT_SPACE,
+        space = LiquidityProvider::LEN,
         seeds = [b"liquidity_provider", market.key().as_ref(), user.key().as_ref()],
         bump
     )]
-    pub liquidity_provider: Account<'info, LiquidityProvider>,
+    pub liquidity_provider: Box<Account<'info, LiquidityProvider>>,
 
-    #[account(mut)]
-    pub outcome_a_pool: Account<'info, TokenAccount>,
+    #[account(
+        init_if_needed,
+        payer = user,
+        space = UserPosition::LEN,
+        seeds = [b"user_position", user.key().as_ref(), market.key().as_ref()],
+        bump
+    )]
+    pub user_position: Box<Account<'info, UserPosition>>,
 
-    #[account(mut)]
-    pub outcome_b_pool: Account<'info, TokenAccount>,
+    #[account(
+        mut,
+        seeds = [b"market_vault", market.key().as_ref()],
+        bump = market.vault_bump,
+    )]
+    pub market_vault: Box<Account<'info, TokenAccount>>,
 
     #[account(
         mut,
-        constraint = user_token_account.mint == market.base_mint @ PredictionMarketError::InvalidMint,
+        constraint = user_token_account.mint == market.quote_mint @ PredictionMarketError::InvalidTokenMint,
         constraint = user_token_account.owner == user.key() @ PredictionMarketError::InvalidTokenAccountOwner,
     )]
-    pub user_token_account: Account<'info, TokenAccount>,
+    pub user_token_account: Box<Account<'info, TokenAccount>>,
 
     #[account(
-        init_if_needed,
-        payer = user,
-        associated_token::mint = market.lp_mint,
-        associated_token::authority = user,
+        mut,
+        seeds = [b"lp_escrow", market.key().as_ref()],
+        bump = pool.lp_escrow_bump,
     )]
-    pub user_lp_token_account: Account<'info, TokenAccount>,
+    pub lp_escrow: Box<Account<'info, TokenAccount>>,
 
     #[account(mut)]
-    pub lp_mint: Account<'info, token::Mint>,
+    pub lp_mint: Box<Account<'info, Mint>>,
+
+    #[account(
+        seeds = [b"global_state"],
+        bump = global_state.bump,
+        constraint = !global_state.is_halted(PAUSE_LIQUIDITY) @ PredictionMarketError::MarketPaused,
+    )]
+    pub global_state: Box<Account<'info, GlobalState>>,
 
     #[account(mut)]
     pub user: Signer<'info>,
 
+    /// Required once the market runs a liquidity mining program
+    #[account(
+        mut,
+        seeds = [b"liquidity_rewards", market.key().as_ref()],
+        bump = liquidity_rewards.bump,
+    )]
+    pub liquidity_rewards: Option<Box<Account<'info, LiquidityRewards>>>,
+
     pub token_program: Program<'info, Token>,
-    pub associated_token_program: Program<'info, anchor_spl::associated_token::AssociatedToken>,
     pub system_program: Program<'info, System>,
     pub rent: Sysvar<'info, Rent>,
 }
 
 impl<'info> AddLiquidity<'info> {
+    /// Deposits `amount` collateral into the constant-product pool at the
+    /// current price. Whatever the deposit adds beyond the pool's ratio is
+    /// returned to the provider as outcome shares.
     pub fn add_liquidity(&mut self, amount: u64, bumps: &AddLiquidityBumps) -> Result<()> {
         require!(amount > 0, PredictionMarketError::InvalidAmount);
         require!(
-            self.market.status == MarketStatus::Active,
-            PredictionMarketError::MarketNotActive
+            Clock::get()?.unix_timestamp < self.market.end_time,
+            PredictionMarketError::MarketExpired
         );
 
         let market = &mut self.market;
+        let pool = &mut self.pool;
         let liquidity_provider = &mut self.liquidity_provider;
+        let user_position = &mut self.user_position;
 
-        // Calculate current pool ratio
-        let pool_a_balance = self.outcome_a_pool.amount;
-        let pool_b_balance = self.outcome_b_pool.amount;
-        let total_liquidity = pool_a_balance.checked_add(pool_b_balance)
-            .ok_or(PredictionMarketError::MathOverflow)?;
-
-        // For initial liquidity, split equally between pools
-        let (amount_a, amount_b) = if total_liquidity == 0 {
-            let half_amount = amount.checked_div(2)
-                .ok_or(PredictionMarketError::MathOverflow)?;
-            (half_amount, amount.checked_sub(half_amount)
-                .ok_or(PredictionMarketError::MathOverflow)?)
-        } else {
-            // Maintain current pool ratio
-            let amount_a = amount.checked_mul(pool_a_balance)
-                .ok_or(PredictionMarketError::MathOverflow)?
-                .checked_div(total_liquidity)
-                .ok_or(PredictionMarketError::MathOverflow)?;
-            let amount_b = amount.checked_sub(amount_a)
-                .ok_or(PredictionMarketError::MathOverflow)?;
-            (amount_a, amount_b)
-        };
-
-        // Transfer tokens from user to pools
-        let transfer_to_a_ctx = CpiContext::new(
-            self.token_program.to_account_info(),
-            Transfer {
-                from: self.user_token_account.to_account_info(),
-                to: self.outcome_a_pool.to_account_info(),
-                authority: self.user.to_account_info(),
-            },
-        );
-        token::transfer(transfer_to_a_ctx, amount_a)?;
+        let (added_a, added_b, lp_tokens_to_mint) = amm::calculate_add_liquidity(
+            pool.reserve_a,
+            pool.reserve_b,
+            self.lp_mint.supply,
+            amount,
+        )?;
+        require!(lp_tokens_to_mint > 0, PredictionMarketError::InvalidAmount);
+        let returned_shares = [amount - added_a, amount - added_b];
 
-        let transfer_to_b_ctx = CpiContext::new(
+        // Transfer tokens from user to the market vault
+        let transfer_ctx = CpiContext::new(
             self.token_program.to_account_info(),
             Transfer {
                 from: self.user_token_account.to_account_info(),
-                to: self.outcome_b_pool.to_account_info(),
+                to: self.market_vault.to_account_info(),
                 authority: self.user.to_account_info(),
             },
         );
-        token::transfer(transfer_to_b_ctx, amount_b)?;
-
-        // Calculate LP tokens to mint
-        let lp_tokens_to_mint = if market.total_liquidity == 0 {
-            amount
-        } else {
-            amount.checked_mul(self.lp_mint.supply)
-                .ok_or(PredictionMarketError::MathOverflow)?
-                .checked_div(market.total_liquidity)
-                .ok_or(PredictionMarketError::MathOverflow)?
-        };
-
-        // Mint LP tokens to user
+        token::transfer(transfer_ctx, amount)?;
+
+        // Mint the provider's LP tokens into escrow
+        let market_id_bytes = market.market_id.to_le_bytes();
         let market_seeds = &[
             b"market",
-            market.market_id.to_le_bytes().as_ref(),
+            market_id_bytes.as_ref(),
             &[market.bump],
         ];
         let signer_seeds = &[&market_seeds[..]];
@@ -134,7 +139,7 @@ impl<'info> AddLiquidity<'info> {
             self.token_program.to_account_info(),
             token::MintTo {
                 mint: self.lp_mint.to_account_info(),
-                to: self.user_lp_token_account.to_account_info(),
+                to: self.lp_escrow.to_account_info(),
                 authority: market.to_account_info(),
             },
             signer_seeds,
@@ -146,29 +151,69 @@ impl<'info> AddLiquidity<'info> {
             liquidity_provider.user = self.user.key();
             liquidity_provider.market = market.key();
             liquidity_provider.bump = bumps.liquidity_provider;
+
+            market.liquidity_providers_count = market.liquidity_providers_count
+                .checked_add(1)
+                .ok_or(PredictionMarketError::ArithmeticOverflow)?;
         }
-        
+
+        liquidity_provider.settle_fees(pool.fee_growth_per_share)?;
+        settle_liquidity_rewards(pool, self.liquidity_rewards.as_mut(), liquidity_provider)?;
         liquidity_provider.lp_tokens = liquidity_provider.lp_tokens
             .checked_add(lp_tokens_to_mint)
-            .ok_or(PredictionMarketError::MathOverflow)?;
+            .ok_or(PredictionMarketError::ArithmeticOverflow)?;
         liquidity_provider.total_deposited = liquidity_provider.total_deposited
             .checked_add(amount)
-            .ok_or(PredictionMarketError::MathOverflow)?;
+            .ok_or(PredictionMarketError::ArithmeticOverflow)?;
+
+        // Hand back the shares the pool does not keep
+        if user_position.user == Pubkey::default() {
+            user_position.user = self.user.key();
+            user_position.market = market.key();
+            user_position.bump = bumps.user_position;
 
-        // Update market state
+            market.open_positions = market.open_positions
+                .checked_add(1)
+                .ok_or(PredictionMarketError::ArithmeticOverflow)?;
+        }
+        for (index, shares) in returned_shares.iter().enumerate() {
+            user_position.shares[index] = user_position.shares[index]
+                .checked_add(*shares)
+                .ok_or(PredictionMarketError::ArithmeticOverflow)?;
+            market.outcomes[index].shares = market.outcomes[index].shares
+                .checked_add(*shares)
+                .ok_or(PredictionMarketError::ArithmeticOverflow)?;
+        }
+        user_position.last_update_time = Clock::get()?.unix_timestamp;
+
+        // Update pool and market state
+        pool.reserve_a = pool.reserve_a
+            .checked_add(added_a)
+            .ok_or(PredictionMarketError::ArithmeticOverflow)?;
+        pool.reserve_b = pool.reserve_b
+            .checked_add(added_b)
+            .ok_or(PredictionMarketError::ArithmeticOverflow)?;
+        pool.total_supply = pool.total_supply
+            .checked_add(lp_tokens_to_mint)
+            .ok_or(PredictionMarketError::ArithmeticOverflow)?;
+        pool.total_liquidity = pool.total_liquidity
+            .checked_add(amount)
+            .ok_or(PredictionMarketError::ArithmeticOverflow)?;
         market.total_liquidity = market.total_liquidity
             .checked_add(amount)
-            .ok_or(PredictionMarketError::MathOverflow)?;
-        market.liquidity_providers_count = market.liquidity_providers_count
-            .checked_add(1)
-            .ok_or(PredictionMarketError::MathOverflow)?;
+            .ok_or(PredictionMarketError::ArithmeticOverflow)?;
+
+        let (price_a, price_b) = amm::spot_prices(pool.reserve_a, pool.reserve_b)?;
+        market.outcomes[OUTCOME_A as usize].price = price_a;
+        market.outcomes[OUTCOME_B as usize].price = price_b;
 
         emit!(LiquidityAddedEvent {
             market: market.key(),
             user: self.user.key(),
             amount,
-            amount_a,
-            amount_b,
+            added_a,
+            added_b,
+            returned_shares,
             lp_tokens_minted: lp_tokens_to_mint,
             timestamp: Clock::get()?.unix_timestamp,
         });
@@ -182,9 +227,9 @@ pub struct LiquidityAddedEvent {
     pub market: Pubkey,
     pub user: Pubkey,
     pub amount: u64,
-    pub amount_a: u64,
-    pub amount_b: u64,
+    pub added_a: u64,
+    pub added_b: u64,
+    pub returned_shares: [u64; 2],
     pub lp_tokens_minted: u64,
     pub timestamp: i64,
 }
-```
\ No newline at end of file
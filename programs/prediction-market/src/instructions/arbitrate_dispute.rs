@@ -0,0 +1,151 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::{self, Token, TokenAccount, Transfer};
+
+use crate::state::*;
+use crate::error::*;
+
+#[derive(Accounts)]
+pub struct ArbitrateDispute<'info> {
+    #[account(
+        mut,
+        seeds = [b"market", market.market_id.to_le_bytes().as_ref()],
+        bump = market.bump,
+        has_one = oracle_account @ PredictionMarketError::InvalidOracle,
+        constraint = market.status == MarketStatus::Disputed @ PredictionMarketError::MarketNotResolved,
+        constraint = market.arbitration_deadline > Clock::get()?.unix_timestamp @ PredictionMarketError::ArbitrationExpired,
+    )]
+    pub market: Box<Account<'info, PredictionMarket>>,
+
+    #[account(
+        mut,
+        seeds = [b"global_state"],
+        bump = global_state.bump,
+        has_one = arbitrator @ PredictionMarketError::UnauthorizedArbitrator,
+    )]
+    pub global_state: Box<Account<'info, GlobalState>>,
+
+    #[account(
+        mut,
+        seeds = [b"dispute", market.key().as_ref()],
+        bump = dispute.bump,
+        constraint = dispute.status == DisputeStatus::Pending @ PredictionMarketError::DisputeAlreadyResolved,
+    )]
+    pub dispute: Box<Account<'info, Dispute>>,
+
+    #[account(mut)]
+    pub oracle_account: Box<Account<'info, OracleAccount>>,
+
+    #[account(
+        mut,
+        seeds = [b"market_vault", market.key().as_ref()],
+        bump = market.vault_bump,
+    )]
+    pub vault: Box<Account<'info, TokenAccount>>,
+
+    #[account(
+        mut,
+        seeds = [b"treasury", market.quote_mint.as_ref()],
+        bump,
+    )]
+    pub treasury: Box<Account<'info, TokenAccount>>,
+
+    #[account(
+        mut,
+        constraint = challenger_token_account.mint == market.quote_mint @ PredictionMarketError::InvalidTokenMint,
+        constraint = challenger_token_account.owner == dispute.challenger @ PredictionMarketError::InvalidTokenAccountOwner,
+    )]
+    pub challenger_token_account: Box<Account<'info, TokenAccount>>,
+
+    pub arbitrator: Signer<'info>,
+
+    pub token_program: Program<'info, Token>,
+}
+
+/// Final ruling on a disputed resolution, due before the market's
+/// arbitration deadline. If the arbitrator overturns the oracle the
+/// challenger's bond is refunded; otherwise it is slashed to the platform
+/// treasury, which funds arbitration. Either way the result is final and
+/// claims open at once.
+pub fn arbitrate_dispute(ctx: Context<ArbitrateDispute>, params: ResolutionParams) -> Result<()> {
+    let market = &mut ctx.accounts.market;
+    let dispute = &mut ctx.accounts.dispute;
+    let clock = Clock::get()?;
+
+    let overturned = !market.matches_resolution(&params);
+    let winning_pool = market.apply_resolution(&params)?;
+
+    let market_id_bytes = market.market_id.to_le_bytes();
+    let market_seeds = &[
+        b"market",
+        market_id_bytes.as_ref(),
+        &[market.bump],
+    ];
+    let signer_seeds = &[&market_seeds[..]];
+
+    if overturned {
+        let transfer_ctx = CpiContext::new_with_signer(
+            ctx.accounts.token_program.to_account_info(),
+            Transfer {
+                from: ctx.accounts.vault.to_account_info(),
+                to: ctx.accounts.challenger_token_account.to_account_info(),
+                authority: market.to_account_info(),
+            },
+            signer_seeds,
+        );
+        token::transfer(transfer_ctx, dispute.bond)?;
+
+        // Committee and price feed resolutions never credited the oracle
+        if market.resolved_by_oracle() {
+            ctx.accounts.oracle_account.record_overturned()?;
+        }
+        dispute.status = DisputeStatus::Overturned;
+    } else {
+        let transfer_ctx = CpiContext::new_with_signer(
+            ctx.accounts.token_program.to_account_info(),
+            Transfer {
+                from: ctx.accounts.vault.to_account_info(),
+                to: ctx.accounts.treasury.to_account_info(),
+                authority: market.to_account_info(),
+            },
+            signer_seeds,
+        );
+        token::transfer(transfer_ctx, dispute.bond)?;
+
+        let global_state = &mut ctx.accounts.global_state;
+        global_state.total_fees_collected = global_state.total_fees_collected
+            .checked_add(dispute.bond)
+            .ok_or(PredictionMarketError::ArithmeticOverflow)?;
+
+        dispute.status = DisputeStatus::Upheld;
+    }
+
+    dispute.resolved_at = clock.unix_timestamp;
+    market.status = MarketStatus::Resolved;
+    market.resolution_timestamp = clock.unix_timestamp;
+    market.dispute_end_time = clock.unix_timestamp;
+
+    emit!(DisputeResolvedEvent {
+        market: market.key(),
+        challenger: dispute.challenger,
+        overturned,
+        bond: dispute.bond,
+        outcome: market.winning_outcome,
+        resolution_price: market.resolution_price,
+        winning_pool,
+        timestamp: clock.unix_timestamp,
+    });
+
+    Ok(())
+}
+
+#[event]
+pub struct DisputeResolvedEvent {
+    pub market: Pubkey,
+    pub challenger: Pubkey,
+    pub overturned: bool,
+    pub bond: u64,
+    pub outcome: Option<u8>,
+    pub resolution_price: Option<u64>,
+    pub winning_pool: u64,
+    pub timestamp: i64,
+}
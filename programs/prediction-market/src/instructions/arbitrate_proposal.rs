@@ -0,0 +1,128 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::{self, Token, TokenAccount, Transfer};
+
+use crate::instructions::resolve_market::MarketResolvedEvent;
+use crate::state::*;
+use crate::error::*;
+
+#[derive(Accounts)]
+pub struct ArbitrateProposal<'info> {
+    #[account(
+        mut,
+        seeds = [b"market", market.market_id.to_le_bytes().as_ref()],
+        bump = market.bump,
+        has_one = arbiter @ PredictionMarketError::UnauthorizedArbitrator,
+        constraint = market.status == MarketStatus::Disputed @ PredictionMarketError::MarketNotResolved,
+        constraint = market.arbitration_deadline > Clock::get()?.unix_timestamp @ PredictionMarketError::ArbitrationExpired,
+    )]
+    pub market: Box<Account<'info, PredictionMarket>>,
+
+    #[account(
+        mut,
+        seeds = [b"proposal", market.key().as_ref()],
+        bump = proposal.bump,
+        constraint = proposal.status == ProposalStatus::Challenged @ PredictionMarketError::ProposalNotPending,
+    )]
+    pub proposal: Box<Account<'info, Proposal>>,
+
+    #[account(
+        mut,
+        seeds = [b"market_vault", market.key().as_ref()],
+        bump = market.vault_bump,
+    )]
+    pub vault: Box<Account<'info, TokenAccount>>,
+
+    #[account(
+        mut,
+        constraint = proposer_token_account.mint == market.quote_mint @ PredictionMarketError::InvalidTokenMint,
+        constraint = proposer_token_account.owner == proposal.proposer @ PredictionMarketError::InvalidTokenAccountOwner,
+    )]
+    pub proposer_token_account: Box<Account<'info, TokenAccount>>,
+
+    #[account(
+        mut,
+        constraint = challenger_token_account.mint == market.quote_mint @ PredictionMarketError::InvalidTokenMint,
+        constraint = challenger_token_account.owner == proposal.challenger @ PredictionMarketError::InvalidTokenAccountOwner,
+    )]
+    pub challenger_token_account: Box<Account<'info, TokenAccount>>,
+
+    pub arbiter: Signer<'info>,
+
+    pub token_program: Program<'info, Token>,
+}
+
+/// The arbiter's final ruling on a challenged proposal, due before the
+/// market's arbitration deadline. The market settles on `params`, and
+/// whichever side the ruling agrees with takes both bonds. Past the deadline
+/// the market can only be cancelled, which returns both bonds.
+pub fn arbitrate_proposal(ctx: Context<ArbitrateProposal>, params: ResolutionParams) -> Result<()> {
+    let market = &mut ctx.accounts.market;
+    let proposal = &mut ctx.accounts.proposal;
+    let now = Clock::get()?.unix_timestamp;
+
+    let (winning_pool, losing_pool) = market.settle(&params, 0, now)?;
+    market.dispute_end_time = now;
+
+    let proposal_upheld = market.matches_resolution(&proposal.params());
+    let (winner_token_account, status) = if proposal_upheld {
+        (&ctx.accounts.proposer_token_account, ProposalStatus::Accepted)
+    } else {
+        (&ctx.accounts.challenger_token_account, ProposalStatus::Rejected)
+    };
+    proposal.status = status;
+
+    let payout = proposal.bond
+        .checked_mul(2)
+        .ok_or(PredictionMarketError::ArithmeticOverflow)?;
+
+    let market_id_bytes = market.market_id.to_le_bytes();
+    let market_seeds = &[
+        b"market",
+        market_id_bytes.as_ref(),
+        &[market.bump],
+    ];
+    let signer_seeds = &[&market_seeds[..]];
+
+    let transfer_ctx = CpiContext::new_with_signer(
+        ctx.accounts.token_program.to_account_info(),
+        Transfer {
+            from: ctx.accounts.vault.to_account_info(),
+            to: winner_token_account.to_account_info(),
+            authority: market.to_account_info(),
+        },
+        signer_seeds,
+    );
+    token::transfer(transfer_ctx, payout)?;
+
+    emit!(ProposalArbitratedEvent {
+        market: market.key(),
+        proposer: proposal.proposer,
+        challenger: proposal.challenger,
+        proposal_upheld,
+        bond_payout: payout,
+        timestamp: now,
+    });
+
+    emit!(MarketResolvedEvent {
+        market: market.key(),
+        outcome: market.winning_outcome,
+        resolution_price: market.resolution_price,
+        winning_pool,
+        losing_pool,
+        oracle_fee: 0,
+        resolution_timestamp: market.resolution_timestamp,
+        dispute_end_time: market.dispute_end_time,
+    });
+
+    Ok(())
+}
+
+#[event]
+pub struct ProposalArbitratedEvent {
+    pub market: Pubkey,
+    pub proposer: Pubkey,
+    pub challenger: Pubkey,
+    pub proposal_upheld: bool,
+    pub bond_payout: u64,
+    pub timestamp: i64,
+}
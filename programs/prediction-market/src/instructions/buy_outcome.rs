@@ -0,0 +1,216 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::{self, Token, TokenAccount, Transfer};
+
+use crate::amm;
+use crate::state::*;
+use crate::error::*;
+use crate::instructions::register_referrer::record_referred_trade;
+
+#[derive(Accounts)]
+pub struct BuyOutcome<'info> {
+    #[account(
+        mut,
+        seeds = [b"market", market.market_id.to_le_bytes().as_ref()],
+        bump = market.bump,
+        constraint = !market.is_halted(PAUSE_TRADING) @ PredictionMarketError::MarketPaused,
+        constraint = market.status == MarketStatus::Active @ PredictionMarketError::MarketNotActive,
+        constraint = market.pricing_model == PricingModel::ConstantProduct @ PredictionMarketError::InvalidPricingModel,
+    )]
+    pub market: Box<Account<'info, PredictionMarket>>,
+
+    #[account(
+        mut,
+        seeds = [b"pool", market.key().as_ref()],
+        bump = pool.bump,
+    )]
+    pub pool: Box<Account<'info, LiquidityPool>>,
+
+    #[account(
+        init_if_needed,
+        payer = user,
+        space = UserPosition::LEN,
+        seeds = [b"user_position", user.key().as_ref(), market.key().as_ref()],
+        bump
+    )]
+    pub user_position: Box<Account<'info, UserPosition>>,
+
+    #[account(
+        mut,
+        seeds = [b"market_vault", market.key().as_ref()],
+        bump = market.vault_bump,
+    )]
+    pub market_vault: Account<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        constraint = user_token_account.mint == market.quote_mint @ PredictionMarketError::InvalidTokenMint,
+        constraint = user_token_account.owner == user.key() @ PredictionMarketError::InvalidTokenAccountOwner,
+    )]
+    pub user_token_account: Account<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        seeds = [b"global_state"],
+        bump = global_state.bump,
+        constraint = !global_state.is_halted(PAUSE_TRADING) @ PredictionMarketError::MarketPaused,
+    )]
+    pub global_state: Box<Account<'info, GlobalState>>,
+
+    #[account(mut)]
+    pub user: Signer<'info>,
+
+    /// Partner frontend that referred this trade
+    #[account(
+        mut,
+        seeds = [b"referrer", market.quote_mint.as_ref(), referrer.authority.as_ref()],
+        bump = referrer.bump,
+    )]
+    pub referrer: Option<Box<Account<'info, Referrer>>>,
+
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+}
+
+/// Buys outcome shares for `params.amount` collateral against the pool.
+pub fn buy_outcome(ctx: Context<BuyOutcome>, params: TradeParams) -> Result<()> {
+    let market = &mut ctx.accounts.market;
+    let pool = &mut ctx.accounts.pool;
+    let user_position = &mut ctx.accounts.user_position;
+    let clock = Clock::get()?;
+
+    require!(params.amount > 0, PredictionMarketError::InvalidAmount);
+    require!(
+        params.outcome == OUTCOME_A || params.outcome == OUTCOME_B,
+        PredictionMarketError::InvalidOutcome
+    );
+    require!(
+        clock.unix_timestamp < market.end_time,
+        PredictionMarketError::MarketExpired
+    );
+
+    let fee = (params.amount as u128)
+        .checked_mul(pool.fee_rate as u128)
+        .ok_or(PredictionMarketError::ArithmeticOverflow)?
+        .checked_div(10000)
+        .ok_or(PredictionMarketError::DivisionByZero)? as u64;
+    let creator_fee = market.accrue_creator_fee(params.amount)?;
+    let net_amount = params.amount
+        .checked_sub(fee)
+        .and_then(|amount| amount.checked_sub(creator_fee))
+        .ok_or(PredictionMarketError::ArithmeticUnderflow)?;
+
+    let (reserve_out, reserve_other) = if params.outcome == OUTCOME_A {
+        (pool.reserve_a, pool.reserve_b)
+    } else {
+        (pool.reserve_b, pool.reserve_a)
+    };
+    let shares = amm::calculate_buy_shares(reserve_out, reserve_other, net_amount)?;
+    require!(shares > 0, PredictionMarketError::InsufficientLiquidity);
+
+    let executed_price = amm::execution_price(params.amount, shares)?;
+    amm::check_slippage(params.quoted_price, executed_price, params.max_slippage, true)?;
+
+    let transfer_ctx = CpiContext::new(
+        ctx.accounts.token_program.to_account_info(),
+        Transfer {
+            from: ctx.accounts.user_token_account.to_account_info(),
+            to: ctx.accounts.market_vault.to_account_info(),
+            authority: ctx.accounts.user.to_account_info(),
+        },
+    );
+    token::transfer(transfer_ctx, params.amount)?;
+
+    // Net collateral mints complete sets into both reserves, then the bought
+    // side releases the trader's shares
+    let new_reserve_out = reserve_out
+        .checked_add(net_amount)
+        .and_then(|reserve| reserve.checked_sub(shares))
+        .ok_or(PredictionMarketError::ArithmeticOverflow)?;
+    let new_reserve_other = reserve_other
+        .checked_add(net_amount)
+        .ok_or(PredictionMarketError::ArithmeticOverflow)?;
+    if params.outcome == OUTCOME_A {
+        pool.reserve_a = new_reserve_out;
+        pool.reserve_b = new_reserve_other;
+    } else {
+        pool.reserve_b = new_reserve_out;
+        pool.reserve_a = new_reserve_other;
+    }
+    pool.accrue_fee(fee)?;
+
+    // Initialize or update position
+    if user_position.user == Pubkey::default() {
+        user_position.user = ctx.accounts.user.key();
+        user_position.market = market.key();
+        user_position.bump = ctx.bumps.user_position;
+
+        market.open_positions = market.open_positions
+            .checked_add(1)
+            .ok_or(PredictionMarketError::ArithmeticOverflow)?;
+    }
+    user_position.shares[params.outcome as usize] = user_position.shares[params.outcome as usize]
+        .checked_add(shares)
+        .ok_or(PredictionMarketError::ArithmeticOverflow)?;
+    user_position.total_invested = user_position.total_invested
+        .checked_add(params.amount - creator_fee)
+        .ok_or(PredictionMarketError::ArithmeticOverflow)?;
+    user_position.lp_fees_paid = user_position.lp_fees_paid
+        .checked_add(fee)
+        .ok_or(PredictionMarketError::ArithmeticOverflow)?;
+    user_position.last_update_time = clock.unix_timestamp;
+
+    if let Some(referrer) = ctx.accounts.referrer.as_mut() {
+        record_referred_trade(
+            referrer,
+            &mut user_position.referrer,
+            ctx.accounts.user.key(),
+            market.key(),
+            params.amount,
+        )?;
+    }
+
+    let market_outcome = market.outcome_mut(params.outcome)?;
+    market_outcome.shares = market_outcome.shares
+        .checked_add(shares)
+        .ok_or(PredictionMarketError::ArithmeticOverflow)?;
+
+    let (price_a, price_b) = amm::spot_prices(pool.reserve_a, pool.reserve_b)?;
+    market.outcomes[OUTCOME_A as usize].price = price_a;
+    market.outcomes[OUTCOME_B as usize].price = price_b;
+
+    let global_state = &mut ctx.accounts.global_state;
+    global_state.total_volume = global_state.total_volume
+        .checked_add(params.amount)
+        .ok_or(PredictionMarketError::ArithmeticOverflow)?;
+
+    emit!(OutcomeBoughtEvent {
+        market: market.key(),
+        user: ctx.accounts.user.key(),
+        outcome: params.outcome,
+        amount: params.amount,
+        fee,
+        creator_fee,
+        shares,
+        executed_price,
+        price_a,
+        price_b,
+        timestamp: clock.unix_timestamp,
+    });
+
+    Ok(())
+}
+
+#[event]
+pub struct OutcomeBoughtEvent {
+    pub market: Pubkey,
+    pub user: Pubkey,
+    pub outcome: u8,
+    pub amount: u64,
+    pub fee: u64,
+    pub creator_fee: u64,
+    pub shares: u64,
+    pub executed_price: u64,
+    pub price_a: u64,
+    pub price_b: u64,
+    pub timestamp: i64,
+}
@@ -0,0 +1,174 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::{self, Token, TokenAccount, Transfer};
+
+use crate::lmsr;
+use crate::state::*;
+use crate::error::*;
+use crate::instructions::register_referrer::record_referred_trade;
+
+#[derive(Accounts)]
+pub struct BuyShares<'info> {
+    #[account(
+        mut,
+        seeds = [b"market", market.market_id.to_le_bytes().as_ref()],
+        bump = market.bump,
+        constraint = !market.is_halted(PAUSE_TRADING) @ PredictionMarketError::MarketPaused,
+        constraint = market.status == MarketStatus::Active @ PredictionMarketError::MarketNotActive,
+        constraint = market.pricing_model == PricingModel::Lmsr @ PredictionMarketError::InvalidPricingModel,
+    )]
+    pub market: Box<Account<'info, PredictionMarket>>,
+
+    #[account(
+        init_if_needed,
+        payer = user,
+        space = UserPosition::LEN,
+        seeds = [b"user_position", user.key().as_ref(), market.key().as_ref()],
+        bump
+    )]
+    pub user_position: Box<Account<'info, UserPosition>>,
+
+    #[account(
+        mut,
+        seeds = [b"market_vault", market.key().as_ref()],
+        bump = market.vault_bump,
+    )]
+    pub market_vault: Account<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        constraint = user_token_account.mint == market.quote_mint @ PredictionMarketError::InvalidTokenMint,
+        constraint = user_token_account.owner == user.key() @ PredictionMarketError::InvalidTokenAccountOwner,
+    )]
+    pub user_token_account: Account<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        seeds = [b"global_state"],
+        bump = global_state.bump,
+        constraint = !global_state.is_halted(PAUSE_TRADING) @ PredictionMarketError::MarketPaused,
+    )]
+    pub global_state: Box<Account<'info, GlobalState>>,
+
+    #[account(mut)]
+    pub user: Signer<'info>,
+
+    /// Partner frontend that referred this trade
+    #[account(
+        mut,
+        seeds = [b"referrer", market.quote_mint.as_ref(), referrer.authority.as_ref()],
+        bump = referrer.bump,
+    )]
+    pub referrer: Option<Box<Account<'info, Referrer>>>,
+
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+}
+
+pub fn buy_shares(
+    ctx: Context<BuyShares>,
+    outcome: u8,
+    shares: u64,
+    max_cost: u64,
+) -> Result<()> {
+    let market = &mut ctx.accounts.market;
+    let user_position = &mut ctx.accounts.user_position;
+    let clock = Clock::get()?;
+
+    require!(shares > 0, PredictionMarketError::InvalidAmount);
+    require!(market.is_valid_outcome(outcome), PredictionMarketError::InvalidOutcome);
+    require!(
+        clock.unix_timestamp < market.end_time,
+        PredictionMarketError::MarketExpired
+    );
+
+    let cost = lmsr::buy_cost(
+        &market.outcome_shares(),
+        market.lmsr_liquidity,
+        outcome as usize,
+        shares,
+    )?;
+    let creator_fee = market.accrue_creator_fee(cost)?;
+    let total_cost = cost
+        .checked_add(creator_fee)
+        .ok_or(PredictionMarketError::ArithmeticOverflow)?;
+    require!(total_cost <= max_cost, PredictionMarketError::TradeLimitExceeded);
+
+    let transfer_ctx = CpiContext::new(
+        ctx.accounts.token_program.to_account_info(),
+        Transfer {
+            from: ctx.accounts.user_token_account.to_account_info(),
+            to: ctx.accounts.market_vault.to_account_info(),
+            authority: ctx.accounts.user.to_account_info(),
+        },
+    );
+    token::transfer(transfer_ctx, total_cost)?;
+
+    // Initialize or update position
+    if user_position.user == Pubkey::default() {
+        user_position.user = ctx.accounts.user.key();
+        user_position.market = market.key();
+        user_position.bump = ctx.bumps.user_position;
+
+        market.open_positions = market.open_positions
+            .checked_add(1)
+            .ok_or(PredictionMarketError::ArithmeticOverflow)?;
+    }
+    user_position.shares[outcome as usize] = user_position.shares[outcome as usize]
+        .checked_add(shares)
+        .ok_or(PredictionMarketError::ArithmeticOverflow)?;
+    user_position.total_invested = user_position.total_invested
+        .checked_add(cost)
+        .ok_or(PredictionMarketError::ArithmeticOverflow)?;
+    user_position.last_update_time = clock.unix_timestamp;
+
+    if let Some(referrer) = ctx.accounts.referrer.as_mut() {
+        record_referred_trade(
+            referrer,
+            &mut user_position.referrer,
+            ctx.accounts.user.key(),
+            market.key(),
+            total_cost,
+        )?;
+    }
+
+    // Update outstanding shares and re-quote every outcome
+    let market_outcome = market.outcome_mut(outcome)?;
+    market_outcome.shares = market_outcome.shares
+        .checked_add(shares)
+        .ok_or(PredictionMarketError::ArithmeticOverflow)?;
+
+    let prices = lmsr::prices(&market.outcome_shares(), market.lmsr_liquidity)?;
+    for (market_outcome, price) in market.outcomes.iter_mut().zip(prices.iter()) {
+        market_outcome.price = *price;
+    }
+
+    let global_state = &mut ctx.accounts.global_state;
+    global_state.total_volume = global_state.total_volume
+        .checked_add(total_cost)
+        .ok_or(PredictionMarketError::ArithmeticOverflow)?;
+
+    emit!(SharesBoughtEvent {
+        market: market.key(),
+        user: ctx.accounts.user.key(),
+        outcome,
+        shares,
+        cost,
+        creator_fee,
+        prices,
+        timestamp: clock.unix_timestamp,
+    });
+
+    Ok(())
+}
+
+#[event]
+pub struct SharesBoughtEvent {
+    pub market: Pubkey,
+    pub user: Pubkey,
+    pub outcome: u8,
+    pub shares: u64,
+    pub cost: u64,
+    pub creator_fee: u64,
+    pub prices: Vec<u64>,
+    pub timestamp: i64,
+}
@@ -0,0 +1,170 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::{self, Token, TokenAccount, Transfer};
+
+use crate::state::*;
+use crate::error::*;
+
+#[derive(Accounts)]
+pub struct CancelMarket<'info> {
+    #[account(
+        mut,
+        seeds = [b"market", market.market_id.to_le_bytes().as_ref()],
+        bump = market.bump,
+        constraint = matches!(market.status, MarketStatus::Active | MarketStatus::Disputed)
+            @ PredictionMarketError::MarketNotActive,
+    )]
+    pub market: Box<Account<'info, PredictionMarket>>,
+
+    /// Pending dispute whose bond is returned when a disputed market times out
+    #[account(
+        mut,
+        seeds = [b"dispute", market.key().as_ref()],
+        bump = dispute.bump,
+    )]
+    pub dispute: Option<Box<Account<'info, Dispute>>>,
+
+    /// Unsettled proposal whose bonds are returned on cancellation
+    #[account(
+        mut,
+        seeds = [b"proposal", market.key().as_ref()],
+        bump = proposal.bump,
+    )]
+    pub proposal: Option<Box<Account<'info, Proposal>>>,
+
+    #[account(
+        mut,
+        seeds = [b"market_vault", market.key().as_ref()],
+        bump = market.vault_bump,
+    )]
+    pub vault: Option<Box<Account<'info, TokenAccount>>>,
+
+    #[account(
+        mut,
+        constraint = proposer_token_account.mint == market.quote_mint @ PredictionMarketError::InvalidTokenMint,
+    )]
+    pub proposer_token_account: Option<Box<Account<'info, TokenAccount>>>,
+
+    #[account(
+        mut,
+        constraint = challenger_token_account.mint == market.quote_mint @ PredictionMarketError::InvalidTokenMint,
+    )]
+    pub challenger_token_account: Option<Box<Account<'info, TokenAccount>>>,
+
+    pub canceller: Signer<'info>,
+
+    pub token_program: Option<Program<'info, Token>>,
+}
+
+/// Voids a market whose question turned out to be ambiguous or invalid.
+/// Trading stops and every participant can take their stake back with
+/// `claim_refund`. The oracle may cancel a market it resolves at any time;
+/// on other markets, and for the creator, only while trading is still open,
+/// so nobody can void a market once its outcome is known. Anyone may cancel
+/// a price threshold market nobody resolved within its price window, or a
+/// disputed market that was not ruled on by its arbitration deadline. Any dispute or proposal bonds still escrowed go back to whoever
+/// posted them.
+pub fn cancel_market(ctx: Context<CancelMarket>) -> Result<()> {
+    let clock = Clock::get()?;
+    let canceller = ctx.accounts.canceller.key();
+
+    if ctx.accounts.market.status == MarketStatus::Active {
+        ctx.accounts.market.check_cancel_authority(&canceller, clock.unix_timestamp)?;
+    } else {
+        require!(
+            clock.unix_timestamp >= ctx.accounts.market.arbitration_deadline,
+            PredictionMarketError::ArbitrationPending
+        );
+    }
+    ctx.accounts.return_escrowed_bonds(clock.unix_timestamp)?;
+
+    let market = &mut ctx.accounts.market;
+    market.status = MarketStatus::Cancelled;
+    market.resolution_timestamp = clock.unix_timestamp;
+    market.winning_outcome = None;
+    market.resolution_price = None;
+
+    emit!(MarketCancelledEvent {
+        market: market.key(),
+        cancelled_by: canceller,
+        total_pool: market.total_pool()?,
+        timestamp: clock.unix_timestamp,
+    });
+
+    Ok(())
+}
+
+impl<'info> CancelMarket<'info> {
+    /// Refunds whatever bonds the market still escrows: the challenger's on a
+    /// pending dispute, or the proposer's (and the challenger's, once
+    /// challenged) on an unsettled proposal. A disputed market always holds
+    /// one of the two.
+    fn return_escrowed_bonds(&mut self, now: i64) -> Result<()> {
+        let (proposer_refund, challenger_refund) = if let Some(dispute) = self.dispute
+            .as_mut()
+            .filter(|dispute| dispute.status == DisputeStatus::Pending)
+        {
+            dispute.status = DisputeStatus::Voided;
+            dispute.resolved_at = now;
+            (None, Some((dispute.challenger, dispute.bond)))
+        } else if let Some(proposal) = self.proposal.as_mut() {
+            proposal.void()
+        } else {
+            (None, None)
+        };
+        if self.market.status == MarketStatus::Disputed {
+            require!(
+                proposer_refund.is_some() || challenger_refund.is_some(),
+                PredictionMarketError::EscrowAccountsRequired
+            );
+        }
+
+        if let Some((proposer, bond)) = proposer_refund {
+            let proposer_token_account = self.proposer_token_account
+                .as_ref()
+                .ok_or(PredictionMarketError::EscrowAccountsRequired)?;
+            self.refund_bond(proposer_token_account, proposer, bond)?;
+        }
+        if let Some((challenger, bond)) = challenger_refund {
+            let challenger_token_account = self.challenger_token_account
+                .as_ref()
+                .ok_or(PredictionMarketError::EscrowAccountsRequired)?;
+            self.refund_bond(challenger_token_account, challenger, bond)?;
+        }
+        Ok(())
+    }
+
+    fn refund_bond(&self, to: &Account<'info, TokenAccount>, owner: Pubkey, amount: u64) -> Result<()> {
+        require!(to.owner == owner, PredictionMarketError::InvalidTokenAccountOwner);
+        let (vault, token_program) = match (self.vault.as_ref(), self.token_program.as_ref()) {
+            (Some(vault), Some(token_program)) => (vault, token_program),
+            _ => return err!(PredictionMarketError::EscrowAccountsRequired),
+        };
+
+        let market_id_bytes = self.market.market_id.to_le_bytes();
+        let market_seeds = &[
+            b"market",
+            market_id_bytes.as_ref(),
+            &[self.market.bump],
+        ];
+        let signer_seeds = &[&market_seeds[..]];
+
+        let transfer_ctx = CpiContext::new_with_signer(
+            token_program.to_account_info(),
+            Transfer {
+                from: vault.to_account_info(),
+                to: to.to_account_info(),
+                authority: self.market.to_account_info(),
+            },
+            signer_seeds,
+        );
+        token::transfer(transfer_ctx, amount)
+    }
+}
+
+#[event]
+pub struct MarketCancelledEvent {
+    pub market: Pubkey,
+    pub cancelled_by: Pubkey,
+    pub total_pool: u64,
+    pub timestamp: i64,
+}
@@ -0,0 +1,84 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::{self, Token, TokenAccount, Transfer};
+
+use crate::state::*;
+use crate::error::*;
+
+#[derive(Accounts)]
+pub struct ChallengeProposal<'info> {
+    #[account(
+        mut,
+        seeds = [b"market", market.market_id.to_le_bytes().as_ref()],
+        bump = market.bump,
+        constraint = market.status == MarketStatus::Active @ PredictionMarketError::MarketNotActive,
+    )]
+    pub market: Box<Account<'info, PredictionMarket>>,
+
+    #[account(
+        mut,
+        seeds = [b"proposal", market.key().as_ref()],
+        bump = proposal.bump,
+        constraint = proposal.status == ProposalStatus::Pending @ PredictionMarketError::ProposalNotPending,
+        constraint = proposal.liveness_end > Clock::get()?.unix_timestamp @ PredictionMarketError::LivenessExpired,
+    )]
+    pub proposal: Box<Account<'info, Proposal>>,
+
+    #[account(
+        mut,
+        seeds = [b"market_vault", market.key().as_ref()],
+        bump = market.vault_bump,
+    )]
+    pub vault: Box<Account<'info, TokenAccount>>,
+
+    #[account(
+        mut,
+        constraint = challenger_token_account.mint == market.quote_mint @ PredictionMarketError::InvalidTokenMint,
+        constraint = challenger_token_account.owner == challenger.key() @ PredictionMarketError::InvalidTokenAccountOwner,
+    )]
+    pub challenger_token_account: Box<Account<'info, TokenAccount>>,
+
+    pub challenger: Signer<'info>,
+
+    pub token_program: Program<'info, Token>,
+}
+
+/// Challenges a pending proposal with a matching bond, escalating the market
+/// to its arbiter. If the arbiter does not rule by the arbitration deadline
+/// the market can be cancelled and both bonds are returned.
+pub fn challenge_proposal(ctx: Context<ChallengeProposal>) -> Result<()> {
+    let market = &mut ctx.accounts.market;
+    let proposal = &mut ctx.accounts.proposal;
+
+    let transfer_ctx = CpiContext::new(
+        ctx.accounts.token_program.to_account_info(),
+        Transfer {
+            from: ctx.accounts.challenger_token_account.to_account_info(),
+            to: ctx.accounts.vault.to_account_info(),
+            authority: ctx.accounts.challenger.to_account_info(),
+        },
+    );
+    token::transfer(transfer_ctx, proposal.bond)?;
+
+    proposal.challenger = ctx.accounts.challenger.key();
+    proposal.status = ProposalStatus::Challenged;
+    market.escalate(Clock::get()?.unix_timestamp)?;
+
+    emit!(ProposalChallengedEvent {
+        market: market.key(),
+        proposer: proposal.proposer,
+        challenger: proposal.challenger,
+        bond: proposal.bond,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    Ok(())
+}
+
+#[event]
+pub struct ProposalChallengedEvent {
+    pub market: Pubkey,
+    pub proposer: Pubkey,
+    pub challenger: Pubkey,
+    pub bond: u64,
+    pub timestamp: i64,
+}
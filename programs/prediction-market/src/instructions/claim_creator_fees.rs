@@ -0,0 +1,94 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::{self, Token, TokenAccount, Transfer};
+
+use crate::state::*;
+use crate::error::*;
+
+#[derive(Accounts)]
+pub struct ClaimCreatorFees<'info> {
+    #[account(
+        mut,
+        seeds = [b"market", market.market_id.to_le_bytes().as_ref()],
+        bump = market.bump,
+        has_one = authority @ PredictionMarketError::UnauthorizedResolution,
+        constraint = !market.is_halted(PAUSE_CLAIMS) @ PredictionMarketError::MarketPaused,
+    )]
+    pub market: Box<Account<'info, PredictionMarket>>,
+
+    #[account(
+        mut,
+        seeds = [b"market_vault", market.key().as_ref()],
+        bump = market.vault_bump,
+    )]
+    pub market_vault: Account<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        constraint = authority_token_account.mint == market.quote_mint @ PredictionMarketError::InvalidTokenMint,
+        constraint = authority_token_account.owner == authority.key() @ PredictionMarketError::InvalidTokenAccountOwner,
+    )]
+    pub authority_token_account: Account<'info, TokenAccount>,
+
+    #[account(
+        seeds = [b"global_state"],
+        bump = global_state.bump,
+        constraint = !global_state.is_halted(PAUSE_CLAIMS) @ PredictionMarketError::MarketPaused,
+    )]
+    pub global_state: Box<Account<'info, GlobalState>>,
+
+    pub authority: Signer<'info>,
+
+    pub token_program: Program<'info, Token>,
+}
+
+/// Pays the market creator every creator fee accrued so far. Fees are
+/// earned at trade time, so they can be claimed at any point in the market's
+/// life, including after a cancellation.
+pub fn claim_creator_fees(ctx: Context<ClaimCreatorFees>) -> Result<()> {
+    let market = &mut ctx.accounts.market;
+    let amount = market.creator_fees;
+
+    require!(amount > 0, PredictionMarketError::NoCreatorFeesToClaim);
+    require!(
+        ctx.accounts.market_vault.amount >= amount,
+        PredictionMarketError::InsufficientVaultFunds
+    );
+
+    let market_id_bytes = market.market_id.to_le_bytes();
+    let market_seeds = &[
+        b"market",
+        market_id_bytes.as_ref(),
+        &[market.bump],
+    ];
+    let signer_seeds = &[&market_seeds[..]];
+
+    let transfer_ctx = CpiContext::new_with_signer(
+        ctx.accounts.token_program.to_account_info(),
+        Transfer {
+            from: ctx.accounts.market_vault.to_account_info(),
+            to: ctx.accounts.authority_token_account.to_account_info(),
+            authority: market.to_account_info(),
+        },
+        signer_seeds,
+    );
+    token::transfer(transfer_ctx, amount)?;
+
+    market.creator_fees = 0;
+
+    emit!(CreatorFeesClaimedEvent {
+        market: market.key(),
+        creator: market.authority,
+        amount,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    Ok(())
+}
+
+#[event]
+pub struct CreatorFeesClaimedEvent {
+    pub market: Pubkey,
+    pub creator: Pubkey,
+    pub amount: u64,
+    pub timestamp: i64,
+}
@@ -0,0 +1,148 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::{self, Token, TokenAccount, Transfer};
+
+use crate::state::*;
+use crate::error::*;
+
+#[derive(Accounts)]
+pub struct ClaimLiquidityRewards<'info> {
+    #[account(
+        seeds = [b"market", market.market_id.to_le_bytes().as_ref()],
+        bump = market.bump,
+        constraint = !market.is_halted(PAUSE_CLAIMS) @ PredictionMarketError::MarketPaused,
+    )]
+    pub market: Box<Account<'info, PredictionMarket>>,
+
+    #[account(
+        seeds = [b"pool", market.key().as_ref()],
+        bump = pool.bump,
+    )]
+    pub pool: Box<Account<'info, LiquidityPool>>,
+
+    #[account(
+        mut,
+        seeds = [b"liquidity_rewards", market.key().as_ref()],
+        bump = liquidity_rewards.bump,
+    )]
+    pub liquidity_rewards: Box<Account<'info, LiquidityRewards>>,
+
+    #[account(
+        mut,
+        seeds = [b"reward_vault", market.key().as_ref()],
+        bump = liquidity_rewards.vault_bump,
+    )]
+    pub reward_vault: Box<Account<'info, TokenAccount>>,
+
+    #[account(
+        mut,
+        seeds = [b"liquidity_provider", market.key().as_ref(), user.key().as_ref()],
+        bump = liquidity_provider.bump,
+        constraint = liquidity_provider.user == user.key() @ PredictionMarketError::InvalidUser,
+    )]
+    pub liquidity_provider: Box<Account<'info, LiquidityProvider>>,
+
+    #[account(
+        mut,
+        constraint = user_reward_account.mint == liquidity_rewards.reward_mint @ PredictionMarketError::InvalidTokenMint,
+        constraint = user_reward_account.owner == user.key() @ PredictionMarketError::InvalidTokenAccountOwner,
+    )]
+    pub user_reward_account: Box<Account<'info, TokenAccount>>,
+
+    #[account(
+        seeds = [b"global_state"],
+        bump = global_state.bump,
+        constraint = !global_state.is_halted(PAUSE_CLAIMS) @ PredictionMarketError::MarketPaused,
+    )]
+    pub global_state: Box<Account<'info, GlobalState>>,
+
+    pub user: Signer<'info>,
+
+    pub token_program: Program<'info, Token>,
+}
+
+/// Pays a liquidity provider the mining rewards earned by their LP tokens
+/// since they last claimed. Rewards already earned stay claimable after the
+/// provider exits or the program is stopped.
+pub fn claim_liquidity_rewards(ctx: Context<ClaimLiquidityRewards>) -> Result<()> {
+    let market = &ctx.accounts.market;
+    let liquidity_rewards = &mut ctx.accounts.liquidity_rewards;
+    let liquidity_provider = &mut ctx.accounts.liquidity_provider;
+    let clock = Clock::get()?;
+
+    liquidity_rewards.accrue(clock.unix_timestamp, ctx.accounts.pool.total_supply)?;
+    liquidity_provider.settle_rewards(liquidity_rewards.reward_per_share)?;
+    let amount = liquidity_provider.rewards_owed;
+
+    require!(amount > 0, PredictionMarketError::NoLiquidityRewards);
+    require!(
+        ctx.accounts.reward_vault.amount >= amount,
+        PredictionMarketError::InsufficientVaultFunds
+    );
+
+    let market_key = market.key();
+    let rewards_seeds = &[
+        b"liquidity_rewards",
+        market_key.as_ref(),
+        &[liquidity_rewards.bump],
+    ];
+    let signer_seeds = &[&rewards_seeds[..]];
+
+    let transfer_ctx = CpiContext::new_with_signer(
+        ctx.accounts.token_program.to_account_info(),
+        Transfer {
+            from: ctx.accounts.reward_vault.to_account_info(),
+            to: ctx.accounts.user_reward_account.to_account_info(),
+            authority: liquidity_rewards.to_account_info(),
+        },
+        signer_seeds,
+    );
+    token::transfer(transfer_ctx, amount)?;
+
+    liquidity_provider.rewards_owed = 0;
+    liquidity_provider.rewards_claimed = liquidity_provider.rewards_claimed
+        .checked_add(amount)
+        .ok_or(PredictionMarketError::ArithmeticOverflow)?;
+    liquidity_rewards.total_claimed = liquidity_rewards.total_claimed
+        .checked_add(amount)
+        .ok_or(PredictionMarketError::ArithmeticOverflow)?;
+
+    emit!(LiquidityRewardsClaimedEvent {
+        market: market.key(),
+        user: ctx.accounts.user.key(),
+        reward_mint: liquidity_rewards.reward_mint,
+        amount,
+        total_claimed: liquidity_provider.rewards_claimed,
+        timestamp: clock.unix_timestamp,
+    });
+
+    Ok(())
+}
+
+/// Banks a provider's mining rewards before their LP balance changes.
+/// Instructions that mint or burn LP tokens pass the market's rewards
+/// account here; it is required once the pool has a rewards program, so a
+/// provider cannot skip the checkpoint and be paid for tokens they did not
+/// hold.
+pub fn settle_liquidity_rewards(
+    pool: &LiquidityPool,
+    liquidity_rewards: Option<&mut Box<Account<LiquidityRewards>>>,
+    liquidity_provider: &mut LiquidityProvider,
+) -> Result<()> {
+    if pool.rewards == Pubkey::default() {
+        return Ok(());
+    }
+
+    let liquidity_rewards = liquidity_rewards.ok_or(PredictionMarketError::LiquidityRewardsRequired)?;
+    liquidity_rewards.accrue(Clock::get()?.unix_timestamp, pool.total_supply)?;
+    liquidity_provider.settle_rewards(liquidity_rewards.reward_per_share)
+}
+
+#[event]
+pub struct LiquidityRewardsClaimedEvent {
+    pub market: Pubkey,
+    pub user: Pubkey,
+    pub reward_mint: Pubkey,
+    pub amount: u64,
+    pub total_claimed: u64,
+    pub timestamp: i64,
+}
@@ -0,0 +1,118 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::{self, Token, TokenAccount, Transfer};
+
+use crate::state::*;
+use crate::error::*;
+
+#[derive(Accounts)]
+pub struct ClaimLpFees<'info> {
+    #[account(
+        seeds = [b"market", market.market_id.to_le_bytes().as_ref()],
+        bump = market.bump,
+        constraint = !market.is_halted(PAUSE_CLAIMS) @ PredictionMarketError::MarketPaused,
+    )]
+    pub market: Box<Account<'info, PredictionMarket>>,
+
+    #[account(
+        mut,
+        seeds = [b"pool", market.key().as_ref()],
+        bump = pool.bump,
+    )]
+    pub pool: Box<Account<'info, LiquidityPool>>,
+
+    #[account(
+        mut,
+        seeds = [b"liquidity_provider", market.key().as_ref(), user.key().as_ref()],
+        bump = liquidity_provider.bump,
+        constraint = liquidity_provider.user == user.key() @ PredictionMarketError::InvalidUser,
+    )]
+    pub liquidity_provider: Box<Account<'info, LiquidityProvider>>,
+
+    #[account(
+        mut,
+        seeds = [b"market_vault", market.key().as_ref()],
+        bump = market.vault_bump,
+    )]
+    pub market_vault: Box<Account<'info, TokenAccount>>,
+
+    #[account(
+        mut,
+        constraint = user_token_account.mint == market.quote_mint @ PredictionMarketError::InvalidTokenMint,
+        constraint = user_token_account.owner == user.key() @ PredictionMarketError::InvalidTokenAccountOwner,
+    )]
+    pub user_token_account: Box<Account<'info, TokenAccount>>,
+
+    #[account(
+        seeds = [b"global_state"],
+        bump = global_state.bump,
+        constraint = !global_state.is_halted(PAUSE_CLAIMS) @ PredictionMarketError::MarketPaused,
+    )]
+    pub global_state: Box<Account<'info, GlobalState>>,
+
+    pub user: Signer<'info>,
+
+    pub token_program: Program<'info, Token>,
+}
+
+/// Pays a liquidity provider the trading fees earned by their LP tokens
+/// since they last claimed, without touching their liquidity.
+pub fn claim_lp_fees(ctx: Context<ClaimLpFees>) -> Result<()> {
+    let market = &ctx.accounts.market;
+    let pool = &mut ctx.accounts.pool;
+    let liquidity_provider = &mut ctx.accounts.liquidity_provider;
+
+    liquidity_provider.settle_fees(pool.fee_growth_per_share)?;
+    let amount = liquidity_provider.fees_owed;
+
+    require!(amount > 0, PredictionMarketError::NoFeesToClaim);
+    require!(
+        ctx.accounts.market_vault.amount >= amount,
+        PredictionMarketError::InsufficientVaultFunds
+    );
+
+    let market_id_bytes = market.market_id.to_le_bytes();
+    let market_seeds = &[
+        b"market",
+        market_id_bytes.as_ref(),
+        &[market.bump],
+    ];
+    let signer_seeds = &[&market_seeds[..]];
+
+    let transfer_ctx = CpiContext::new_with_signer(
+        ctx.accounts.token_program.to_account_info(),
+        Transfer {
+            from: ctx.accounts.market_vault.to_account_info(),
+            to: ctx.accounts.user_token_account.to_account_info(),
+            authority: market.to_account_info(),
+        },
+        signer_seeds,
+    );
+    token::transfer(transfer_ctx, amount)?;
+
+    liquidity_provider.fees_owed = 0;
+    liquidity_provider.fees_claimed = liquidity_provider.fees_claimed
+        .checked_add(amount)
+        .ok_or(PredictionMarketError::ArithmeticOverflow)?;
+    pool.fee_collected = pool.fee_collected
+        .checked_sub(amount)
+        .ok_or(PredictionMarketError::ArithmeticUnderflow)?;
+
+    emit!(LpFeesClaimedEvent {
+        market: market.key(),
+        user: ctx.accounts.user.key(),
+        amount,
+        total_claimed: liquidity_provider.fees_claimed,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    Ok(())
+}
+
+#[event]
+pub struct LpFeesClaimedEvent {
+    pub market: Pubkey,
+    pub user: Pubkey,
+    pub amount: u64,
+    pub total_claimed: u64,
+    pub timestamp: i64,
+}
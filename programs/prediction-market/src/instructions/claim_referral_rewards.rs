@@ -0,0 +1,85 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::{self, Token, TokenAccount, Transfer};
+
+use crate::state::*;
+use crate::error::*;
+
+#[derive(Accounts)]
+pub struct ClaimReferralRewards<'info> {
+    #[account(
+        mut,
+        seeds = [b"referrer", referrer.mint.as_ref(), authority.key().as_ref()],
+        bump = referrer.bump,
+        has_one = authority @ PredictionMarketError::InvalidUser,
+    )]
+    pub referrer: Account<'info, Referrer>,
+
+    #[account(
+        mut,
+        seeds = [b"referral_vault", referrer.mint.as_ref()],
+        bump,
+    )]
+    pub referral_vault: Account<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        constraint = authority_token_account.mint == referrer.mint @ PredictionMarketError::InvalidTokenMint,
+        constraint = authority_token_account.owner == authority.key() @ PredictionMarketError::InvalidTokenAccountOwner,
+    )]
+    pub authority_token_account: Account<'info, TokenAccount>,
+
+    #[account(
+        seeds = [b"global_state"],
+        bump = global_state.bump,
+        constraint = !global_state.is_halted(PAUSE_CLAIMS) @ PredictionMarketError::MarketPaused,
+    )]
+    pub global_state: Box<Account<'info, GlobalState>>,
+
+    pub authority: Signer<'info>,
+
+    pub token_program: Program<'info, Token>,
+}
+
+/// Pays a referrer everything it has earned and not yet claimed.
+pub fn claim_referral_rewards(ctx: Context<ClaimReferralRewards>) -> Result<()> {
+    let referrer = &mut ctx.accounts.referrer;
+    let global_state = &ctx.accounts.global_state;
+    let amount = referrer.claimable()?;
+
+    require!(amount > 0, PredictionMarketError::NoReferralRewards);
+
+    let global_state_seeds = &[b"global_state".as_ref(), &[global_state.bump]];
+    let signer_seeds = &[&global_state_seeds[..]];
+
+    let transfer_ctx = CpiContext::new_with_signer(
+        ctx.accounts.token_program.to_account_info(),
+        Transfer {
+            from: ctx.accounts.referral_vault.to_account_info(),
+            to: ctx.accounts.authority_token_account.to_account_info(),
+            authority: global_state.to_account_info(),
+        },
+        signer_seeds,
+    );
+    token::transfer(transfer_ctx, amount)?;
+
+    referrer.rewards_claimed = referrer.rewards_earned;
+
+    emit!(ReferralRewardsClaimedEvent {
+        referrer: referrer.key(),
+        authority: referrer.authority,
+        amount,
+        total_claimed: referrer.rewards_claimed,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    Ok(())
+}
+
+#[event]
+pub struct ReferralRewardsClaimedEvent {
+    pub referrer: Pubkey,
+    pub authority: Pubkey,
+    pub amount: u64,
+    pub total_claimed: u64,
+    pub timestamp: i64,
+}
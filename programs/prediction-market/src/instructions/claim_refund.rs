@@ -0,0 +1,139 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::{self, Token, TokenAccount, Transfer};
+
+use crate::state::*;
+use crate::error::*;
+
+#[derive(Accounts)]
+pub struct ClaimRefund<'info> {
+    #[account(
+        mut,
+        seeds = [b"market", market.market_id.to_le_bytes().as_ref()],
+        bump = market.bump,
+        constraint = !market.is_halted(PAUSE_CLAIMS) @ PredictionMarketError::MarketPaused,
+        constraint = market.status == MarketStatus::Cancelled @ PredictionMarketError::MarketNotCancelled,
+    )]
+    pub market: Box<Account<'info, PredictionMarket>>,
+
+    #[account(
+        mut,
+        seeds = [b"bet", market.key().as_ref(), user.key().as_ref()],
+        bump = bet.bump,
+        constraint = bet.bettor == user.key() @ PredictionMarketError::InvalidUser,
+    )]
+    pub bet: Option<Box<Account<'info, Bet>>>,
+
+    #[account(
+        mut,
+        seeds = [b"user_position", user.key().as_ref(), market.key().as_ref()],
+        bump = user_position.bump,
+        constraint = user_position.user == user.key() @ PredictionMarketError::InvalidUser,
+    )]
+    pub user_position: Option<Box<Account<'info, UserPosition>>>,
+
+    #[account(
+        mut,
+        seeds = [b"market_vault", market.key().as_ref()],
+        bump = market.vault_bump,
+    )]
+    pub vault: Box<Account<'info, TokenAccount>>,
+
+    #[account(
+        mut,
+        constraint = user_token_account.mint == market.quote_mint @ PredictionMarketError::InvalidTokenMint,
+        constraint = user_token_account.owner == user.key() @ PredictionMarketError::InvalidTokenAccountOwner,
+    )]
+    pub user_token_account: Box<Account<'info, TokenAccount>>,
+
+    #[account(
+        seeds = [b"global_state"],
+        bump = global_state.bump,
+        constraint = !global_state.is_halted(PAUSE_CLAIMS) @ PredictionMarketError::MarketPaused,
+    )]
+    pub global_state: Box<Account<'info, GlobalState>>,
+
+    pub user: Signer<'info>,
+
+    pub token_program: Program<'info, Token>,
+}
+
+/// Returns a participant's original stake in a cancelled market: the
+/// remaining parimutuel bet and the net collateral paid into a share
+/// position. Creator fees and the pool's trading fees already charged are
+/// not returned; the latter belong to the liquidity providers. Each account
+/// that is passed is refunded once. Liquidity comes back through
+/// `settle_liquidity`.
+pub fn claim_refund(ctx: Context<ClaimRefund>) -> Result<()> {
+    let mut bet_refund = 0u64;
+    if let Some(bet) = ctx.accounts.bet.as_mut() {
+        if !bet.is_claimed {
+            bet_refund = bet.amount;
+            bet.is_claimed = true;
+            bet.winnings_claimed = bet_refund;
+        }
+    }
+
+    let mut position_refund = 0u64;
+    if let Some(user_position) = ctx.accounts.user_position.as_mut() {
+        // Net cost basis: what was paid in minus what trading already
+        // returned, less the trading fees earned by the pool
+        position_refund = user_position.total_invested
+            .saturating_sub(user_position.total_withdrawn)
+            .saturating_sub(user_position.lp_fees_paid);
+        user_position.shares = [0; MAX_OUTCOMES];
+        user_position.total_withdrawn = user_position.total_withdrawn
+            .checked_add(position_refund)
+            .ok_or(PredictionMarketError::ArithmeticOverflow)?;
+        user_position.last_update_time = Clock::get()?.unix_timestamp;
+    }
+
+    let refund = bet_refund
+        .checked_add(position_refund)
+        .ok_or(PredictionMarketError::ArithmeticOverflow)?;
+
+    require!(refund > 0, PredictionMarketError::NothingToRefund);
+    require!(ctx.accounts.vault.amount >= refund, PredictionMarketError::InsufficientVaultFunds);
+
+    let market = &mut ctx.accounts.market;
+    let market_id_bytes = market.market_id.to_le_bytes();
+    let market_seeds = &[
+        b"market",
+        market_id_bytes.as_ref(),
+        &[market.bump],
+    ];
+    let signer_seeds = &[&market_seeds[..]];
+
+    let transfer_ctx = CpiContext::new_with_signer(
+        ctx.accounts.token_program.to_account_info(),
+        Transfer {
+            from: ctx.accounts.vault.to_account_info(),
+            to: ctx.accounts.user_token_account.to_account_info(),
+            authority: market.to_account_info(),
+        },
+        signer_seeds,
+    );
+    token::transfer(transfer_ctx, refund)?;
+
+    market.total_claimed = market.total_claimed
+        .checked_add(refund)
+        .ok_or(PredictionMarketError::ArithmeticOverflow)?;
+
+    emit!(RefundClaimedEvent {
+        market: market.key(),
+        user: ctx.accounts.user.key(),
+        bet_refund,
+        position_refund,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    Ok(())
+}
+
+#[event]
+pub struct RefundClaimedEvent {
+    pub market: Pubkey,
+    pub user: Pubkey,
+    pub bet_refund: u64,
+    pub position_refund: u64,
+    pub timestamp: i64,
+}
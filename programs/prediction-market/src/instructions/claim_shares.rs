@@ -0,0 +1,154 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::{self, Token, TokenAccount, Transfer};
+
+use crate::state::*;
+use crate::error::*;
+use crate::utils::{calculate_platform_fee, distribute_platform_fee, PlatformFeeAccounts};
+
+#[derive(Accounts)]
+pub struct ClaimShares<'info> {
+    #[account(
+        mut,
+        seeds = [b"market", market.market_id.to_le_bytes().as_ref()],
+        bump = market.bump,
+        constraint = !market.is_halted(PAUSE_CLAIMS) @ PredictionMarketError::MarketPaused,
+        constraint = market.status == MarketStatus::Resolved @ PredictionMarketError::MarketNotResolved,
+        constraint = market.dispute_end_time <= Clock::get()?.unix_timestamp @ PredictionMarketError::DisputeWindowOpen,
+    )]
+    pub market: Box<Account<'info, PredictionMarket>>,
+
+    #[account(
+        mut,
+        seeds = [b"user_position", user.key().as_ref(), market.key().as_ref()],
+        bump = user_position.bump,
+        constraint = user_position.user == user.key() @ PredictionMarketError::InvalidUser,
+    )]
+    pub user_position: Box<Account<'info, UserPosition>>,
+
+    #[account(
+        mut,
+        seeds = [b"market_vault", market.key().as_ref()],
+        bump = market.vault_bump,
+    )]
+    pub vault: Account<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        seeds = [b"treasury", market.quote_mint.as_ref()],
+        bump,
+    )]
+    pub treasury: Account<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        seeds = [b"referrer", market.quote_mint.as_ref(), referrer.authority.as_ref()],
+        bump = referrer.bump,
+        constraint = referrer.key() == user_position.referrer @ PredictionMarketError::ReferrerMismatch,
+    )]
+    pub referrer: Option<Box<Account<'info, Referrer>>>,
+
+    #[account(
+        mut,
+        seeds = [b"referral_vault", market.quote_mint.as_ref()],
+        bump,
+    )]
+    pub referral_vault: Option<Account<'info, TokenAccount>>,
+
+    #[account(
+        mut,
+        constraint = user_token_account.mint == market.quote_mint @ PredictionMarketError::InvalidTokenMint,
+        constraint = user_token_account.owner == user.key() @ PredictionMarketError::InvalidTokenAccountOwner,
+    )]
+    pub user_token_account: Account<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        seeds = [b"global_state"],
+        bump = global_state.bump,
+        constraint = !global_state.is_halted(PAUSE_CLAIMS) @ PredictionMarketError::MarketPaused,
+    )]
+    pub global_state: Box<Account<'info, GlobalState>>,
+
+    pub user: Signer<'info>,
+
+    pub token_program: Program<'info, Token>,
+}
+
+/// Redeems every share in the position at its settled value.
+pub fn claim_shares(ctx: Context<ClaimShares>) -> Result<()> {
+    let market = &mut ctx.accounts.market;
+    let user_position = &mut ctx.accounts.user_position;
+
+    let payout = market.position_payout(&user_position.shares)?;
+
+    require!(payout > 0, PredictionMarketError::NoWinningsToClaim);
+    require!(ctx.accounts.vault.amount >= payout, PredictionMarketError::InsufficientVaultFunds);
+
+    let market_id_bytes = market.market_id.to_le_bytes();
+    let market_seeds = &[
+        b"market",
+        market_id_bytes.as_ref(),
+        &[market.bump],
+    ];
+    let signer_seeds = &[&market_seeds[..]];
+
+    let platform_fee = calculate_platform_fee(payout, market.platform_fee_rate)?;
+    let net_payout = payout - platform_fee;
+
+    let transfer_ctx = CpiContext::new_with_signer(
+        ctx.accounts.token_program.to_account_info(),
+        Transfer {
+            from: ctx.accounts.vault.to_account_info(),
+            to: ctx.accounts.user_token_account.to_account_info(),
+            authority: market.to_account_info(),
+        },
+        signer_seeds,
+    );
+    token::transfer(transfer_ctx, net_payout)?;
+
+    // Referred positions pass part of the fee on to their referrer
+    let market_info = market.to_account_info();
+    distribute_platform_fee(
+        PlatformFeeAccounts {
+            vault: &ctx.accounts.vault,
+            market: &market_info,
+            treasury: &ctx.accounts.treasury,
+            referral_vault: ctx.accounts.referral_vault.as_ref(),
+            token_program: &ctx.accounts.token_program,
+        },
+        &mut ctx.accounts.global_state,
+        ctx.accounts.referrer.as_deref_mut(),
+        user_position.referrer,
+        ctx.accounts.user.key(),
+        platform_fee,
+        signer_seeds,
+    )?;
+
+    user_position.shares = [0; MAX_OUTCOMES];
+    user_position.total_withdrawn = user_position.total_withdrawn
+        .checked_add(net_payout)
+        .ok_or(PredictionMarketError::ArithmeticOverflow)?;
+    user_position.last_update_time = Clock::get()?.unix_timestamp;
+
+    market.total_claimed = market.total_claimed.checked_add(payout)
+        .ok_or(PredictionMarketError::ArithmeticOverflow)?;
+
+    emit!(SharesClaimedEvent {
+        market: market.key(),
+        user: ctx.accounts.user.key(),
+        amount: net_payout,
+        platform_fee,
+        timestamp: user_position.last_update_time,
+    });
+
+    Ok(())
+}
+
+#[event]
+pub struct SharesClaimedEvent {
+    pub market: Pubkey,
+    pub user: Pubkey,
+    pub amount: u64,
+    pub platform_fee: u64,
+    pub timestamp: i64,
+}
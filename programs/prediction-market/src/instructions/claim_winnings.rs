@@ -1,9 +1,9 @@
-```rust
 use anchor_lang::prelude::*;
 use anchor_spl::token::{self, Token, TokenAccount, Transfer};
 
 use crate::state::*;
-use crate::errors::*;
+use crate::error::*;
+use crate::utils::{calculate_platform_fee, distribute_platform_fee, PlatformFeeAccounts};
 
 #[derive(Accounts)]
 pub struct ClaimWinnings<'info> {
@@ -11,47 +11,72 @@ pub struct ClaimWinnings<'info> {
         mut,
         seeds = [b"market", market.market_id.to_le_bytes().as_ref()],
         bump = market.bump,
-        constraint = market.is_resolved @ PredictionMarketError::MarketNotResolved,
+        constraint = !market.is_halted(PAUSE_CLAIMS) @ PredictionMarketError::MarketPaused,
+        constraint = market.status == MarketStatus::Resolved @ PredictionMarketError::MarketNotResolved,
+        constraint = market.dispute_end_time <= Clock::get()?.unix_timestamp @ PredictionMarketError::DisputeWindowOpen,
     )]
-    pub market: Account<'info, Market>,
+    pub market: Account<'info, PredictionMarket>,
 
     #[account(
         mut,
         seeds = [
-            b"position",
+            b"bet",
             market.key().as_ref(),
             user.key().as_ref()
         ],
-        bump = position.bump,
-        constraint = position.market == market.key() @ PredictionMarketError::InvalidPosition,
-        constraint = position.user == user.key() @ PredictionMarketError::InvalidPosition,
-        constraint = !position.claimed @ PredictionMarketError::AlreadyClaimed,
+        bump = bet.bump,
+        constraint = bet.market == market.key() @ PredictionMarketError::NoPredictionFound,
+        constraint = bet.bettor == user.key() @ PredictionMarketError::NoPredictionFound,
+        constraint = !bet.is_claimed @ PredictionMarketError::WinningsAlreadyClaimed,
     )]
-    pub position: Account<'info, Position>,
+    pub bet: Account<'info, Bet>,
 
     #[account(
         mut,
-        seeds = [b"vault", market.key().as_ref()],
+        seeds = [b"market_vault", market.key().as_ref()],
         bump = market.vault_bump,
     )]
     pub vault: Account<'info, TokenAccount>,
 
     #[account(
         mut,
-        constraint = user_token_account.mint == market.token_mint @ PredictionMarketError::InvalidTokenAccount,
-        constraint = user_token_account.owner == user.key() @ PredictionMarketError::InvalidTokenAccount,
+        seeds = [b"treasury", market.quote_mint.as_ref()],
+        bump,
     )]
-    pub user_token_account: Account<'info, TokenAccount>,
+    pub treasury: Account<'info, TokenAccount>,
 
-    #[account(mut)]
-    pub user: Signer<'info>,
+    #[account(
+        mut,
+        seeds = [b"referrer", market.quote_mint.as_ref(), referrer.authority.as_ref()],
+        bump = referrer.bump,
+        constraint = referrer.key() == bet.referrer @ PredictionMarketError::ReferrerMismatch,
+    )]
+    pub referrer: Option<Box<Account<'info, Referrer>>>,
+
+    #[account(
+        mut,
+        seeds = [b"referral_vault", market.quote_mint.as_ref()],
+        bump,
+    )]
+    pub referral_vault: Option<Account<'info, TokenAccount>>,
+
+    #[account(
+        mut,
+        constraint = user_token_account.mint == market.quote_mint @ PredictionMarketError::InvalidTokenMint,
+        constraint = user_token_account.owner == user.key() @ PredictionMarketError::InvalidTokenAccountOwner,
+    )]
+    pub user_token_account: Account<'info, TokenAccount>,
 
     #[account(
-        seeds = [b"authority", market.key().as_ref()],
-        bump = market.authority_bump,
+        mut,
+        seeds = [b"global_state"],
+        bump = global_state.bump,
+        constraint = !global_state.is_halted(PAUSE_CLAIMS) @ PredictionMarketError::MarketPaused,
     )]
-    /// CHECK: This is a PDA used as authority for token transfers
-    pub vault_authority: UncheckedAccount<'info>,
+    pub global_state: Box<Account<'info, GlobalState>>,
+
+    #[account(mut)]
+    pub user: Signer<'info>,
 
     pub token_program: Program<'info, Token>,
     pub system_program: Program<'info, System>,
@@ -59,74 +84,73 @@ pub struct ClaimWinnings<'info> {
 
 pub fn claim_winnings(ctx: Context<ClaimWinnings>) -> Result<()> {
     let market = &mut ctx.accounts.market;
-    let position = &mut ctx.accounts.position;
+    let bet = &mut ctx.accounts.bet;
     let vault = &ctx.accounts.vault;
     let user_token_account = &ctx.accounts.user_token_account;
-    let vault_authority = &ctx.accounts.vault_authority;
     let token_program = &ctx.accounts.token_program;
 
-    // Check if user has a winning position
-    let is_winner = match market.outcome {
-        Some(outcome) => position.outcome == outcome,
-        None => return Err(PredictionMarketError::MarketNotResolved.into()),
-    };
-
-    require!(is_winner, PredictionMarketError::NotAWinner);
+    // Calculate the bet's share of the payout pool
+    let winnings = market.calculate_payout(bet.outcome, bet.amount)?;
 
-    // Calculate winnings
-    let total_pool = market.total_yes_amount + market.total_no_amount;
-    let winning_pool = if position.outcome {
-        market.total_yes_amount
-    } else {
-        market.total_no_amount
-    };
-
-    require!(winning_pool > 0, PredictionMarketError::InvalidWinningPool);
-
-    // Calculate user's share of the total pool
-    let winnings = (position.amount as u128)
-        .checked_mul(total_pool as u128)
-        .ok_or(PredictionMarketError::MathOverflow)?
-        .checked_div(winning_pool as u128)
-        .ok_or(PredictionMarketError::MathOverflow)? as u64;
-
-    require!(winnings > 0, PredictionMarketError::NoWinnings);
+    require!(winnings > 0, PredictionMarketError::NoWinningsToClaim);
     require!(vault.amount >= winnings, PredictionMarketError::InsufficientVaultFunds);
 
-    // Transfer winnings from vault to user
-    let market_key = market.key();
-    let authority_seeds = &[
-        b"authority",
-        market_key.as_ref(),
-        &[market.authority_bump],
+    // Transfer winnings less the platform fee from vault to user
+    let platform_fee = calculate_platform_fee(winnings, market.platform_fee_rate)?;
+    let net_winnings = winnings - platform_fee;
+
+    let market_id_bytes = market.market_id.to_le_bytes();
+    let market_seeds = &[
+        b"market",
+        market_id_bytes.as_ref(),
+        &[market.bump],
     ];
-    let signer_seeds = &[&authority_seeds[..]];
+    let signer_seeds = &[&market_seeds[..]];
 
     let transfer_ctx = CpiContext::new_with_signer(
         token_program.to_account_info(),
         Transfer {
             from: vault.to_account_info(),
             to: user_token_account.to_account_info(),
-            authority: vault_authority.to_account_info(),
+            authority: market.to_account_info(),
         },
         signer_seeds,
     );
 
-    token::transfer(transfer_ctx, winnings)?;
+    token::transfer(transfer_ctx, net_winnings)?;
+
+    // Referred positions pass part of the fee on to their referrer
+    let market_info = market.to_account_info();
+    distribute_platform_fee(
+        PlatformFeeAccounts {
+            vault,
+            market: &market_info,
+            treasury: &ctx.accounts.treasury,
+            referral_vault: ctx.accounts.referral_vault.as_ref(),
+            token_program,
+        },
+        &mut ctx.accounts.global_state,
+        ctx.accounts.referrer.as_deref_mut(),
+        bet.referrer,
+        bet.bettor,
+        platform_fee,
+        signer_seeds,
+    )?;
 
-    // Mark position as claimed
-    position.claimed = true;
-    position.winnings_claimed = winnings;
+    // Mark bet as claimed
+    bet.is_claimed = true;
+    bet.winnings_claimed = net_winnings;
 
     // Update market statistics
     market.total_claimed = market.total_claimed.checked_add(winnings)
-        .ok_or(PredictionMarketError::MathOverflow)?;
+        .ok_or(PredictionMarketError::ArithmeticOverflow)?;
 
     emit!(WinningsClaimedEvent {
         market: market.key(),
         user: ctx.accounts.user.key(),
-        amount: winnings,
-        outcome: position.outcome,
+        amount: net_winnings,
+        platform_fee,
+        outcome: bet.outcome,
         timestamp: Clock::get()?.unix_timestamp,
     });
 
@@ -138,7 +162,7 @@ pub struct WinningsClaimedEvent {
     pub market: Pubkey,
     pub user: Pubkey,
     pub amount: u64,
-    pub outcome: bool,
+    pub platform_fee: u64,
+    pub outcome: u8,
     pub timestamp: i64,
 }
-```
\ No newline at end of file
@@ -0,0 +1,60 @@
+use anchor_lang::prelude::*;
+
+use crate::state::*;
+use crate::error::*;
+
+#[derive(Accounts)]
+pub struct CloseBet<'info> {
+    #[account(
+        mut,
+        seeds = [b"bet", market.key().as_ref(), user.key().as_ref()],
+        bump = bet.bump,
+        constraint = bet.bettor == user.key() @ PredictionMarketError::InvalidUser,
+        close = user,
+    )]
+    pub bet: Account<'info, Bet>,
+
+    /// CHECK: the bet's market; may already have been closed by its creator,
+    /// in which case nothing more can be owed on the bet
+    #[account(mut, address = bet.market)]
+    pub market: UncheckedAccount<'info>,
+
+    #[account(mut)]
+    pub user: Signer<'info>,
+}
+
+/// Closes a bet that has nothing left to pay out and returns its rent to the
+/// bettor.
+pub fn close_bet(ctx: Context<CloseBet>) -> Result<()> {
+    let market_info = ctx.accounts.market.to_account_info();
+
+    if market_info.owner == &crate::ID && !market_info.data_is_empty() {
+        let mut market = PredictionMarket::try_deserialize(&mut &market_info.data.borrow()[..])?;
+        require!(
+            market.bet_settled(&ctx.accounts.bet, Clock::get()?.unix_timestamp)?,
+            PredictionMarketError::PositionNotSettled
+        );
+        // Fully sold bets were already taken off the count by `sell_bet`
+        if ctx.accounts.bet.amount > 0 {
+            market.open_positions = market.open_positions
+                .checked_sub(1)
+                .ok_or(PredictionMarketError::ArithmeticUnderflow)?;
+        }
+        market.try_serialize(&mut &mut market_info.data.borrow_mut()[..])?;
+    }
+
+    emit!(BetClosedEvent {
+        market: market_info.key(),
+        bettor: ctx.accounts.user.key(),
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    Ok(())
+}
+
+#[event]
+pub struct BetClosedEvent {
+    pub market: Pubkey,
+    pub bettor: Pubkey,
+    pub timestamp: i64,
+}
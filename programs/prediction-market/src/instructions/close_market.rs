@@ -0,0 +1,317 @@
+use anchor_lang::prelude::*;
+use anchor_lang::Discriminator;
+use anchor_spl::token::{self, Mint, Token, TokenAccount, Transfer};
+
+use crate::state::*;
+use crate::error::*;
+use crate::utils::close_program_account;
+
+#[derive(Accounts)]
+pub struct CloseMarket<'info> {
+    #[account(
+        mut,
+        seeds = [b"market", market.market_id.to_le_bytes().as_ref()],
+        bump = market.bump,
+        has_one = authority @ PredictionMarketError::UnauthorizedResolution,
+        has_one = outcome_a_mint,
+        has_one = outcome_b_mint,
+        has_one = lp_mint,
+        constraint = matches!(market.status, MarketStatus::Resolved | MarketStatus::Cancelled)
+            @ PredictionMarketError::MarketNotResolved,
+        close = authority,
+    )]
+    pub market: Box<Account<'info, PredictionMarket>>,
+
+    #[account(
+        mut,
+        seeds = [b"market_vault", market.key().as_ref()],
+        bump = market.vault_bump,
+    )]
+    pub market_vault: Box<Account<'info, TokenAccount>>,
+
+    #[account(mut)]
+    pub outcome_a_mint: Box<Account<'info, Mint>>,
+
+    #[account(mut)]
+    pub outcome_b_mint: Box<Account<'info, Mint>>,
+
+    #[account(
+        mut,
+        seeds = [b"outcome_a_vault", market.key().as_ref()],
+        bump = market.outcome_a_vault_bump,
+    )]
+    pub outcome_a_vault: Box<Account<'info, TokenAccount>>,
+
+    #[account(
+        mut,
+        seeds = [b"outcome_b_vault", market.key().as_ref()],
+        bump = market.outcome_b_vault_bump,
+    )]
+    pub outcome_b_vault: Box<Account<'info, TokenAccount>>,
+
+    #[account(mut)]
+    pub lp_mint: Box<Account<'info, Mint>>,
+
+    /// The pool's LP token escrow; required if the market has a pool
+    #[account(
+        mut,
+        seeds = [b"lp_escrow", market.key().as_ref()],
+        bump,
+    )]
+    pub lp_escrow: Option<Box<Account<'info, TokenAccount>>>,
+
+    /// CHECK: the market's order book PDA, closed here if it was ever opened
+    #[account(mut, seeds = [b"order_book", market.key().as_ref()], bump)]
+    pub order_book: UncheckedAccount<'info>,
+
+    /// CHECK: the market's liquidity pool PDA, closed here if it was ever opened
+    #[account(mut, seeds = [b"pool", market.key().as_ref()], bump)]
+    pub pool: UncheckedAccount<'info>,
+
+    /// CHECK: the market's committee vote PDA, closed here if it was ever opened
+    #[account(mut, seeds = [b"resolution_vote", market.key().as_ref()], bump)]
+    pub resolution_vote: UncheckedAccount<'info>,
+
+    /// CHECK: the market's optimistic proposal PDA, closed here if it was ever opened
+    #[account(mut, seeds = [b"proposal", market.key().as_ref()], bump)]
+    pub proposal: UncheckedAccount<'info>,
+
+    /// CHECK: the market's dispute PDA, closed here if it was ever opened
+    #[account(mut, seeds = [b"dispute", market.key().as_ref()], bump)]
+    pub dispute: UncheckedAccount<'info>,
+
+    /// CHECK: gets the proposal's rent back; checked against `proposal.proposer`
+    #[account(mut)]
+    pub proposer: Option<UncheckedAccount<'info>>,
+
+    /// CHECK: gets the dispute's rent back; checked against `dispute.challenger`
+    #[account(mut)]
+    pub challenger: Option<UncheckedAccount<'info>>,
+
+    #[account(
+        mut,
+        seeds = [b"treasury", market.quote_mint.as_ref()],
+        bump,
+    )]
+    pub treasury: Box<Account<'info, TokenAccount>>,
+
+    #[account(
+        mut,
+        seeds = [b"global_state"],
+        bump = global_state.bump,
+    )]
+    pub global_state: Box<Account<'info, GlobalState>>,
+
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    pub token_program: Program<'info, Token>,
+}
+
+impl<'info> CloseMarket<'info> {
+    /// Deserializes `account` if it was ever opened, i.e. this program owns it.
+    fn load_opened<T: AccountDeserialize>(account: &AccountInfo<'info>) -> Result<Option<T>> {
+        if account.owner != &crate::ID {
+            return Ok(None);
+        }
+        Ok(Some(T::try_deserialize(&mut &account.try_borrow_data()?[..])?))
+    }
+
+    /// Open order count of the market's order book, if it was ever opened.
+    fn open_orders(&self) -> Result<Option<u32>> {
+        if self.order_book.owner != &crate::ID {
+            return Ok(None);
+        }
+        let data = self.order_book.try_borrow_data()?;
+        let end = 8 + std::mem::size_of::<OrderBook>();
+        require!(
+            data.len() >= end && data[..8] == OrderBook::DISCRIMINATOR,
+            ErrorCode::AccountDiscriminatorMismatch
+        );
+        let order_book: &OrderBook = bytemuck::from_bytes(&data[8..end]);
+        Ok(Some(order_book.order_count))
+    }
+
+    /// Closes an opened bond record, returning its rent to `payer`, who must
+    /// be passed as `recipient`.
+    fn close_to_payer(
+        account: &AccountInfo<'info>,
+        recipient: &Option<UncheckedAccount<'info>>,
+        payer: Pubkey,
+    ) -> Result<()> {
+        let recipient = recipient
+            .as_ref()
+            .filter(|recipient| recipient.key() == payer)
+            .ok_or(PredictionMarketError::InvalidRentRecipient)?;
+        close_program_account(account, recipient)
+    }
+}
+
+/// Closes a settled market and its vaults, returning their rent to the
+/// creator. Allowed once every bet, position and order has been closed, all
+/// liquidity and outcome tokens are gone, LP and creator fees are claimed and
+/// no bond is escrowed, or unconditionally after the claim deadline. Whatever
+/// collateral is left in the vault is swept to the treasury and leftover
+/// escrowed outcome tokens are burned.
+///
+/// The order book, pool (with its LP escrow) and committee vote are closed
+/// along with it, their rent going to the creator who opened them; a
+/// proposal or dispute returns its rent to whoever posted it. A pool with a
+/// rewards program attached is left open, as is the program itself: its
+/// vault holds the sponsor's tokens in another mint, which
+/// `stop_liquidity_rewards` returns against the pool.
+pub fn close_market(ctx: Context<CloseMarket>) -> Result<()> {
+    let market = &ctx.accounts.market;
+    let now = Clock::get()?.unix_timestamp;
+
+    require!(
+        market.status == MarketStatus::Cancelled || market.dispute_end_time <= now,
+        PredictionMarketError::DisputeWindowOpen
+    );
+
+    let open_orders = ctx.accounts.open_orders()?;
+    let pool = CloseMarket::load_opened::<LiquidityPool>(&ctx.accounts.pool)?;
+    let proposal = CloseMarket::load_opened::<Proposal>(&ctx.accounts.proposal)?;
+    let dispute = CloseMarket::load_opened::<Dispute>(&ctx.accounts.dispute)?;
+
+    // Order escrow, LP funds and bonds all sit in the market vault; once the
+    // pool is drained its leftover fee dust is swept with the rest
+    let settled = market.open_positions == 0
+        && market.total_liquidity == 0
+        && market.creator_fees == 0
+        && ctx.accounts.outcome_a_mint.supply == 0
+        && ctx.accounts.outcome_b_mint.supply == 0
+        && open_orders.unwrap_or(0) == 0
+        && pool.as_ref().map(|pool| pool.is_drained()).unwrap_or(true)
+        && !proposal.as_ref().is_some_and(|proposal| {
+            matches!(proposal.status, ProposalStatus::Pending | ProposalStatus::Challenged)
+        })
+        && !dispute.as_ref().is_some_and(|dispute| dispute.status == DisputeStatus::Pending);
+    require!(
+        settled || now >= market.claim_deadline()?,
+        PredictionMarketError::MarketNotSettled
+    );
+
+    let market_id_bytes = market.market_id.to_le_bytes();
+    let market_seeds = &[
+        b"market",
+        market_id_bytes.as_ref(),
+        &[market.bump],
+    ];
+    let signer_seeds = &[&market_seeds[..]];
+
+    for (mint, vault) in [
+        (&ctx.accounts.outcome_a_mint, &ctx.accounts.outcome_a_vault),
+        (&ctx.accounts.outcome_b_mint, &ctx.accounts.outcome_b_vault),
+    ] {
+        if vault.amount > 0 {
+            let burn_ctx = CpiContext::new_with_signer(
+                ctx.accounts.token_program.to_account_info(),
+                token::Burn {
+                    mint: mint.to_account_info(),
+                    from: vault.to_account_info(),
+                    authority: market.to_account_info(),
+                },
+                signer_seeds,
+            );
+            token::burn(burn_ctx, vault.amount)?;
+        }
+    }
+
+    let dust = ctx.accounts.market_vault.amount;
+    if dust > 0 {
+        let sweep_ctx = CpiContext::new_with_signer(
+            ctx.accounts.token_program.to_account_info(),
+            Transfer {
+                from: ctx.accounts.market_vault.to_account_info(),
+                to: ctx.accounts.treasury.to_account_info(),
+                authority: market.to_account_info(),
+            },
+            signer_seeds,
+        );
+        token::transfer(sweep_ctx, dust)?;
+
+        let global_state = &mut ctx.accounts.global_state;
+        global_state.total_fees_collected = global_state.total_fees_collected
+            .checked_add(dust)
+            .ok_or(PredictionMarketError::ArithmeticOverflow)?;
+    }
+
+    for vault in [
+        &ctx.accounts.market_vault,
+        &ctx.accounts.outcome_a_vault,
+        &ctx.accounts.outcome_b_vault,
+    ] {
+        let close_ctx = CpiContext::new_with_signer(
+            ctx.accounts.token_program.to_account_info(),
+            token::CloseAccount {
+                account: vault.to_account_info(),
+                destination: ctx.accounts.authority.to_account_info(),
+                authority: market.to_account_info(),
+            },
+            signer_seeds,
+        );
+        token::close_account(close_ctx)?;
+    }
+
+    let authority = ctx.accounts.authority.to_account_info();
+    if open_orders.is_some() {
+        close_program_account(&ctx.accounts.order_book, &authority)?;
+    }
+    if pool.as_ref().is_some_and(|pool| pool.rewards == Pubkey::default()) {
+        let lp_escrow = ctx.accounts.lp_escrow
+            .as_ref()
+            .ok_or(PredictionMarketError::EscrowAccountsRequired)?;
+        if lp_escrow.amount > 0 {
+            let burn_ctx = CpiContext::new_with_signer(
+                ctx.accounts.token_program.to_account_info(),
+                token::Burn {
+                    mint: ctx.accounts.lp_mint.to_account_info(),
+                    from: lp_escrow.to_account_info(),
+                    authority: market.to_account_info(),
+                },
+                signer_seeds,
+            );
+            token::burn(burn_ctx, lp_escrow.amount)?;
+        }
+        let close_ctx = CpiContext::new_with_signer(
+            ctx.accounts.token_program.to_account_info(),
+            token::CloseAccount {
+                account: lp_escrow.to_account_info(),
+                destination: authority.clone(),
+                authority: market.to_account_info(),
+            },
+            signer_seeds,
+        );
+        token::close_account(close_ctx)?;
+        close_program_account(&ctx.accounts.pool, &authority)?;
+    }
+    if ctx.accounts.resolution_vote.owner == &crate::ID {
+        close_program_account(&ctx.accounts.resolution_vote, &authority)?;
+    }
+    if let Some(proposal) = &proposal {
+        CloseMarket::close_to_payer(&ctx.accounts.proposal, &ctx.accounts.proposer, proposal.proposer)?;
+    }
+    if let Some(dispute) = &dispute {
+        CloseMarket::close_to_payer(&ctx.accounts.dispute, &ctx.accounts.challenger, dispute.challenger)?;
+    }
+
+    emit!(MarketClosedEvent {
+        market: market.key(),
+        authority: market.authority,
+        swept: dust,
+        forced: !settled,
+        timestamp: now,
+    });
+
+    Ok(())
+}
+
+#[event]
+pub struct MarketClosedEvent {
+    pub market: Pubkey,
+    pub authority: Pubkey,
+    pub swept: u64,
+    pub forced: bool,
+    pub timestamp: i64,
+}
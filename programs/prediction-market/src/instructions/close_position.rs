@@ -0,0 +1,57 @@
+use anchor_lang::prelude::*;
+
+use crate::state::*;
+use crate::error::*;
+
+#[derive(Accounts)]
+pub struct ClosePosition<'info> {
+    #[account(
+        mut,
+        seeds = [b"user_position", user.key().as_ref(), market.key().as_ref()],
+        bump = user_position.bump,
+        constraint = user_position.user == user.key() @ PredictionMarketError::InvalidUser,
+        close = user,
+    )]
+    pub user_position: Account<'info, UserPosition>,
+
+    /// CHECK: the position's market; may already have been closed by its
+    /// creator, in which case nothing more can be owed on the position
+    #[account(mut, address = user_position.market)]
+    pub market: UncheckedAccount<'info>,
+
+    #[account(mut)]
+    pub user: Signer<'info>,
+}
+
+/// Closes a share position that has nothing left to pay out and returns its
+/// rent to the user.
+pub fn close_position(ctx: Context<ClosePosition>) -> Result<()> {
+    let market_info = ctx.accounts.market.to_account_info();
+
+    if market_info.owner == &crate::ID && !market_info.data_is_empty() {
+        let mut market = PredictionMarket::try_deserialize(&mut &market_info.data.borrow()[..])?;
+        require!(
+            market.position_settled(&ctx.accounts.user_position, Clock::get()?.unix_timestamp)?,
+            PredictionMarketError::PositionNotSettled
+        );
+        market.open_positions = market.open_positions
+            .checked_sub(1)
+            .ok_or(PredictionMarketError::ArithmeticUnderflow)?;
+        market.try_serialize(&mut &mut market_info.data.borrow_mut()[..])?;
+    }
+
+    emit!(PositionClosedEvent {
+        market: market_info.key(),
+        user: ctx.accounts.user.key(),
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    Ok(())
+}
+
+#[event]
+pub struct PositionClosedEvent {
+    pub market: Pubkey,
+    pub user: Pubkey,
+    pub timestamp: i64,
+}
@@ -0,0 +1,81 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::Mint;
+
+use crate::state::*;
+use crate::error::*;
+
+#[derive(Accounts)]
+pub struct ConfigureOptimisticOracle<'info> {
+    #[account(
+        mut,
+        seeds = [b"market", market.market_id.to_le_bytes().as_ref()],
+        bump = market.bump,
+        has_one = authority @ PredictionMarketError::UnauthorizedResolution,
+        constraint = market.status == MarketStatus::Active @ PredictionMarketError::MarketNotActive,
+        constraint = market.resolved_by_oracle() @ PredictionMarketError::ResolutionSourceAlreadySet,
+        has_one = outcome_a_mint,
+        has_one = outcome_b_mint,
+    )]
+    pub market: Box<Account<'info, PredictionMarket>>,
+
+    pub outcome_a_mint: Box<Account<'info, Mint>>,
+
+    pub outcome_b_mint: Box<Account<'info, Mint>>,
+
+    pub authority: Signer<'info>,
+}
+
+/// Switches a market to optimistic resolution: anyone may propose the result
+/// by posting `bond`, which becomes final after `liveness` seconds unless
+/// someone challenges it with a matching bond, in which case `arbiter`
+/// decides. The oracle fee is dropped since no oracle operator is involved.
+/// Like the other resolution sources, it can only be set before anyone has
+/// taken a position.
+pub fn configure_optimistic_oracle(
+    ctx: Context<ConfigureOptimisticOracle>,
+    arbiter: Pubkey,
+    bond: u64,
+    liveness: i64,
+) -> Result<()> {
+    let market = &mut ctx.accounts.market;
+
+    require!(
+        Clock::get()?.unix_timestamp < market.end_time,
+        PredictionMarketError::MarketExpired
+    );
+    require!(
+        !market.has_positions()?
+            && ctx.accounts.outcome_a_mint.supply == 0
+            && ctx.accounts.outcome_b_mint.supply == 0,
+        PredictionMarketError::MarketHasPositions
+    );
+    require!(
+        arbiter != Pubkey::default()
+            && bond > 0
+            && liveness > 0
+            && liveness <= MAX_DISPUTE_PERIOD,
+        PredictionMarketError::InvalidOptimisticConfig
+    );
+
+    market.arbiter = arbiter;
+    market.proposal_bond = bond;
+    market.proposal_liveness = liveness;
+    market.oracle_fee_bps = 0;
+
+    emit!(OptimisticOracleConfiguredEvent {
+        market: market.key(),
+        arbiter,
+        bond,
+        liveness,
+    });
+
+    Ok(())
+}
+
+#[event]
+pub struct OptimisticOracleConfiguredEvent {
+    pub market: Pubkey,
+    pub arbiter: Pubkey,
+    pub bond: u64,
+    pub liveness: i64,
+}
@@ -0,0 +1,103 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::Mint;
+
+use crate::state::*;
+use crate::error::*;
+
+#[derive(Accounts)]
+pub struct ConfigureOracleCommittee<'info> {
+    #[account(
+        mut,
+        seeds = [b"market", market.market_id.to_le_bytes().as_ref()],
+        bump = market.bump,
+        has_one = authority @ PredictionMarketError::UnauthorizedResolution,
+        constraint = market.status == MarketStatus::Active @ PredictionMarketError::MarketNotActive,
+        constraint = market.resolved_by_oracle() @ PredictionMarketError::ResolutionSourceAlreadySet,
+        has_one = outcome_a_mint,
+        has_one = outcome_b_mint,
+    )]
+    pub market: Box<Account<'info, PredictionMarket>>,
+
+    pub outcome_a_mint: Box<Account<'info, Mint>>,
+
+    pub outcome_b_mint: Box<Account<'info, Mint>>,
+
+    #[account(
+        init,
+        payer = authority,
+        space = ResolutionVote::LEN,
+        seeds = [b"resolution_vote", market.key().as_ref()],
+        bump
+    )]
+    pub resolution_vote: Box<Account<'info, ResolutionVote>>,
+
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// Hands resolution of a market to an M-of-N oracle committee. The single
+/// registered oracle can no longer resolve it, and since no one oracle does
+/// the work the market's oracle fee is dropped. A committee can only be set
+/// once, and only before anyone has taken a position, so traders never end
+/// up settled by a resolver they did not sign up for.
+pub fn configure_oracle_committee(
+    ctx: Context<ConfigureOracleCommittee>,
+    members: Vec<Pubkey>,
+    threshold: u8,
+) -> Result<()> {
+    let market = &mut ctx.accounts.market;
+    let resolution_vote = &mut ctx.accounts.resolution_vote;
+
+    require!(
+        Clock::get()?.unix_timestamp < market.end_time,
+        PredictionMarketError::MarketExpired
+    );
+    require!(
+        !market.has_positions()?
+            && ctx.accounts.outcome_a_mint.supply == 0
+            && ctx.accounts.outcome_b_mint.supply == 0,
+        PredictionMarketError::MarketHasPositions
+    );
+    require!(
+        !members.is_empty() && members.len() <= MAX_COMMITTEE_MEMBERS,
+        PredictionMarketError::InvalidCommittee
+    );
+    require!(
+        threshold > 0 && threshold as usize <= members.len(),
+        PredictionMarketError::InvalidCommittee
+    );
+    for (index, member) in members.iter().enumerate() {
+        require!(
+            !members[..index].contains(member),
+            PredictionMarketError::InvalidCommittee
+        );
+    }
+
+    resolution_vote.market = market.key();
+    resolution_vote.votes = vec![CommitteeVote::default(); members.len()];
+    resolution_vote.members = members;
+    resolution_vote.threshold = threshold;
+    resolution_vote.bump = ctx.bumps.resolution_vote;
+
+    market.resolution_committee = resolution_vote.key();
+    market.oracle_fee_bps = 0;
+
+    emit!(OracleCommitteeConfiguredEvent {
+        market: market.key(),
+        resolution_vote: resolution_vote.key(),
+        members: resolution_vote.members.clone(),
+        threshold,
+    });
+
+    Ok(())
+}
+
+#[event]
+pub struct OracleCommitteeConfiguredEvent {
+    pub market: Pubkey,
+    pub resolution_vote: Pubkey,
+    pub members: Vec<Pubkey>,
+    pub threshold: u8,
+}
@@ -0,0 +1,87 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::Mint;
+
+use crate::price_feed;
+use crate::state::*;
+use crate::error::*;
+
+#[derive(Accounts)]
+pub struct ConfigurePriceFeed<'info> {
+    #[account(
+        mut,
+        seeds = [b"market", market.market_id.to_le_bytes().as_ref()],
+        bump = market.bump,
+        has_one = authority @ PredictionMarketError::UnauthorizedResolution,
+        constraint = market.status == MarketStatus::Active @ PredictionMarketError::MarketNotActive,
+        constraint = market.market_type == MarketType::Binary @ PredictionMarketError::InvalidPriceFeedConfig,
+        constraint = market.resolved_by_oracle() @ PredictionMarketError::ResolutionSourceAlreadySet,
+        has_one = outcome_a_mint,
+        has_one = outcome_b_mint,
+    )]
+    pub market: Box<Account<'info, PredictionMarket>>,
+
+    pub outcome_a_mint: Box<Account<'info, Mint>>,
+
+    pub outcome_b_mint: Box<Account<'info, Mint>>,
+
+    /// CHECK: must be a feed `price_feed::load_price` can parse; checked in the handler
+    pub price_feed: UncheckedAccount<'info>,
+
+    pub authority: Signer<'info>,
+}
+
+/// Turns a binary market into a price threshold market: "Yes" (outcome A)
+/// wins if the feed reads at or above `strike_price` at `end_time`, "No"
+/// otherwise. The strike is in the feed's own units. Must be set before
+/// anyone has taken a position, and the market then resolves only from the
+/// feed; the oracle fee is dropped since anyone can trigger resolution.
+pub fn configure_price_feed(
+    ctx: Context<ConfigurePriceFeed>,
+    strike_price: u64,
+    max_price_staleness: i64,
+    max_price_confidence_bps: u16,
+) -> Result<()> {
+    let market = &mut ctx.accounts.market;
+
+    require!(
+        !market.has_positions()?
+            && ctx.accounts.outcome_a_mint.supply == 0
+            && ctx.accounts.outcome_b_mint.supply == 0,
+        PredictionMarketError::MarketHasPositions
+    );
+    require!(
+        strike_price > 0
+            && max_price_staleness > 0
+            && max_price_confidence_bps > 0
+            && max_price_confidence_bps <= 10000,
+        PredictionMarketError::InvalidPriceFeedConfig
+    );
+
+    // Fail early if the feed is not one we know how to read
+    price_feed::load_price(&ctx.accounts.price_feed.to_account_info())?;
+
+    market.price_feed = ctx.accounts.price_feed.key();
+    market.strike_price = strike_price;
+    market.max_price_staleness = max_price_staleness;
+    market.max_price_confidence_bps = max_price_confidence_bps;
+    market.oracle_fee_bps = 0;
+
+    emit!(PriceFeedConfiguredEvent {
+        market: market.key(),
+        price_feed: market.price_feed,
+        strike_price,
+        max_price_staleness,
+        max_price_confidence_bps,
+    });
+
+    Ok(())
+}
+
+#[event]
+pub struct PriceFeedConfiguredEvent {
+    pub market: Pubkey,
+    pub price_feed: Pubkey,
+    pub strike_price: u64,
+    pub max_price_staleness: i64,
+    pub max_price_confidence_bps: u16,
+}
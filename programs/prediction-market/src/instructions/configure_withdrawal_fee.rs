@@ -0,0 +1,78 @@
+use anchor_lang::prelude::*;
+
+use crate::state::*;
+use crate::error::*;
+
+#[derive(Accounts)]
+pub struct ConfigureWithdrawalFee<'info> {
+    #[account(
+        seeds = [b"market", market.market_id.to_le_bytes().as_ref()],
+        bump = market.bump,
+        has_one = authority @ PredictionMarketError::UnauthorizedResolution,
+        constraint = market.status == MarketStatus::Active @ PredictionMarketError::MarketNotActive,
+        constraint = market.pricing_model == PricingModel::ConstantProduct @ PredictionMarketError::InvalidPricingModel,
+    )]
+    pub market: Box<Account<'info, PredictionMarket>>,
+
+    #[account(
+        mut,
+        seeds = [b"pool", market.key().as_ref()],
+        bump = pool.bump,
+    )]
+    pub pool: Box<Account<'info, LiquidityPool>>,
+
+    pub authority: Signer<'info>,
+}
+
+/// Sets the pool's withdrawal fee curve: `base_rate` applies until
+/// `ramp_period` seconds before `resolution_time`, after which the fee rises
+/// linearly to `max_rate`. With `lock_after_cutoff` set, liquidity cannot be
+/// withdrawn at all once trading closes and must wait for settlement. Only
+/// allowed while the creator is the sole provider, so nobody else joins under
+/// one set of terms and leaves under another.
+pub fn configure_withdrawal_fee(
+    ctx: Context<ConfigureWithdrawalFee>,
+    params: WithdrawalFeeParams,
+) -> Result<()> {
+    let market = &ctx.accounts.market;
+    let pool = &mut ctx.accounts.pool;
+
+    require!(
+        market.liquidity_providers_count <= 1,
+        PredictionMarketError::WithdrawalFeeLocked
+    );
+    require!(
+        params.base_rate <= params.max_rate && params.max_rate <= 10000,
+        PredictionMarketError::InvalidFeePercentage
+    );
+    require!(
+        params.ramp_period >= 0,
+        PredictionMarketError::InvalidWithdrawalFeeConfig
+    );
+
+    pool.withdrawal_fee_rate = params.base_rate;
+    pool.max_withdrawal_fee_rate = params.max_rate;
+    pool.withdrawal_fee_ramp = params.ramp_period;
+    pool.lock_after_cutoff = params.lock_after_cutoff;
+
+    emit!(WithdrawalFeeConfiguredEvent {
+        market: market.key(),
+        pool: pool.key(),
+        base_rate: params.base_rate,
+        max_rate: params.max_rate,
+        ramp_period: params.ramp_period,
+        lock_after_cutoff: params.lock_after_cutoff,
+    });
+
+    Ok(())
+}
+
+#[event]
+pub struct WithdrawalFeeConfiguredEvent {
+    pub market: Pubkey,
+    pub pool: Pubkey,
+    pub base_rate: u16,
+    pub max_rate: u16,
+    pub ramp_period: i64,
+    pub lock_after_cutoff: bool,
+}
@@ -1,6 +1,9 @@
-```rust
 use anchor_lang::prelude::*;
-use anchor_spl::token::{self, Token, TokenAccount, Mint};
+use anchor_spl::token::{Mint, Token, TokenAccount};
+
+use crate::state::*;
+use crate::error::*;
+use crate::utils::validate_market_parameters;
 
 #[derive(Accounts)]
 #[instruction(market_id: u64)]
@@ -8,11 +11,11 @@ pub struct CreateMarket<'info> {
     #[account(
         init,
         payer = authority,
-        space = 8 + PredictionMarket::INIT_SPACE,
+        space = PredictionMarket::LEN,
         seeds = [b"market", market_id.to_le_bytes().as_ref()],
         bump
     )]
-    pub market: Account<'info, PredictionMarket>,
+    pub market: Box<Account<'info, PredictionMarket>>,
 
     #[account(
         init,
@@ -22,7 +25,27 @@ pub struct CreateMarket<'info> {
         seeds = [b"market_vault", market.key().as_ref()],
         bump
     )]
-    pub market_vault: Account<'info, TokenAccount>,
+    pub market_vault: Box<Account<'info, TokenAccount>>,
+
+    #[account(
+        init,
+        payer = authority,
+        mint::decimals = quote_mint.decimals,
+        mint::authority = market,
+        seeds = [b"outcome_a_mint", market.key().as_ref()],
+        bump
+    )]
+    pub outcome_a_mint: Box<Account<'info, Mint>>,
+
+    #[account(
+        init,
+        payer = authority,
+        mint::decimals = quote_mint.decimals,
+        mint::authority = market,
+        seeds = [b"outcome_b_mint", market.key().as_ref()],
+        bump
+    )]
+    pub outcome_b_mint: Box<Account<'info, Mint>>,
 
     #[account(
         init,
@@ -32,7 +55,7 @@ pub struct CreateMarket<'info> {
         seeds = [b"outcome_a_vault", market.key().as_ref()],
         bump
     )]
-    pub outcome_a_vault: Account<'info, TokenAccount>,
+    pub outcome_a_vault: Box<Account<'info, TokenAccount>>,
 
     #[account(
         init,
@@ -42,29 +65,56 @@ pub struct CreateMarket<'info> {
         seeds = [b"outcome_b_vault", market.key().as_ref()],
         bump
     )]
-    pub outcome_b_vault: Account<'info, TokenAccount>,
+    pub outcome_b_vault: Box<Account<'info, TokenAccount>>,
 
     #[account(
         init,
         payer = authority,
         mint::decimals = quote_mint.decimals,
         mint::authority = market,
-        seeds = [b"outcome_a_mint", market.key().as_ref()],
+        seeds = [b"lp_mint", market.key().as_ref()],
         bump
     )]
-    pub outcome_a_mint: Account<'info, Mint>,
+    pub lp_mint: Box<Account<'info, Mint>>,
 
+    pub quote_mint: Box<Account<'info, Mint>>,
+
+    /// Opened with the first market in `quote_mint` if the admin hasn't
+    /// already, so fee-charging payouts never wait on it
     #[account(
-        init,
+        init_if_needed,
         payer = authority,
-        mint::decimals = quote_mint.decimals,
-        mint::authority = market,
-        seeds = [b"outcome_b_mint", market.key().as_ref()],
-        bump
+        seeds = [b"treasury", quote_mint.key().as_ref()],
+        bump,
+        token::mint = quote_mint,
+        token::authority = global_state,
+    )]
+    pub treasury: Box<Account<'info, TokenAccount>>,
+
+    #[account(
+        init_if_needed,
+        payer = authority,
+        seeds = [b"referral_vault", quote_mint.key().as_ref()],
+        bump,
+        token::mint = quote_mint,
+        token::authority = global_state,
+    )]
+    pub referral_vault: Box<Account<'info, TokenAccount>>,
+
+    #[account(
+        seeds = [b"oracle", oracle_account.oracle_id.to_le_bytes().as_ref()],
+        bump = oracle_account.bump,
+        constraint = oracle_account.is_active @ PredictionMarketError::OracleInactive,
     )]
-    pub outcome_b_mint: Account<'info, Mint>,
+    pub oracle_account: Box<Account<'info, OracleAccount>>,
 
-    pub quote_mint: Account<'info, Mint>,
+    #[account(
+        mut,
+        seeds = [b"global_state"],
+        bump = global_state.bump,
+        constraint = !global_state.is_paused @ PredictionMarketError::MarketPaused,
+    )]
+    pub global_state: Box<Account<'info, GlobalState>>,
 
     #[account(mut)]
     pub authority: Signer<'info>,
@@ -74,119 +124,194 @@ pub struct CreateMarket<'info> {
     pub rent: Sysvar<'info, Rent>,
 }
 
-#[account]
-#[derive(InitSpace)]
-pub struct PredictionMarket {
-    pub authority: Pubkey,
-    pub market_id: u64,
-    pub question: String,
-    pub description: String,
-    pub quote_mint: Pubkey,
-    pub outcome_a_mint: Pubkey,
-    pub outcome_b_mint: Pubkey,
-    pub market_vault: Pubkey,
-    pub outcome_a_vault: Pubkey,
-    pub outcome_b_vault: Pubkey,
-    pub resolution_time: i64,
-    pub creation_time: i64,
-    pub total_liquidity: u64,
-    pub outcome_a_price: u64,
-    pub outcome_b_price: u64,
-    pub is_resolved: bool,
-    pub winning_outcome: Option<u8>,
-    pub market_vault_bump: u8,
-    pub outcome_a_vault_bump: u8,
-    pub outcome_b_vault_bump: u8,
-    pub outcome_a_mint_bump: u8,
-    pub outcome_b_mint_bump: u8,
-    pub bump: u8,
-}
-
 pub fn create_market(
     ctx: Context<CreateMarket>,
     market_id: u64,
-    question: String,
-    description: String,
-    resolution_time: i64,
+    params: MarketParams,
 ) -> Result<()> {
     let market = &mut ctx.accounts.market;
     let clock = Clock::get()?;
 
     require!(
-        resolution_time > clock.unix_timestamp,
+        params.end_time > clock.unix_timestamp,
+        PredictionMarketError::InvalidResolutionTime
+    );
+
+    require!(
+        params.resolution_time >= params.end_time,
         PredictionMarketError::InvalidResolutionTime
     );
 
+    let global_state = &mut ctx.accounts.global_state;
+    validate_market_parameters(
+        params.resolution_time,
+        clock.unix_timestamp,
+        global_state.min_resolution_time,
+        global_state.max_resolution_time,
+    )?;
+
+    require!(
+        params.title.len() <= MAX_TITLE_LEN,
+        PredictionMarketError::MarketTitleTooLong
+    );
+
+    require!(
+        params.description.len() <= MAX_DESCRIPTION_LEN,
+        PredictionMarketError::MarketDescriptionTooLong
+    );
+
+    require!(
+        params.category.len() <= MAX_CATEGORY_LEN,
+        PredictionMarketError::MarketCategoryTooLong
+    );
+
+    require!(
+        params.dispute_period >= 0 && params.dispute_period <= MAX_DISPUTE_PERIOD,
+        PredictionMarketError::InvalidDisputePeriod
+    );
+
     require!(
-        question.len() <= 200,
-        PredictionMarketError::QuestionTooLong
+        params.fee_rate <= global_state.max_creator_fee_rate,
+        PredictionMarketError::CreatorFeeTooHigh
     );
+    require!(
+        params.oracle_fee_bps <= 10000,
+        PredictionMarketError::InvalidFeePercentage
+    );
+
+    let outcome_labels = match params.market_type {
+        MarketType::Binary if params.outcome_labels.is_empty() => {
+            vec!["Yes".to_string(), "No".to_string()]
+        }
+        MarketType::Binary => {
+            require!(
+                params.outcome_labels.len() == 2,
+                PredictionMarketError::InvalidOutcomeCount
+            );
+            params.outcome_labels
+        }
+        MarketType::Categorical => {
+            require!(
+                params.outcome_labels.len() >= 2 && params.outcome_labels.len() <= MAX_OUTCOMES,
+                PredictionMarketError::InvalidOutcomeCount
+            );
+            params.outcome_labels
+        }
+        MarketType::Scalar => {
+            require!(
+                params.outcome_labels.is_empty(),
+                PredictionMarketError::InvalidOutcomeCount
+            );
+            vec!["Long".to_string(), "Short".to_string()]
+        }
+    };
+
+    if params.market_type == MarketType::Scalar {
+        require!(
+            params.lower_bound < params.upper_bound,
+            PredictionMarketError::InvalidScalarBounds
+        );
+    } else {
+        require!(
+            params.lower_bound == 0 && params.upper_bound == 0,
+            PredictionMarketError::InvalidScalarBounds
+        );
+    }
 
     require!(
-        description.len() <= 500,
-        PredictionMarketError::DescriptionTooLong
+        outcome_labels
+            .iter()
+            .all(|label| !label.is_empty() && label.len() <= MAX_OUTCOME_LABEL_LEN),
+        PredictionMarketError::InvalidOutcomeLabel
     );
 
+    let initial_price = PRICE_PRECISION / outcome_labels.len() as u64;
+    let outcomes = outcome_labels
+        .into_iter()
+        .map(|label| MarketOutcome {
+            label,
+            pool: 0,
+            shares: 0,
+            price: initial_price,
+        })
+        .collect();
+
     market.authority = ctx.accounts.authority.key();
     market.market_id = market_id;
-    market.question = question;
-    market.description = description;
+    market.title = params.title;
+    market.description = params.description;
+    market.category = params.category;
+    market.oracle = ctx.accounts.oracle_account.authority;
+    market.oracle_account = ctx.accounts.oracle_account.key();
+    market.resolution_committee = Pubkey::default();
+    market.price_feed = Pubkey::default();
+    market.strike_price = 0;
+    market.max_price_staleness = 0;
+    market.max_price_confidence_bps = 0;
+    market.arbiter = Pubkey::default();
+    market.proposal_bond = 0;
+    market.proposal_liveness = 0;
     market.quote_mint = ctx.accounts.quote_mint.key();
+    market.market_vault = ctx.accounts.market_vault.key();
     market.outcome_a_mint = ctx.accounts.outcome_a_mint.key();
     market.outcome_b_mint = ctx.accounts.outcome_b_mint.key();
-    market.market_vault = ctx.accounts.market_vault.key();
     market.outcome_a_vault = ctx.accounts.outcome_a_vault.key();
     market.outcome_b_vault = ctx.accounts.outcome_b_vault.key();
-    market.resolution_time = resolution_time;
+    market.lp_mint = ctx.accounts.lp_mint.key();
+    market.end_time = params.end_time;
+    market.resolution_time = params.resolution_time;
     market.creation_time = clock.unix_timestamp;
+    market.resolution_timestamp = 0;
+    market.dispute_period = params.dispute_period;
+    market.dispute_end_time = 0;
+    market.arbitration_deadline = 0;
     market.total_liquidity = 0;
-    market.outcome_a_price = 50_000_000; // 0.5 with 8 decimals
-    market.outcome_b_price = 50_000_000; // 0.5 with 8 decimals
-    market.is_resolved = false;
+    market.liquidity_providers_count = 0;
+    market.market_type = params.market_type;
+    market.outcomes = outcomes;
+    market.lower_bound = params.lower_bound;
+    market.upper_bound = params.upper_bound;
+    market.pricing_model = PricingModel::Parimutuel;
+    market.lmsr_liquidity = 0;
+    market.lmsr_subsidy = 0;
+    market.total_bets = 0;
+    market.open_positions = 0;
+    market.winning_pool = 0;
+    market.total_payout_pool = 0;
+    market.total_claimed = 0;
+    market.creator_fees = 0;
+    market.forfeited_stakes = 0;
+    market.fee_rate = params.fee_rate;
+    market.platform_fee_rate = global_state.platform_fee_rate;
+    market.exit_fee_rate = global_state.exit_fee_rate;
+    market.oracle_fee_bps = params.oracle_fee_bps;
+    market.status = MarketStatus::Active;
+    market.paused_classes = 0;
     market.winning_outcome = None;
+    market.resolution_price = None;
+    market.oracle_data = Vec::new();
+    market.bump = ctx.bumps.market;
+    market.vault_bump = ctx.bumps.market_vault;
+    market.outcome_a_mint_bump = ctx.bumps.outcome_a_mint;
+    market.outcome_b_mint_bump = ctx.bumps.outcome_b_mint;
+    market.outcome_a_vault_bump = ctx.bumps.outcome_a_vault;
+    market.outcome_b_vault_bump = ctx.bumps.outcome_b_vault;
+    market.lp_mint_bump = ctx.bumps.lp_mint;
 
-    let (_, market_vault_bump) = Pubkey::find_program_address(
-        &[b"market_vault", market.key().as_ref()],
-        ctx.program_id
-    );
-    market.market_vault_bump = market_vault_bump;
-
-    let (_, outcome_a_vault_bump) = Pubkey::find_program_address(
-        &[b"outcome_a_vault", market.key().as_ref()],
-        ctx.program_id
-    );
-    market.outcome_a_vault_bump = outcome_a_vault_bump;
-
-    let (_, outcome_b_vault_bump) = Pubkey::find_program_address(
-        &[b"outcome_b_vault", market.key().as_ref()],
-        ctx.program_id
-    );
-    market.outcome_b_vault_bump = outcome_b_vault_bump;
-
-    let (_, outcome_a_mint_bump) = Pubkey::find_program_address(
-        &[b"outcome_a_mint", market.key().as_ref()],
-        ctx.program_id
-    );
-    market.outcome_a_mint_bump = outcome_a_mint_bump;
-
-    let (_, outcome_b_mint_bump) = Pubkey::find_program_address(
-        &[b"outcome_b_mint", market.key().as_ref()],
-        ctx.program_id
-    );
-    market.outcome_b_mint_bump = outcome_b_mint_bump;
-
-    let (_, bump) = Pubkey::find_program_address(
-        &[b"market", market_id.to_le_bytes().as_ref()],
-        ctx.program_id
-    );
-    market.bump = bump;
+    global_state.total_markets = global_state.total_markets
+        .checked_add(1)
+        .ok_or(PredictionMarketError::ArithmeticOverflow)?;
 
     emit!(MarketCreated {
         market: market.key(),
         authority: market.authority,
         market_id,
-        question: market.question.clone(),
-        resolution_time,
+        title: market.title.clone(),
+        oracle_account: market.oracle_account,
+        market_type: market.market_type,
+        outcome_count: market.outcomes.len() as u8,
+        end_time: market.end_time,
+        resolution_time: market.resolution_time,
         creation_time: market.creation_time,
     });
 
@@ -198,18 +323,11 @@ pub struct MarketCreated {
     pub market: Pubkey,
     pub authority: Pubkey,
     pub market_id: u64,
-    pub question: String,
+    pub title: String,
+    pub oracle_account: Pubkey,
+    pub market_type: MarketType,
+    pub outcome_count: u8,
+    pub end_time: i64,
     pub resolution_time: i64,
     pub creation_time: i64,
 }
-
-#[error_code]
-pub enum PredictionMarketError {
-    #[msg("Invalid resolution time")]
-    InvalidResolutionTime,
-    #[msg("Question too long")]
-    QuestionTooLong,
-    #[msg("Description too long")]
-    DescriptionTooLong,
-}
-```
\ No newline at end of file
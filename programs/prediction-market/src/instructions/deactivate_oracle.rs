@@ -0,0 +1,61 @@
+use anchor_lang::prelude::*;
+
+use crate::state::*;
+use crate::error::*;
+
+#[derive(Accounts)]
+pub struct DeactivateOracle<'info> {
+    #[account(
+        mut,
+        seeds = [b"oracle", oracle_account.oracle_id.to_le_bytes().as_ref()],
+        bump = oracle_account.bump,
+        constraint = oracle_account.is_active @ PredictionMarketError::OracleInactive,
+    )]
+    pub oracle_account: Account<'info, OracleAccount>,
+
+    /// Program admin; lets the platform retire a misbehaving oracle
+    #[account(
+        seeds = [b"global_state"],
+        bump = global_state.bump,
+    )]
+    pub global_state: Option<Account<'info, GlobalState>>,
+
+    pub signer: Signer<'info>,
+}
+
+/// Retires an oracle so it can no longer be picked for new markets or
+/// resolve existing ones. Either the oracle itself or the program admin can
+/// deactivate it; markets it was assigned to can still be cancelled.
+pub fn deactivate_oracle(ctx: Context<DeactivateOracle>) -> Result<()> {
+    let oracle_account = &mut ctx.accounts.oracle_account;
+    let signer = ctx.accounts.signer.key();
+
+    let is_admin = ctx
+        .accounts
+        .global_state
+        .as_ref()
+        .is_some_and(|global_state| global_state.authority == signer);
+    require!(
+        signer == oracle_account.authority || is_admin,
+        PredictionMarketError::InvalidOracleAuthority
+    );
+
+    oracle_account.is_active = false;
+
+    emit!(OracleDeactivatedEvent {
+        oracle_account: oracle_account.key(),
+        deactivated_by: signer,
+        reputation_score: oracle_account.reputation_score,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    Ok(())
+}
+
+#[event]
+pub struct OracleDeactivatedEvent {
+    pub oracle_account: Pubkey,
+    pub deactivated_by: Pubkey,
+    pub reputation_score: u64,
+    pub timestamp: i64,
+}
@@ -0,0 +1,156 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::{self, Token, TokenAccount, Transfer};
+
+use crate::state::*;
+use crate::error::*;
+
+#[derive(Accounts)]
+pub struct DisputeResolution<'info> {
+    #[account(
+        mut,
+        seeds = [b"market", market.market_id.to_le_bytes().as_ref()],
+        bump = market.bump,
+        constraint = market.status == MarketStatus::Resolved @ PredictionMarketError::MarketNotResolved,
+        constraint = market.dispute_end_time > Clock::get()?.unix_timestamp @ PredictionMarketError::DisputeWindowClosed,
+    )]
+    pub market: Box<Account<'info, PredictionMarket>>,
+
+    #[account(
+        seeds = [b"global_state"],
+        bump = global_state.bump,
+    )]
+    pub global_state: Box<Account<'info, GlobalState>>,
+
+    #[account(
+        init,
+        payer = challenger,
+        space = Dispute::LEN,
+        seeds = [b"dispute", market.key().as_ref()],
+        bump
+    )]
+    pub dispute: Box<Account<'info, Dispute>>,
+
+    #[account(
+        mut,
+        seeds = [b"market_vault", market.key().as_ref()],
+        bump = market.vault_bump,
+    )]
+    pub vault: Box<Account<'info, TokenAccount>>,
+
+    #[account(
+        mut,
+        constraint = challenger_token_account.mint == market.quote_mint @ PredictionMarketError::InvalidTokenMint,
+        constraint = challenger_token_account.owner == challenger.key() @ PredictionMarketError::InvalidTokenAccountOwner,
+    )]
+    pub challenger_token_account: Box<Account<'info, TokenAccount>>,
+
+    /// The challenger's parimutuel stake, if that is what they hold
+    #[account(
+        seeds = [b"bet", market.key().as_ref(), challenger.key().as_ref()],
+        bump = bet.bump,
+    )]
+    pub bet: Option<Box<Account<'info, Bet>>>,
+
+    /// The challenger's share position, if that is what they hold
+    #[account(
+        seeds = [b"user_position", challenger.key().as_ref(), market.key().as_ref()],
+        bump = user_position.bump,
+    )]
+    pub user_position: Option<Box<Account<'info, UserPosition>>>,
+
+    /// The challenger's liquidity, if that is what they hold
+    #[account(
+        seeds = [b"liquidity_provider", market.key().as_ref(), challenger.key().as_ref()],
+        bump = liquidity_provider.bump,
+    )]
+    pub liquidity_provider: Option<Box<Account<'info, LiquidityProvider>>>,
+
+    /// The challenger's outcome tokens, if that is what they hold
+    #[account(
+        constraint = [market.outcome_a_mint, market.outcome_b_mint].contains(&holding_account.mint)
+            @ PredictionMarketError::InvalidTokenMint,
+        constraint = holding_account.owner == challenger.key() @ PredictionMarketError::InvalidTokenAccountOwner,
+    )]
+    pub holding_account: Option<Box<Account<'info, TokenAccount>>>,
+
+    #[account(mut)]
+    pub challenger: Signer<'info>,
+
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+}
+
+/// Challenges a resolution during its dispute window by posting the bond
+/// configured in `GlobalState`. Only participants may dispute, shown by
+/// passing a bet, share position, liquidity or outcome tokens they hold in
+/// the market. The market is frozen as `Disputed` until the
+/// arbitrator rules, or is cancelled if they have not ruled by the
+/// arbitration deadline; each market can be disputed once.
+pub fn dispute_resolution(ctx: Context<DisputeResolution>, params: ResolutionParams) -> Result<()> {
+    let market = &mut ctx.accounts.market;
+    let dispute = &mut ctx.accounts.dispute;
+    let bond = ctx.accounts.global_state.dispute_bond;
+    let clock = Clock::get()?;
+
+    require!(bond > 0, PredictionMarketError::InvalidAmount);
+    let is_holder = ctx.accounts.bet.as_ref().is_some_and(|bet| bet.amount > 0)
+        || ctx.accounts.user_position
+            .as_ref()
+            .is_some_and(|position| position.shares.iter().any(|shares| *shares > 0))
+        || ctx.accounts.liquidity_provider.as_ref().is_some_and(|provider| provider.lp_tokens > 0)
+        || ctx.accounts.holding_account.as_ref().is_some_and(|account| account.amount > 0);
+    require!(is_holder, PredictionMarketError::NotAMarketParticipant);
+    if market.market_type != MarketType::Scalar {
+        require!(market.is_valid_outcome(params.outcome), PredictionMarketError::InvalidOutcome);
+    }
+    require!(
+        !market.matches_resolution(&params),
+        PredictionMarketError::DisputeMatchesResolution
+    );
+
+    // Escrow the bond in the market vault
+    let transfer_ctx = CpiContext::new(
+        ctx.accounts.token_program.to_account_info(),
+        Transfer {
+            from: ctx.accounts.challenger_token_account.to_account_info(),
+            to: ctx.accounts.vault.to_account_info(),
+            authority: ctx.accounts.challenger.to_account_info(),
+        },
+    );
+    token::transfer(transfer_ctx, bond)?;
+
+    dispute.market = market.key();
+    dispute.challenger = ctx.accounts.challenger.key();
+    dispute.bond = bond;
+    dispute.original_outcome = market.winning_outcome;
+    dispute.original_price = market.resolution_price;
+    dispute.proposed_outcome = params.outcome;
+    dispute.proposed_price = params.resolution_price;
+    dispute.created_at = clock.unix_timestamp;
+    dispute.resolved_at = 0;
+    dispute.status = DisputeStatus::Pending;
+    dispute.bump = ctx.bumps.dispute;
+
+    market.escalate(clock.unix_timestamp)?;
+
+    emit!(ResolutionDisputedEvent {
+        market: market.key(),
+        challenger: dispute.challenger,
+        bond,
+        proposed_outcome: params.outcome,
+        proposed_price: params.resolution_price,
+        timestamp: clock.unix_timestamp,
+    });
+
+    Ok(())
+}
+
+#[event]
+pub struct ResolutionDisputedEvent {
+    pub market: Pubkey,
+    pub challenger: Pubkey,
+    pub bond: u64,
+    pub proposed_outcome: u8,
+    pub proposed_price: u64,
+    pub timestamp: i64,
+}
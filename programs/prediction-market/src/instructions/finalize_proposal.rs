@@ -0,0 +1,87 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::{self, Token, TokenAccount, Transfer};
+
+use crate::instructions::resolve_market::MarketResolvedEvent;
+use crate::state::*;
+use crate::error::*;
+
+#[derive(Accounts)]
+pub struct FinalizeProposal<'info> {
+    #[account(
+        mut,
+        seeds = [b"market", market.market_id.to_le_bytes().as_ref()],
+        bump = market.bump,
+        constraint = market.status == MarketStatus::Active @ PredictionMarketError::MarketNotActive,
+    )]
+    pub market: Box<Account<'info, PredictionMarket>>,
+
+    #[account(
+        mut,
+        seeds = [b"proposal", market.key().as_ref()],
+        bump = proposal.bump,
+        constraint = proposal.status == ProposalStatus::Pending @ PredictionMarketError::ProposalNotPending,
+        constraint = proposal.liveness_end <= Clock::get()?.unix_timestamp @ PredictionMarketError::LivenessNotExpired,
+    )]
+    pub proposal: Box<Account<'info, Proposal>>,
+
+    #[account(
+        mut,
+        seeds = [b"market_vault", market.key().as_ref()],
+        bump = market.vault_bump,
+    )]
+    pub vault: Box<Account<'info, TokenAccount>>,
+
+    #[account(
+        mut,
+        constraint = proposer_token_account.mint == market.quote_mint @ PredictionMarketError::InvalidTokenMint,
+        constraint = proposer_token_account.owner == proposal.proposer @ PredictionMarketError::InvalidTokenAccountOwner,
+    )]
+    pub proposer_token_account: Box<Account<'info, TokenAccount>>,
+
+    pub token_program: Program<'info, Token>,
+}
+
+/// Permissionlessly settles the market on an unchallenged proposal once its
+/// liveness window has passed and returns the proposer's bond. The liveness
+/// window already served as the dispute period, so claims open at once.
+pub fn finalize_proposal(ctx: Context<FinalizeProposal>) -> Result<()> {
+    let market = &mut ctx.accounts.market;
+    let proposal = &mut ctx.accounts.proposal;
+    let now = Clock::get()?.unix_timestamp;
+
+    let (winning_pool, losing_pool) = market.settle(&proposal.params(), 0, now)?;
+    market.dispute_end_time = now;
+    proposal.status = ProposalStatus::Accepted;
+
+    let market_id_bytes = market.market_id.to_le_bytes();
+    let market_seeds = &[
+        b"market",
+        market_id_bytes.as_ref(),
+        &[market.bump],
+    ];
+    let signer_seeds = &[&market_seeds[..]];
+
+    let transfer_ctx = CpiContext::new_with_signer(
+        ctx.accounts.token_program.to_account_info(),
+        Transfer {
+            from: ctx.accounts.vault.to_account_info(),
+            to: ctx.accounts.proposer_token_account.to_account_info(),
+            authority: market.to_account_info(),
+        },
+        signer_seeds,
+    );
+    token::transfer(transfer_ctx, proposal.bond)?;
+
+    emit!(MarketResolvedEvent {
+        market: market.key(),
+        outcome: market.winning_outcome,
+        resolution_price: market.resolution_price,
+        winning_pool,
+        losing_pool,
+        oracle_fee: 0,
+        resolution_timestamp: market.resolution_timestamp,
+        dispute_end_time: market.dispute_end_time,
+    });
+
+    Ok(())
+}
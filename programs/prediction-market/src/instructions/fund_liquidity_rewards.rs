@@ -0,0 +1,145 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::{self, Mint, Token, TokenAccount, Transfer};
+
+use crate::state::*;
+use crate::error::*;
+
+#[derive(Accounts)]
+pub struct FundLiquidityRewards<'info> {
+    #[account(
+        seeds = [b"market", market.market_id.to_le_bytes().as_ref()],
+        bump = market.bump,
+        constraint = !market.is_halted(PAUSE_LIQUIDITY) @ PredictionMarketError::MarketPaused,
+        constraint = market.status == MarketStatus::Active @ PredictionMarketError::MarketNotActive,
+        constraint = market.pricing_model == PricingModel::ConstantProduct @ PredictionMarketError::InvalidPricingModel,
+    )]
+    pub market: Box<Account<'info, PredictionMarket>>,
+
+    #[account(
+        mut,
+        seeds = [b"pool", market.key().as_ref()],
+        bump = pool.bump,
+    )]
+    pub pool: Box<Account<'info, LiquidityPool>>,
+
+    #[account(
+        init,
+        payer = sponsor,
+        space = LiquidityRewards::LEN,
+        seeds = [b"liquidity_rewards", market.key().as_ref()],
+        bump
+    )]
+    pub liquidity_rewards: Box<Account<'info, LiquidityRewards>>,
+
+    pub reward_mint: Box<Account<'info, Mint>>,
+
+    #[account(
+        init,
+        payer = sponsor,
+        seeds = [b"reward_vault", market.key().as_ref()],
+        bump,
+        token::mint = reward_mint,
+        token::authority = liquidity_rewards,
+    )]
+    pub reward_vault: Box<Account<'info, TokenAccount>>,
+
+    #[account(
+        mut,
+        constraint = sponsor_token_account.mint == reward_mint.key() @ PredictionMarketError::InvalidTokenMint,
+        constraint = sponsor_token_account.owner == sponsor.key() @ PredictionMarketError::InvalidTokenAccountOwner,
+    )]
+    pub sponsor_token_account: Box<Account<'info, TokenAccount>>,
+
+    #[account(
+        seeds = [b"global_state"],
+        bump = global_state.bump,
+        constraint = !global_state.is_halted(PAUSE_LIQUIDITY) @ PredictionMarketError::MarketPaused,
+    )]
+    pub global_state: Box<Account<'info, GlobalState>>,
+
+    #[account(mut)]
+    pub sponsor: Signer<'info>,
+
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+    pub rent: Sysvar<'info, Rent>,
+}
+
+/// Starts a liquidity mining program for a constant-product market. The
+/// sponsor deposits `emission_rate` tokens of `reward_mint` for every second
+/// between `start_time` and `end_time`, and the emission is shared among
+/// the market's providers in proportion to their LP tokens. Each market can
+/// run one program.
+pub fn fund_liquidity_rewards(
+    ctx: Context<FundLiquidityRewards>,
+    emission_rate: u64,
+    start_time: i64,
+    end_time: i64,
+) -> Result<()> {
+    let market = &ctx.accounts.market;
+    let pool = &mut ctx.accounts.pool;
+    let liquidity_rewards = &mut ctx.accounts.liquidity_rewards;
+    let clock = Clock::get()?;
+
+    require!(emission_rate > 0, PredictionMarketError::InvalidAmount);
+    require!(
+        start_time >= clock.unix_timestamp && end_time > start_time,
+        PredictionMarketError::InvalidRewardSchedule
+    );
+
+    let total_funded = u64::try_from((end_time - start_time) as u128 * emission_rate as u128)
+        .map_err(|_| PredictionMarketError::ArithmeticOverflow)?;
+
+    let transfer_ctx = CpiContext::new(
+        ctx.accounts.token_program.to_account_info(),
+        Transfer {
+            from: ctx.accounts.sponsor_token_account.to_account_info(),
+            to: ctx.accounts.reward_vault.to_account_info(),
+            authority: ctx.accounts.sponsor.to_account_info(),
+        },
+    );
+    token::transfer(transfer_ctx, total_funded)?;
+
+    liquidity_rewards.market = market.key();
+    liquidity_rewards.sponsor = ctx.accounts.sponsor.key();
+    liquidity_rewards.reward_mint = ctx.accounts.reward_mint.key();
+    liquidity_rewards.reward_vault = ctx.accounts.reward_vault.key();
+    liquidity_rewards.emission_rate = emission_rate;
+    liquidity_rewards.start_time = start_time;
+    liquidity_rewards.end_time = end_time;
+    liquidity_rewards.last_update_time = start_time;
+    liquidity_rewards.reward_per_share = 0;
+    liquidity_rewards.total_funded = total_funded;
+    liquidity_rewards.total_distributed = 0;
+    liquidity_rewards.total_claimed = 0;
+    liquidity_rewards.total_reclaimed = 0;
+    liquidity_rewards.bump = ctx.bumps.liquidity_rewards;
+    liquidity_rewards.vault_bump = ctx.bumps.reward_vault;
+
+    pool.rewards = liquidity_rewards.key();
+
+    emit!(LiquidityRewardsFundedEvent {
+        market: market.key(),
+        liquidity_rewards: liquidity_rewards.key(),
+        sponsor: liquidity_rewards.sponsor,
+        reward_mint: liquidity_rewards.reward_mint,
+        emission_rate,
+        start_time,
+        end_time,
+        total_funded,
+    });
+
+    Ok(())
+}
+
+#[event]
+pub struct LiquidityRewardsFundedEvent {
+    pub market: Pubkey,
+    pub liquidity_rewards: Pubkey,
+    pub sponsor: Pubkey,
+    pub reward_mint: Pubkey,
+    pub emission_rate: u64,
+    pub start_time: i64,
+    pub end_time: i64,
+    pub total_funded: u64,
+}
@@ -0,0 +1,100 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::{self, Token, TokenAccount, Transfer};
+
+use crate::lmsr;
+use crate::state::*;
+use crate::error::*;
+
+#[derive(Accounts)]
+pub struct InitializeLmsr<'info> {
+    #[account(
+        mut,
+        seeds = [b"market", market.market_id.to_le_bytes().as_ref()],
+        bump = market.bump,
+        constraint = !market.is_halted(PAUSE_LIQUIDITY) @ PredictionMarketError::MarketPaused,
+        has_one = authority @ PredictionMarketError::UnauthorizedResolution,
+        constraint = market.status == MarketStatus::Active @ PredictionMarketError::MarketNotActive,
+        constraint = market.pricing_model == PricingModel::Parimutuel @ PredictionMarketError::InvalidPricingModel,
+    )]
+    pub market: Account<'info, PredictionMarket>,
+
+    #[account(
+        mut,
+        seeds = [b"market_vault", market.key().as_ref()],
+        bump = market.vault_bump,
+    )]
+    pub market_vault: Account<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        constraint = authority_token_account.mint == market.quote_mint @ PredictionMarketError::InvalidTokenMint,
+        constraint = authority_token_account.owner == authority.key() @ PredictionMarketError::InvalidTokenAccountOwner,
+    )]
+    pub authority_token_account: Account<'info, TokenAccount>,
+
+    #[account(
+        seeds = [b"global_state"],
+        bump = global_state.bump,
+        constraint = !global_state.is_halted(PAUSE_LIQUIDITY) @ PredictionMarketError::MarketPaused,
+    )]
+    pub global_state: Box<Account<'info, GlobalState>>,
+
+    pub authority: Signer<'info>,
+
+    pub token_program: Program<'info, Token>,
+}
+
+/// Switches a freshly created market to LMSR pricing. The creator deposits
+/// the market maker's worst-case loss `b * ln(n)` so every winning share is
+/// fully collateralised no matter how trading goes.
+pub fn initialize_lmsr(ctx: Context<InitializeLmsr>, liquidity: u64) -> Result<()> {
+    let market = &mut ctx.accounts.market;
+
+    require!(liquidity > 0, PredictionMarketError::InvalidLiquidityParameter);
+    require!(
+        market.total_bets == 0 && market.total_pool()? == 0,
+        PredictionMarketError::InvalidPricingModel
+    );
+    require!(
+        Clock::get()?.unix_timestamp < market.end_time,
+        PredictionMarketError::MarketExpired
+    );
+
+    let subsidy = lmsr::max_loss(market.outcomes.len(), liquidity)?;
+
+    let transfer_ctx = CpiContext::new(
+        ctx.accounts.token_program.to_account_info(),
+        Transfer {
+            from: ctx.accounts.authority_token_account.to_account_info(),
+            to: ctx.accounts.market_vault.to_account_info(),
+            authority: ctx.accounts.authority.to_account_info(),
+        },
+    );
+    token::transfer(transfer_ctx, subsidy)?;
+
+    market.pricing_model = PricingModel::Lmsr;
+    market.lmsr_liquidity = liquidity;
+    market.lmsr_subsidy = subsidy;
+
+    let prices = lmsr::prices(&market.outcome_shares(), liquidity)?;
+    for (outcome, price) in market.outcomes.iter_mut().zip(prices.iter()) {
+        outcome.price = *price;
+    }
+
+    emit!(LmsrInitializedEvent {
+        market: market.key(),
+        liquidity,
+        subsidy,
+        prices,
+    });
+
+    Ok(())
+}
+
+#[event]
+pub struct LmsrInitializedEvent {
+    pub market: Pubkey,
+    pub liquidity: u64,
+    pub subsidy: u64,
+    pub prices: Vec<u64>,
+}
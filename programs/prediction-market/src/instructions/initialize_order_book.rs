@@ -0,0 +1,61 @@
+use anchor_lang::prelude::*;
+
+use crate::state::*;
+use crate::error::*;
+
+#[derive(Accounts)]
+pub struct InitializeOrderBook<'info> {
+    #[account(
+        seeds = [b"market", market.market_id.to_le_bytes().as_ref()],
+        bump = market.bump,
+        constraint = !market.is_halted(PAUSE_TRADING) @ PredictionMarketError::MarketPaused,
+        has_one = authority @ PredictionMarketError::UnauthorizedResolution,
+        constraint = market.status == MarketStatus::Active @ PredictionMarketError::MarketNotActive,
+        constraint = market.outcomes.len() == 2 @ PredictionMarketError::InvalidOutcomeCount,
+    )]
+    pub market: Box<Account<'info, PredictionMarket>>,
+
+    #[account(
+        init,
+        payer = authority,
+        space = OrderBook::LEN,
+        seeds = [b"order_book", market.key().as_ref()],
+        bump
+    )]
+    pub order_book: AccountLoader<'info, OrderBook>,
+
+    #[account(
+        seeds = [b"global_state"],
+        bump = global_state.bump,
+        constraint = !global_state.is_halted(PAUSE_TRADING) @ PredictionMarketError::MarketPaused,
+    )]
+    pub global_state: Box<Account<'info, GlobalState>>,
+
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub fn initialize_order_book(ctx: Context<InitializeOrderBook>) -> Result<()> {
+    let mut order_book = ctx.accounts.order_book.load_init()?;
+    order_book.market = ctx.accounts.market.key();
+    order_book.next_order_id = 1;
+    order_book.order_count = 0;
+    order_book.bump = ctx.bumps.order_book;
+
+    emit!(OrderBookInitializedEvent {
+        market: ctx.accounts.market.key(),
+        order_book: ctx.accounts.order_book.key(),
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    Ok(())
+}
+
+#[event]
+pub struct OrderBookInitializedEvent {
+    pub market: Pubkey,
+    pub order_book: Pubkey,
+    pub timestamp: i64,
+}
@@ -0,0 +1,196 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::{self, Mint, Token, TokenAccount, Transfer};
+
+use crate::amm;
+use crate::state::*;
+use crate::error::*;
+
+#[derive(Accounts)]
+pub struct InitializePool<'info> {
+    #[account(
+        mut,
+        seeds = [b"market", market.market_id.to_le_bytes().as_ref()],
+        bump = market.bump,
+        constraint = !market.is_halted(PAUSE_LIQUIDITY) @ PredictionMarketError::MarketPaused,
+        has_one = authority @ PredictionMarketError::UnauthorizedResolution,
+        has_one = lp_mint,
+        constraint = market.status == MarketStatus::Active @ PredictionMarketError::MarketNotActive,
+        constraint = market.pricing_model == PricingModel::Parimutuel @ PredictionMarketError::InvalidPricingModel,
+    )]
+    pub market: Box<Account<'info, PredictionMarket>>,
+
+    #[account(
+        init,
+        payer = authority,
+        space = LiquidityPool::LEN,
+        seeds = [b"pool", market.key().as_ref()],
+        bump
+    )]
+    pub pool: Box<Account<'info, LiquidityPool>>,
+
+    #[account(
+        init_if_needed,
+        payer = authority,
+        space = LiquidityProvider::LEN,
+        seeds = [b"liquidity_provider", market.key().as_ref(), authority.key().as_ref()],
+        bump
+    )]
+    pub liquidity_provider: Box<Account<'info, LiquidityProvider>>,
+
+    #[account(
+        mut,
+        seeds = [b"market_vault", market.key().as_ref()],
+        bump = market.vault_bump,
+    )]
+    pub market_vault: Box<Account<'info, TokenAccount>>,
+
+    #[account(
+        mut,
+        constraint = authority_token_account.mint == market.quote_mint @ PredictionMarketError::InvalidTokenMint,
+        constraint = authority_token_account.owner == authority.key() @ PredictionMarketError::InvalidTokenAccountOwner,
+    )]
+    pub authority_token_account: Box<Account<'info, TokenAccount>>,
+
+    /// Holds every LP token of the pool on behalf of its providers
+    #[account(
+        init,
+        payer = authority,
+        token::mint = lp_mint,
+        token::authority = market,
+        seeds = [b"lp_escrow", market.key().as_ref()],
+        bump
+    )]
+    pub lp_escrow: Box<Account<'info, TokenAccount>>,
+
+    #[account(mut)]
+    pub lp_mint: Box<Account<'info, Mint>>,
+
+    #[account(
+        seeds = [b"global_state"],
+        bump = global_state.bump,
+        constraint = !global_state.is_halted(PAUSE_LIQUIDITY) @ PredictionMarketError::MarketPaused,
+    )]
+    pub global_state: Box<Account<'info, GlobalState>>,
+
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+    pub rent: Sysvar<'info, Rent>,
+}
+
+/// Switches a freshly created binary or scalar market to constant-product
+/// pricing. The creator's deposit mints complete sets that seed both
+/// reserves equally, so trading opens at 50/50.
+pub fn initialize_pool(ctx: Context<InitializePool>, amount: u64, fee_rate: u16) -> Result<()> {
+    let market = &mut ctx.accounts.market;
+    let pool = &mut ctx.accounts.pool;
+    let liquidity_provider = &mut ctx.accounts.liquidity_provider;
+
+    require!(amount > 0, PredictionMarketError::InvalidAmount);
+    require!(fee_rate <= 10000, PredictionMarketError::InvalidFeePercentage);
+    require!(
+        market.outcomes.len() == 2,
+        PredictionMarketError::InvalidOutcomeCount
+    );
+    require!(
+        market.total_bets == 0 && market.total_pool()? == 0,
+        PredictionMarketError::InvalidPricingModel
+    );
+    require!(
+        Clock::get()?.unix_timestamp < market.end_time,
+        PredictionMarketError::MarketExpired
+    );
+
+    let transfer_ctx = CpiContext::new(
+        ctx.accounts.token_program.to_account_info(),
+        Transfer {
+            from: ctx.accounts.authority_token_account.to_account_info(),
+            to: ctx.accounts.market_vault.to_account_info(),
+            authority: ctx.accounts.authority.to_account_info(),
+        },
+    );
+    token::transfer(transfer_ctx, amount)?;
+
+    let market_id_bytes = market.market_id.to_le_bytes();
+    let market_seeds = &[
+        b"market",
+        market_id_bytes.as_ref(),
+        &[market.bump],
+    ];
+    let signer_seeds = &[&market_seeds[..]];
+
+    let mint_ctx = CpiContext::new_with_signer(
+        ctx.accounts.token_program.to_account_info(),
+        token::MintTo {
+            mint: ctx.accounts.lp_mint.to_account_info(),
+            to: ctx.accounts.lp_escrow.to_account_info(),
+            authority: market.to_account_info(),
+        },
+        signer_seeds,
+    );
+    token::mint_to(mint_ctx, amount)?;
+
+    pool.market = market.key();
+    pool.token_mint = market.quote_mint;
+    pool.token_vault = market.market_vault;
+    pool.total_supply = amount;
+    pool.total_liquidity = amount;
+    pool.reserve_a = amount;
+    pool.reserve_b = amount;
+    pool.fee_collected = 0;
+    pool.total_fees_collected = 0;
+    pool.fee_growth_per_share = 0;
+    pool.fee_rate = fee_rate;
+    pool.withdrawal_fee_rate = 0;
+    pool.max_withdrawal_fee_rate = 0;
+    pool.withdrawal_fee_ramp = 0;
+    pool.lock_after_cutoff = false;
+    pool.rewards = Pubkey::default();
+    pool.bump = ctx.bumps.pool;
+    pool.vault_bump = market.vault_bump;
+    pool.lp_escrow_bump = ctx.bumps.lp_escrow;
+
+    if liquidity_provider.user == Pubkey::default() {
+        liquidity_provider.user = ctx.accounts.authority.key();
+        liquidity_provider.market = market.key();
+        liquidity_provider.bump = ctx.bumps.liquidity_provider;
+
+        market.liquidity_providers_count = market.liquidity_providers_count
+            .checked_add(1)
+            .ok_or(PredictionMarketError::ArithmeticOverflow)?;
+    }
+    liquidity_provider.settle_fees(pool.fee_growth_per_share)?;
+    liquidity_provider.lp_tokens = liquidity_provider.lp_tokens
+        .checked_add(amount)
+        .ok_or(PredictionMarketError::ArithmeticOverflow)?;
+    liquidity_provider.total_deposited = liquidity_provider.total_deposited
+        .checked_add(amount)
+        .ok_or(PredictionMarketError::ArithmeticOverflow)?;
+
+    let (price_a, price_b) = amm::spot_prices(pool.reserve_a, pool.reserve_b)?;
+    market.outcomes[OUTCOME_A as usize].price = price_a;
+    market.outcomes[OUTCOME_B as usize].price = price_b;
+    market.pricing_model = PricingModel::ConstantProduct;
+    market.total_liquidity = market.total_liquidity
+        .checked_add(amount)
+        .ok_or(PredictionMarketError::ArithmeticOverflow)?;
+
+    emit!(PoolInitializedEvent {
+        market: market.key(),
+        pool: pool.key(),
+        amount,
+        fee_rate,
+    });
+
+    Ok(())
+}
+
+#[event]
+pub struct PoolInitializedEvent {
+    pub market: Pubkey,
+    pub pool: Pubkey,
+    pub amount: u64,
+    pub fee_rate: u16,
+}
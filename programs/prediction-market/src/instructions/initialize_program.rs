@@ -0,0 +1,67 @@
+use anchor_lang::prelude::*;
+
+use crate::program::PredictionMarket as PredictionMarketProgram;
+use crate::state::*;
+use crate::error::*;
+
+#[derive(Accounts)]
+pub struct InitializeProgram<'info> {
+    #[account(
+        init,
+        payer = authority,
+        space = GlobalState::LEN,
+        seeds = [b"global_state"],
+        bump
+    )]
+    pub global_state: Box<Account<'info, GlobalState>>,
+
+    #[account(
+        constraint = program.programdata_address()? == Some(program_data.key()) @ PredictionMarketError::UnauthorizedAdmin,
+    )]
+    pub program: Program<'info, PredictionMarketProgram>,
+
+    #[account(
+        constraint = program_data.upgrade_authority_address == Some(authority.key()) @ PredictionMarketError::UnauthorizedAdmin,
+    )]
+    pub program_data: Account<'info, ProgramData>,
+
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// Creates the program-wide config. Only the program's upgrade authority can
+/// call it, so the admin role can't be claimed by whoever gets there first.
+pub fn initialize_program(ctx: Context<InitializeProgram>, config: ProgramConfig) -> Result<()> {
+    let global_state = &mut ctx.accounts.global_state;
+
+    global_state.authority = ctx.accounts.authority.key();
+    global_state.pending_authority = Pubkey::default();
+    global_state.total_markets = 0;
+    global_state.total_volume = 0;
+    global_state.total_fees_collected = 0;
+    global_state.is_paused = false;
+    global_state.paused_classes = 0;
+    global_state.bump = ctx.bumps.global_state;
+    global_state.apply_config(&config)?;
+
+    emit!(ProgramInitializedEvent {
+        authority: global_state.authority,
+        platform_fee_rate: global_state.platform_fee_rate,
+        min_resolution_time: global_state.min_resolution_time,
+        max_resolution_time: global_state.max_resolution_time,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    Ok(())
+}
+
+#[event]
+pub struct ProgramInitializedEvent {
+    pub authority: Pubkey,
+    pub platform_fee_rate: u16,
+    pub min_resolution_time: i64,
+    pub max_resolution_time: i64,
+    pub timestamp: i64,
+}
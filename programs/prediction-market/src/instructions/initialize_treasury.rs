@@ -0,0 +1,68 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::{Mint, Token, TokenAccount};
+
+use crate::state::*;
+use crate::error::*;
+
+#[derive(Accounts)]
+pub struct InitializeTreasury<'info> {
+    #[account(
+        seeds = [b"global_state"],
+        bump = global_state.bump,
+        has_one = authority @ PredictionMarketError::UnauthorizedAdmin,
+    )]
+    pub global_state: Box<Account<'info, GlobalState>>,
+
+    pub quote_mint: Account<'info, Mint>,
+
+    #[account(
+        init_if_needed,
+        payer = authority,
+        seeds = [b"treasury", quote_mint.key().as_ref()],
+        bump,
+        token::mint = quote_mint,
+        token::authority = global_state,
+    )]
+    pub treasury: Account<'info, TokenAccount>,
+
+    /// Holds referrers' share of the platform fee until they claim it
+    #[account(
+        init_if_needed,
+        payer = authority,
+        seeds = [b"referral_vault", quote_mint.key().as_ref()],
+        bump,
+        token::mint = quote_mint,
+        token::authority = global_state,
+    )]
+    pub referral_vault: Account<'info, TokenAccount>,
+
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+    pub rent: Sysvar<'info, Rent>,
+}
+
+/// Opens the platform treasury and referral vault for `quote_mint`. Markets
+/// quoted in that mint route their platform fee here. `create_market` opens
+/// them too, so this only matters for pre-opening a mint; calling it again
+/// is a no-op.
+pub fn initialize_treasury(ctx: Context<InitializeTreasury>) -> Result<()> {
+    emit!(TreasuryInitializedEvent {
+        treasury: ctx.accounts.treasury.key(),
+        referral_vault: ctx.accounts.referral_vault.key(),
+        quote_mint: ctx.accounts.quote_mint.key(),
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    Ok(())
+}
+
+#[event]
+pub struct TreasuryInitializedEvent {
+    pub treasury: Pubkey,
+    pub referral_vault: Pubkey,
+    pub quote_mint: Pubkey,
+    pub timestamp: i64,
+}
@@ -0,0 +1,137 @@
+use anchor_lang::prelude::*;
+
+use crate::state::*;
+use crate::error::*;
+
+#[derive(Accounts)]
+pub struct MatchOrders<'info> {
+    #[account(
+        mut,
+        seeds = [b"market", market.market_id.to_le_bytes().as_ref()],
+        bump = market.bump,
+        constraint = !market.is_halted(PAUSE_TRADING) @ PredictionMarketError::MarketPaused,
+        constraint = market.status == MarketStatus::Active @ PredictionMarketError::MarketNotActive,
+    )]
+    pub market: Box<Account<'info, PredictionMarket>>,
+
+    #[account(
+        mut,
+        seeds = [b"order_book", market.key().as_ref()],
+        bump = order_book.load()?.bump,
+    )]
+    pub order_book: AccountLoader<'info, OrderBook>,
+
+    #[account(
+        mut,
+        seeds = [b"global_state"],
+        bump = global_state.bump,
+        constraint = !global_state.is_halted(PAUSE_TRADING) @ PredictionMarketError::MarketPaused,
+    )]
+    pub global_state: Box<Account<'info, GlobalState>>,
+
+    pub cranker: Signer<'info>,
+}
+
+/// Permissionless crank that crosses the best bid and ask of `outcome` up to
+/// `max_fills` times. Fills execute at the resting (older) order's price and
+/// only move balances inside the book; owners withdraw with `settle_order`.
+/// The creator fee comes out of the seller's proceeds.
+pub fn match_orders(ctx: Context<MatchOrders>, outcome: u8, max_fills: u8) -> Result<()> {
+    let market = &mut ctx.accounts.market;
+    let now = Clock::get()?.unix_timestamp;
+
+    require!(now < market.end_time, PredictionMarketError::MarketExpired);
+    market.outcome_token_accounts(outcome)?;
+
+    let mut order_book = ctx.accounts.order_book.load_mut()?;
+    order_book.expire_orders(now);
+
+    let mut fills = 0u8;
+    let mut volume = 0u64;
+    while fills < max_fills {
+        let (Some(bid_index), Some(ask_index)) = (
+            order_book.best_order(outcome, OrderSide::Buy),
+            order_book.best_order(outcome, OrderSide::Sell),
+        ) else {
+            break;
+        };
+
+        let bid = order_book.orders[bid_index];
+        let ask = order_book.orders[ask_index];
+        if bid.price < ask.price {
+            break;
+        }
+
+        let (bid_order_id, ask_order_id) = (bid.order_id, ask.order_id);
+        let shares = bid.remaining().min(ask.remaining());
+        let price = if bid_order_id < ask_order_id { bid.price } else { ask.price };
+        let collateral = Order::collateral_for(shares, price, false)?;
+        let creator_fee = market.accrue_creator_fee(collateral)?;
+
+        let bid = &mut order_book.orders[bid_index];
+        bid.filled = bid.filled
+            .checked_add(shares)
+            .ok_or(PredictionMarketError::ArithmeticOverflow)?;
+        bid.escrow = bid.escrow
+            .checked_sub(collateral)
+            .ok_or(PredictionMarketError::ArithmeticUnderflow)?;
+        bid.claimable_shares = bid.claimable_shares
+            .checked_add(shares)
+            .ok_or(PredictionMarketError::ArithmeticOverflow)?;
+        bid.status = if bid.remaining() == 0 {
+            OrderStatus::Filled as u8
+        } else {
+            OrderStatus::PartiallyFilled as u8
+        };
+
+        let ask = &mut order_book.orders[ask_index];
+        ask.filled = ask.filled
+            .checked_add(shares)
+            .ok_or(PredictionMarketError::ArithmeticOverflow)?;
+        ask.claimable_collateral = ask.claimable_collateral
+            .checked_add(collateral - creator_fee)
+            .ok_or(PredictionMarketError::ArithmeticOverflow)?;
+        ask.status = if ask.remaining() == 0 {
+            OrderStatus::Filled as u8
+        } else {
+            OrderStatus::PartiallyFilled as u8
+        };
+
+        emit!(OrderFilledEvent {
+            market: market.key(),
+            outcome,
+            bid_order_id,
+            ask_order_id,
+            price,
+            shares,
+            collateral,
+            creator_fee,
+            timestamp: now,
+        });
+
+        fills += 1;
+        volume = volume
+            .checked_add(collateral)
+            .ok_or(PredictionMarketError::ArithmeticOverflow)?;
+    }
+
+    let global_state = &mut ctx.accounts.global_state;
+    global_state.total_volume = global_state.total_volume
+        .checked_add(volume)
+        .ok_or(PredictionMarketError::ArithmeticOverflow)?;
+
+    Ok(())
+}
+
+#[event]
+pub struct OrderFilledEvent {
+    pub market: Pubkey,
+    pub outcome: u8,
+    pub bid_order_id: u64,
+    pub ask_order_id: u64,
+    pub price: u64,
+    pub shares: u64,
+    pub collateral: u64,
+    pub creator_fee: u64,
+    pub timestamp: i64,
+}
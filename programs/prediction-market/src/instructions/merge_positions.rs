@@ -0,0 +1,123 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::{self, Mint, Token, TokenAccount, Transfer};
+
+use crate::state::*;
+use crate::error::*;
+
+#[derive(Accounts)]
+pub struct MergePositions<'info> {
+    #[account(
+        seeds = [b"market", market.market_id.to_le_bytes().as_ref()],
+        bump = market.bump,
+        has_one = outcome_a_mint,
+        has_one = outcome_b_mint,
+    )]
+    pub market: Box<Account<'info, PredictionMarket>>,
+
+    #[account(
+        mut,
+        seeds = [b"market_vault", market.key().as_ref()],
+        bump = market.vault_bump,
+    )]
+    pub market_vault: Box<Account<'info, TokenAccount>>,
+
+    #[account(mut)]
+    pub outcome_a_mint: Box<Account<'info, Mint>>,
+
+    #[account(mut)]
+    pub outcome_b_mint: Box<Account<'info, Mint>>,
+
+    #[account(
+        mut,
+        constraint = user_token_account.mint == market.quote_mint @ PredictionMarketError::InvalidTokenMint,
+        constraint = user_token_account.owner == user.key() @ PredictionMarketError::InvalidTokenAccountOwner,
+    )]
+    pub user_token_account: Box<Account<'info, TokenAccount>>,
+
+    #[account(
+        mut,
+        constraint = user_outcome_a_account.mint == market.outcome_a_mint @ PredictionMarketError::InvalidTokenMint,
+        constraint = user_outcome_a_account.owner == user.key() @ PredictionMarketError::InvalidTokenAccountOwner,
+    )]
+    pub user_outcome_a_account: Box<Account<'info, TokenAccount>>,
+
+    #[account(
+        mut,
+        constraint = user_outcome_b_account.mint == market.outcome_b_mint @ PredictionMarketError::InvalidTokenMint,
+        constraint = user_outcome_b_account.owner == user.key() @ PredictionMarketError::InvalidTokenAccountOwner,
+    )]
+    pub user_outcome_b_account: Box<Account<'info, TokenAccount>>,
+
+    pub user: Signer<'info>,
+
+    pub token_program: Program<'info, Token>,
+}
+
+/// Burns `amount` complete sets (one A and one B token each) and returns the
+/// same amount of collateral.
+pub fn merge_positions(ctx: Context<MergePositions>, amount: u64) -> Result<()> {
+    let market = &ctx.accounts.market;
+
+    require!(amount > 0, PredictionMarketError::InvalidAmount);
+    require!(
+        ctx.accounts.user_outcome_a_account.amount >= amount
+            && ctx.accounts.user_outcome_b_account.amount >= amount,
+        PredictionMarketError::InsufficientTokenBalance
+    );
+
+    let burn_a_ctx = CpiContext::new(
+        ctx.accounts.token_program.to_account_info(),
+        token::Burn {
+            mint: ctx.accounts.outcome_a_mint.to_account_info(),
+            from: ctx.accounts.user_outcome_a_account.to_account_info(),
+            authority: ctx.accounts.user.to_account_info(),
+        },
+    );
+    token::burn(burn_a_ctx, amount)?;
+
+    let burn_b_ctx = CpiContext::new(
+        ctx.accounts.token_program.to_account_info(),
+        token::Burn {
+            mint: ctx.accounts.outcome_b_mint.to_account_info(),
+            from: ctx.accounts.user_outcome_b_account.to_account_info(),
+            authority: ctx.accounts.user.to_account_info(),
+        },
+    );
+    token::burn(burn_b_ctx, amount)?;
+
+    let market_id_bytes = market.market_id.to_le_bytes();
+    let market_seeds = &[
+        b"market",
+        market_id_bytes.as_ref(),
+        &[market.bump],
+    ];
+    let signer_seeds = &[&market_seeds[..]];
+
+    let transfer_ctx = CpiContext::new_with_signer(
+        ctx.accounts.token_program.to_account_info(),
+        Transfer {
+            from: ctx.accounts.market_vault.to_account_info(),
+            to: ctx.accounts.user_token_account.to_account_info(),
+            authority: market.to_account_info(),
+        },
+        signer_seeds,
+    );
+    token::transfer(transfer_ctx, amount)?;
+
+    emit!(PositionsMergedEvent {
+        market: market.key(),
+        user: ctx.accounts.user.key(),
+        amount,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    Ok(())
+}
+
+#[event]
+pub struct PositionsMergedEvent {
+    pub market: Pubkey,
+    pub user: Pubkey,
+    pub amount: u64,
+    pub timestamp: i64,
+}
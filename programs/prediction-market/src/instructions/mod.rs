@@ -1,13 +1,117 @@
-```rust
 pub mod create_market;
 pub mod place_bet;
 pub mod resolve_market;
 pub mod claim_winnings;
+pub mod add_liquidity;
+pub mod remove_liquidity;
+pub mod initialize_lmsr;
+pub mod buy_shares;
+pub mod sell_shares;
+pub mod claim_shares;
+pub mod initialize_pool;
+pub mod buy_outcome;
+pub mod sell_outcome;
+pub mod sell_bet;
+pub mod split_collateral;
+pub mod merge_positions;
+pub mod redeem;
+pub mod initialize_order_book;
+pub mod place_order;
+pub mod cancel_order;
+pub mod match_orders;
+pub mod settle_order;
+pub mod cancel_market;
+pub mod claim_refund;
+pub mod dispute_resolution;
+pub mod arbitrate_dispute;
+pub mod register_oracle;
+pub mod deactivate_oracle;
+pub mod configure_oracle_committee;
+pub mod submit_resolution_vote;
+pub mod set_mock_price_feed;
+pub mod configure_price_feed;
+pub mod resolve_from_price_feed;
+pub mod configure_optimistic_oracle;
+pub mod propose_outcome;
+pub mod challenge_proposal;
+pub mod finalize_proposal;
+pub mod arbitrate_proposal;
 pub mod initialize_program;
+pub mod update_config;
+pub mod transfer_admin;
+pub mod accept_admin;
+pub mod set_program_pause;
+pub mod set_market_pause;
+pub mod initialize_treasury;
+pub mod withdraw_treasury;
+pub mod claim_creator_fees;
+pub mod register_referrer;
+pub mod claim_referral_rewards;
+pub mod close_bet;
+pub mod close_position;
+pub mod close_market;
+pub mod claim_lp_fees;
+pub mod settle_liquidity;
+pub mod configure_withdrawal_fee;
+pub mod fund_liquidity_rewards;
+pub mod claim_liquidity_rewards;
+pub mod stop_liquidity_rewards;
 
 pub use create_market::*;
 pub use place_bet::*;
 pub use resolve_market::*;
 pub use claim_winnings::*;
+pub use add_liquidity::*;
+pub use remove_liquidity::*;
+pub use initialize_lmsr::*;
+pub use buy_shares::*;
+pub use sell_shares::*;
+pub use claim_shares::*;
+pub use initialize_pool::*;
+pub use buy_outcome::*;
+pub use sell_outcome::*;
+pub use sell_bet::*;
+pub use split_collateral::*;
+pub use merge_positions::*;
+pub use redeem::*;
+pub use initialize_order_book::*;
+pub use place_order::*;
+pub use cancel_order::*;
+pub use match_orders::*;
+pub use settle_order::*;
+pub use cancel_market::*;
+pub use claim_refund::*;
+pub use dispute_resolution::*;
+pub use arbitrate_dispute::*;
+pub use register_oracle::*;
+pub use deactivate_oracle::*;
+pub use configure_oracle_committee::*;
+pub use submit_resolution_vote::*;
+pub use set_mock_price_feed::*;
+pub use configure_price_feed::*;
+pub use resolve_from_price_feed::*;
+pub use configure_optimistic_oracle::*;
+pub use propose_outcome::*;
+pub use challenge_proposal::*;
+pub use finalize_proposal::*;
+pub use arbitrate_proposal::*;
 pub use initialize_program::*;
-```
\ No newline at end of file
+pub use update_config::*;
+pub use transfer_admin::*;
+pub use accept_admin::*;
+pub use set_program_pause::*;
+pub use set_market_pause::*;
+pub use initialize_treasury::*;
+pub use withdraw_treasury::*;
+pub use claim_creator_fees::*;
+pub use register_referrer::*;
+pub use claim_referral_rewards::*;
+pub use close_bet::*;
+pub use close_position::*;
+pub use close_market::*;
+pub use claim_lp_fees::*;
+pub use settle_liquidity::*;
+pub use configure_withdrawal_fee::*;
+pub use fund_liquidity_rewards::*;
+pub use claim_liquidity_rewards::*;
+pub use stop_liquidity_rewards::*;
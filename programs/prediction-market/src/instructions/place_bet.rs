@@ -1,29 +1,39 @@
-```rust
 use anchor_lang::prelude::*;
 use anchor_spl::token::{self, Token, TokenAccount, Transfer};
 
 use crate::state::*;
-use crate::errors::*;
+use crate::error::*;
+use crate::instructions::register_referrer::record_referred_trade;
 
 #[derive(Accounts)]
 #[instruction(market_id: u64, outcome: u8, amount: u64)]
 pub struct PlaceBet<'info> {
+    #[account(
+        mut,
+        seeds = [b"global_state"],
+        bump = global_state.bump,
+        constraint = !global_state.is_halted(PAUSE_TRADING) @ PredictionMarketError::MarketPaused,
+    )]
+    pub global_state: Box<Account<'info, GlobalState>>,
+
     #[account(mut)]
     pub bettor: Signer<'info>,
 
     #[account(
         mut,
         seeds = [b"market", market_id.to_le_bytes().as_ref()],
-        bump,
-        constraint = market.is_active @ PredictionMarketError::MarketNotActive,
-        constraint = outcome < market.outcomes.len() as u8 @ PredictionMarketError::InvalidOutcome
+        bump = market.bump,
+        constraint = !market.is_halted(PAUSE_TRADING) @ PredictionMarketError::MarketPaused,
+        constraint = market.status == MarketStatus::Active @ PredictionMarketError::MarketNotActive,
+        constraint = market.pricing_model == PricingModel::Parimutuel @ PredictionMarketError::InvalidPricingModel,
+        constraint = market.is_valid_outcome(outcome) @ PredictionMarketError::InvalidOutcome
     )]
-    pub market: Account<'info, Market>,
+    pub market: Account<'info, PredictionMarket>,
 
     #[account(
         init_if_needed,
         payer = bettor,
-        space = 8 + Bet::INIT_SPACE,
+        space = Bet::LEN,
         seeds = [b"bet", market.key().as_ref(), bettor.key().as_ref()],
         bump
     )]
@@ -32,18 +42,25 @@ pub struct PlaceBet<'info> {
     #[account(
         mut,
         constraint = bettor_token_account.owner == bettor.key(),
-        constraint = bettor_token_account.mint == market.token_mint
+        constraint = bettor_token_account.mint == market.quote_mint
     )]
     pub bettor_token_account: Account<'info, TokenAccount>,
 
     #[account(
         mut,
-        seeds = [b"vault", market.key().as_ref()],
-        bump,
-        constraint = market_vault.mint == market.token_mint
+        seeds = [b"market_vault", market.key().as_ref()],
+        bump = market.vault_bump,
     )]
     pub market_vault: Account<'info, TokenAccount>,
 
+    /// Partner frontend that referred this trade
+    #[account(
+        mut,
+        seeds = [b"referrer", market.quote_mint.as_ref(), referrer.authority.as_ref()],
+        bump = referrer.bump,
+    )]
+    pub referrer: Option<Box<Account<'info, Referrer>>>,
+
     pub token_program: Program<'info, Token>,
     pub system_program: Program<'info, System>,
 }
@@ -57,7 +74,12 @@ pub fn place_bet(
     let market = &mut ctx.accounts.market;
     let bet = &mut ctx.accounts.bet;
     let bettor = &ctx.accounts.bettor;
+    let clock = Clock::get()?;
 
+    require!(
+        clock.unix_timestamp < market.end_time,
+        PredictionMarketError::MarketExpired
+    );
     require!(amount > 0, PredictionMarketError::InvalidAmount);
     require!(
         ctx.accounts.bettor_token_account.amount >= amount,
@@ -75,39 +97,59 @@ pub fn place_bet(
     );
     token::transfer(transfer_ctx, amount)?;
 
-    // Initialize or update bet
+    // The creator fee stays in the vault; only the remainder is staked
+    let creator_fee = market.accrue_creator_fee(amount)?;
+    let stake = amount - creator_fee;
+    require!(stake > 0, PredictionMarketError::InvalidAmount);
+
+    // Initialize or update bet; a fully sold bet may be reopened on any outcome
     if bet.bettor == Pubkey::default() {
         bet.bettor = bettor.key();
         bet.market = market.key();
+        bet.is_claimed = false;
+        bet.winnings_claimed = 0;
+        bet.referrer = Pubkey::default();
+        bet.bump = ctx.bumps.bet;
+    }
+    if bet.amount == 0 {
         bet.outcome = outcome;
-        bet.amount = amount;
-        bet.timestamp = Clock::get()?.unix_timestamp;
-    } else {
-        require!(
-            bet.outcome == outcome,
-            PredictionMarketError::OutcomeMismatch
-        );
-        bet.amount = bet.amount.checked_add(amount)
-            .ok_or(PredictionMarketError::MathOverflow)?;
+        bet.timestamp = clock.unix_timestamp;
+
+        market.total_bets = market.total_bets.checked_add(1)
+            .ok_or(PredictionMarketError::ArithmeticOverflow)?;
+        market.open_positions = market.open_positions
+            .checked_add(1)
+            .ok_or(PredictionMarketError::ArithmeticOverflow)?;
+    }
+    require!(
+        bet.outcome == outcome,
+        PredictionMarketError::OutcomeMismatch
+    );
+    bet.amount = bet.amount.checked_add(stake)
+        .ok_or(PredictionMarketError::ArithmeticOverflow)?;
+
+    if let Some(referrer) = ctx.accounts.referrer.as_mut() {
+        record_referred_trade(referrer, &mut bet.referrer, bettor.key(), market.key(), amount)?;
     }
 
     // Update market totals
-    market.total_pool = market.total_pool.checked_add(amount)
-        .ok_or(PredictionMarketError::MathOverflow)?;
-    
-    market.outcomes[outcome as usize] = market.outcomes[outcome as usize]
-        .checked_add(amount)
-        .ok_or(PredictionMarketError::MathOverflow)?;
+    let market_outcome = market.outcome_mut(outcome)?;
+    market_outcome.pool = market_outcome.pool.checked_add(stake)
+        .ok_or(PredictionMarketError::ArithmeticOverflow)?;
+    market.refresh_parimutuel_prices()?;
 
-    market.total_bets = market.total_bets.checked_add(1)
-        .ok_or(PredictionMarketError::MathOverflow)?;
+    let global_state = &mut ctx.accounts.global_state;
+    global_state.total_volume = global_state.total_volume
+        .checked_add(amount)
+        .ok_or(PredictionMarketError::ArithmeticOverflow)?;
 
     emit!(BetPlacedEvent {
         market: market.key(),
         bettor: bettor.key(),
         outcome,
         amount,
-        timestamp: Clock::get()?.unix_timestamp,
+        creator_fee,
+        timestamp: clock.unix_timestamp,
     });
 
     Ok(())
@@ -119,6 +161,6 @@ pub struct BetPlacedEvent {
     pub bettor: Pubkey,
     pub outcome: u8,
     pub amount: u64,
+    pub creator_fee: u64,
     pub timestamp: i64,
 }
-```
\ No newline at end of file
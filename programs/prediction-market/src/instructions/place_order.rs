@@ -0,0 +1,171 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::{self, Token, TokenAccount, Transfer};
+
+use crate::state::*;
+use crate::error::*;
+
+#[derive(Accounts)]
+pub struct PlaceOrder<'info> {
+    #[account(
+        seeds = [b"market", market.market_id.to_le_bytes().as_ref()],
+        bump = market.bump,
+        constraint = !market.is_halted(PAUSE_TRADING) @ PredictionMarketError::MarketPaused,
+        constraint = market.status == MarketStatus::Active @ PredictionMarketError::MarketNotActive,
+    )]
+    pub market: Box<Account<'info, PredictionMarket>>,
+
+    #[account(
+        mut,
+        seeds = [b"order_book", market.key().as_ref()],
+        bump = order_book.load()?.bump,
+    )]
+    pub order_book: AccountLoader<'info, OrderBook>,
+
+    #[account(
+        mut,
+        seeds = [b"market_vault", market.key().as_ref()],
+        bump = market.vault_bump,
+    )]
+    pub market_vault: Box<Account<'info, TokenAccount>>,
+
+    /// Market-owned escrow for the outcome being traded
+    #[account(mut)]
+    pub outcome_vault: Box<Account<'info, TokenAccount>>,
+
+    #[account(
+        mut,
+        constraint = user_token_account.mint == market.quote_mint @ PredictionMarketError::InvalidTokenMint,
+        constraint = user_token_account.owner == user.key() @ PredictionMarketError::InvalidTokenAccountOwner,
+    )]
+    pub user_token_account: Box<Account<'info, TokenAccount>>,
+
+    #[account(
+        mut,
+        constraint = user_outcome_token_account.owner == user.key() @ PredictionMarketError::InvalidTokenAccountOwner,
+    )]
+    pub user_outcome_token_account: Box<Account<'info, TokenAccount>>,
+
+    #[account(
+        seeds = [b"global_state"],
+        bump = global_state.bump,
+        constraint = !global_state.is_halted(PAUSE_TRADING) @ PredictionMarketError::MarketPaused,
+    )]
+    pub global_state: Box<Account<'info, GlobalState>>,
+
+    pub user: Signer<'info>,
+
+    pub token_program: Program<'info, Token>,
+}
+
+/// Rests a limit order on the book. Bids escrow `shares * price` collateral
+/// in the market vault; asks escrow the outcome tokens themselves.
+pub fn place_order(ctx: Context<PlaceOrder>, params: OrderParams) -> Result<()> {
+    let market = &ctx.accounts.market;
+    let clock = Clock::get()?;
+
+    require!(clock.unix_timestamp < market.end_time, PredictionMarketError::MarketExpired);
+    require!(params.shares > 0, PredictionMarketError::InvalidAmount);
+    require!(
+        params.price > 0 && params.price < PRICE_PRECISION,
+        PredictionMarketError::InvalidOrderPrice
+    );
+    require!(
+        params.expires_at == 0
+            || (params.expires_at > clock.unix_timestamp && params.expires_at <= market.end_time),
+        PredictionMarketError::InvalidOrderExpiry
+    );
+
+    let (outcome_mint, outcome_vault) = market.outcome_token_accounts(params.outcome)?;
+    require_keys_eq!(
+        ctx.accounts.outcome_vault.key(),
+        outcome_vault,
+        PredictionMarketError::InvalidTokenAccountOwner
+    );
+    require_keys_eq!(
+        ctx.accounts.user_outcome_token_account.mint,
+        outcome_mint,
+        PredictionMarketError::InvalidTokenMint
+    );
+
+    let escrow = match params.side {
+        OrderSide::Buy => {
+            let collateral = Order::collateral_for(params.shares, params.price, true)?;
+            let transfer_ctx = CpiContext::new(
+                ctx.accounts.token_program.to_account_info(),
+                Transfer {
+                    from: ctx.accounts.user_token_account.to_account_info(),
+                    to: ctx.accounts.market_vault.to_account_info(),
+                    authority: ctx.accounts.user.to_account_info(),
+                },
+            );
+            token::transfer(transfer_ctx, collateral)?;
+            collateral
+        }
+        OrderSide::Sell => {
+            let transfer_ctx = CpiContext::new(
+                ctx.accounts.token_program.to_account_info(),
+                Transfer {
+                    from: ctx.accounts.user_outcome_token_account.to_account_info(),
+                    to: ctx.accounts.outcome_vault.to_account_info(),
+                    authority: ctx.accounts.user.to_account_info(),
+                },
+            );
+            token::transfer(transfer_ctx, params.shares)?;
+            0
+        }
+    };
+
+    let mut order_book = ctx.accounts.order_book.load_mut()?;
+    let slot = order_book.free_slot()?;
+    let order_id = order_book.next_order_id;
+
+    order_book.orders[slot] = Order {
+        owner: ctx.accounts.user.key(),
+        order_id,
+        price: params.price,
+        shares: params.shares,
+        filled: 0,
+        escrow,
+        claimable_shares: 0,
+        claimable_collateral: 0,
+        created_at: clock.unix_timestamp,
+        expires_at: params.expires_at,
+        outcome: params.outcome,
+        side: params.side as u8,
+        status: OrderStatus::Open as u8,
+        _padding: [0; 5],
+    };
+    order_book.next_order_id = order_id
+        .checked_add(1)
+        .ok_or(PredictionMarketError::ArithmeticOverflow)?;
+    order_book.order_count = order_book.order_count
+        .checked_add(1)
+        .ok_or(PredictionMarketError::ArithmeticOverflow)?;
+
+    emit!(OrderPlacedEvent {
+        market: market.key(),
+        user: ctx.accounts.user.key(),
+        order_id,
+        outcome: params.outcome,
+        side: params.side,
+        price: params.price,
+        shares: params.shares,
+        expires_at: params.expires_at,
+        timestamp: clock.unix_timestamp,
+    });
+
+    Ok(())
+}
+
+#[event]
+pub struct OrderPlacedEvent {
+    pub market: Pubkey,
+    pub user: Pubkey,
+    pub order_id: u64,
+    pub outcome: u8,
+    pub side: OrderSide,
+    pub price: u64,
+    pub shares: u64,
+    pub expires_at: i64,
+    pub timestamp: i64,
+}
@@ -0,0 +1,102 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::{self, Token, TokenAccount, Transfer};
+
+use crate::state::*;
+use crate::error::*;
+
+#[derive(Accounts)]
+pub struct ProposeOutcome<'info> {
+    #[account(
+        seeds = [b"market", market.market_id.to_le_bytes().as_ref()],
+        bump = market.bump,
+        constraint = market.arbiter != Pubkey::default() @ PredictionMarketError::InvalidOptimisticConfig,
+        constraint = market.status == MarketStatus::Active @ PredictionMarketError::MarketNotActive,
+        constraint = market.resolution_time <= Clock::get()?.unix_timestamp @ PredictionMarketError::MarketNotExpired
+    )]
+    pub market: Box<Account<'info, PredictionMarket>>,
+
+    #[account(
+        init,
+        payer = proposer,
+        space = Proposal::LEN,
+        seeds = [b"proposal", market.key().as_ref()],
+        bump
+    )]
+    pub proposal: Box<Account<'info, Proposal>>,
+
+    #[account(
+        mut,
+        seeds = [b"market_vault", market.key().as_ref()],
+        bump = market.vault_bump,
+    )]
+    pub vault: Box<Account<'info, TokenAccount>>,
+
+    #[account(
+        mut,
+        constraint = proposer_token_account.mint == market.quote_mint @ PredictionMarketError::InvalidTokenMint,
+        constraint = proposer_token_account.owner == proposer.key() @ PredictionMarketError::InvalidTokenAccountOwner,
+    )]
+    pub proposer_token_account: Box<Account<'info, TokenAccount>>,
+
+    #[account(mut)]
+    pub proposer: Signer<'info>,
+
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+}
+
+/// Proposes the market's result, escrowing the market's proposal bond in the
+/// vault. The proposal is final after the liveness window unless challenged.
+pub fn propose_outcome(ctx: Context<ProposeOutcome>, params: ResolutionParams) -> Result<()> {
+    let market = &ctx.accounts.market;
+    let proposal = &mut ctx.accounts.proposal;
+    let now = Clock::get()?.unix_timestamp;
+
+    if market.market_type != MarketType::Scalar {
+        require!(market.is_valid_outcome(params.outcome), PredictionMarketError::InvalidOutcome);
+    }
+
+    let transfer_ctx = CpiContext::new(
+        ctx.accounts.token_program.to_account_info(),
+        Transfer {
+            from: ctx.accounts.proposer_token_account.to_account_info(),
+            to: ctx.accounts.vault.to_account_info(),
+            authority: ctx.accounts.proposer.to_account_info(),
+        },
+    );
+    token::transfer(transfer_ctx, market.proposal_bond)?;
+
+    proposal.market = market.key();
+    proposal.proposer = ctx.accounts.proposer.key();
+    proposal.challenger = Pubkey::default();
+    proposal.outcome = params.outcome;
+    proposal.resolution_price = params.resolution_price;
+    proposal.bond = market.proposal_bond;
+    proposal.proposed_at = now;
+    proposal.liveness_end = now
+        .checked_add(market.proposal_liveness)
+        .ok_or(PredictionMarketError::ArithmeticOverflow)?;
+    proposal.status = ProposalStatus::Pending;
+    proposal.bump = ctx.bumps.proposal;
+
+    emit!(OutcomeProposedEvent {
+        market: market.key(),
+        proposer: proposal.proposer,
+        outcome: params.outcome,
+        resolution_price: params.resolution_price,
+        bond: proposal.bond,
+        liveness_end: proposal.liveness_end,
+    });
+
+    Ok(())
+}
+
+#[event]
+pub struct OutcomeProposedEvent {
+    pub market: Pubkey,
+    pub proposer: Pubkey,
+    pub outcome: u8,
+    pub resolution_price: u64,
+    pub bond: u64,
+    pub liveness_end: i64,
+}
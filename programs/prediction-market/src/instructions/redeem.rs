@@ -0,0 +1,190 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::{self, Mint, Token, TokenAccount, Transfer};
+
+use crate::state::*;
+use crate::error::*;
+use crate::utils::calculate_platform_fee;
+
+#[derive(Accounts)]
+pub struct Redeem<'info> {
+    #[account(
+        seeds = [b"market", market.market_id.to_le_bytes().as_ref()],
+        bump = market.bump,
+        constraint = !market.is_halted(PAUSE_CLAIMS) @ PredictionMarketError::MarketPaused,
+        has_one = outcome_a_mint,
+        has_one = outcome_b_mint,
+        constraint = matches!(market.status, MarketStatus::Resolved | MarketStatus::Cancelled)
+            @ PredictionMarketError::MarketNotResolved,
+        constraint = market.status == MarketStatus::Cancelled
+            || market.dispute_end_time <= Clock::get()?.unix_timestamp @ PredictionMarketError::DisputeWindowOpen,
+    )]
+    pub market: Box<Account<'info, PredictionMarket>>,
+
+    #[account(
+        mut,
+        seeds = [b"market_vault", market.key().as_ref()],
+        bump = market.vault_bump,
+    )]
+    pub market_vault: Box<Account<'info, TokenAccount>>,
+
+    #[account(
+        mut,
+        seeds = [b"treasury", market.quote_mint.as_ref()],
+        bump,
+    )]
+    pub treasury: Box<Account<'info, TokenAccount>>,
+
+    #[account(mut)]
+    pub outcome_a_mint: Box<Account<'info, Mint>>,
+
+    #[account(mut)]
+    pub outcome_b_mint: Box<Account<'info, Mint>>,
+
+    #[account(
+        mut,
+        constraint = user_token_account.mint == market.quote_mint @ PredictionMarketError::InvalidTokenMint,
+        constraint = user_token_account.owner == user.key() @ PredictionMarketError::InvalidTokenAccountOwner,
+    )]
+    pub user_token_account: Box<Account<'info, TokenAccount>>,
+
+    #[account(
+        mut,
+        constraint = user_outcome_a_account.mint == market.outcome_a_mint @ PredictionMarketError::InvalidTokenMint,
+        constraint = user_outcome_a_account.owner == user.key() @ PredictionMarketError::InvalidTokenAccountOwner,
+    )]
+    pub user_outcome_a_account: Box<Account<'info, TokenAccount>>,
+
+    #[account(
+        mut,
+        constraint = user_outcome_b_account.mint == market.outcome_b_mint @ PredictionMarketError::InvalidTokenMint,
+        constraint = user_outcome_b_account.owner == user.key() @ PredictionMarketError::InvalidTokenAccountOwner,
+    )]
+    pub user_outcome_b_account: Box<Account<'info, TokenAccount>>,
+
+    #[account(
+        mut,
+        seeds = [b"global_state"],
+        bump = global_state.bump,
+        constraint = !global_state.is_halted(PAUSE_CLAIMS) @ PredictionMarketError::MarketPaused,
+    )]
+    pub global_state: Box<Account<'info, GlobalState>>,
+
+    pub user: Signer<'info>,
+
+    pub token_program: Program<'info, Token>,
+}
+
+/// Burns the user's entire A and B token balances and pays out their settled
+/// value: one unit of collateral per winning token, or the LONG/SHORT split
+/// for scalar markets, less the platform fee. After a cancellation every
+/// token is refunded at half a unit, fee free, so holders of a single side
+/// can exit without the other half of the set.
+pub fn redeem(ctx: Context<Redeem>) -> Result<()> {
+    let market = &ctx.accounts.market;
+    let amount_a = ctx.accounts.user_outcome_a_account.amount;
+    let amount_b = ctx.accounts.user_outcome_b_account.amount;
+
+    let payout_a = (amount_a as u128)
+        .checked_mul(market.token_redemption_value(OUTCOME_A)? as u128)
+        .ok_or(PredictionMarketError::ArithmeticOverflow)?
+        / PRICE_PRECISION as u128;
+    let payout_b = (amount_b as u128)
+        .checked_mul(market.token_redemption_value(OUTCOME_B)? as u128)
+        .ok_or(PredictionMarketError::ArithmeticOverflow)?
+        / PRICE_PRECISION as u128;
+    let payout = u64::try_from(payout_a + payout_b)
+        .map_err(|_| PredictionMarketError::ArithmeticOverflow)?;
+
+    require!(payout > 0, PredictionMarketError::NoWinningsToClaim);
+
+    if amount_a > 0 {
+        let burn_a_ctx = CpiContext::new(
+            ctx.accounts.token_program.to_account_info(),
+            token::Burn {
+                mint: ctx.accounts.outcome_a_mint.to_account_info(),
+                from: ctx.accounts.user_outcome_a_account.to_account_info(),
+                authority: ctx.accounts.user.to_account_info(),
+            },
+        );
+        token::burn(burn_a_ctx, amount_a)?;
+    }
+
+    if amount_b > 0 {
+        let burn_b_ctx = CpiContext::new(
+            ctx.accounts.token_program.to_account_info(),
+            token::Burn {
+                mint: ctx.accounts.outcome_b_mint.to_account_info(),
+                from: ctx.accounts.user_outcome_b_account.to_account_info(),
+                authority: ctx.accounts.user.to_account_info(),
+            },
+        );
+        token::burn(burn_b_ctx, amount_b)?;
+    }
+
+    let market_id_bytes = market.market_id.to_le_bytes();
+    let market_seeds = &[
+        b"market",
+        market_id_bytes.as_ref(),
+        &[market.bump],
+    ];
+    let signer_seeds = &[&market_seeds[..]];
+
+    let platform_fee = if market.status == MarketStatus::Cancelled {
+        0
+    } else {
+        calculate_platform_fee(payout, market.platform_fee_rate)?
+    };
+    let net_payout = payout - platform_fee;
+
+    let transfer_ctx = CpiContext::new_with_signer(
+        ctx.accounts.token_program.to_account_info(),
+        Transfer {
+            from: ctx.accounts.market_vault.to_account_info(),
+            to: ctx.accounts.user_token_account.to_account_info(),
+            authority: market.to_account_info(),
+        },
+        signer_seeds,
+    );
+    token::transfer(transfer_ctx, net_payout)?;
+
+    if platform_fee > 0 {
+        let fee_ctx = CpiContext::new_with_signer(
+            ctx.accounts.token_program.to_account_info(),
+            Transfer {
+                from: ctx.accounts.market_vault.to_account_info(),
+                to: ctx.accounts.treasury.to_account_info(),
+                authority: market.to_account_info(),
+            },
+            signer_seeds,
+        );
+        token::transfer(fee_ctx, platform_fee)?;
+
+        let global_state = &mut ctx.accounts.global_state;
+        global_state.total_fees_collected = global_state.total_fees_collected
+            .checked_add(platform_fee)
+            .ok_or(PredictionMarketError::ArithmeticOverflow)?;
+    }
+
+    emit!(TokensRedeemedEvent {
+        market: market.key(),
+        user: ctx.accounts.user.key(),
+        amount_a,
+        amount_b,
+        payout: net_payout,
+        platform_fee,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    Ok(())
+}
+
+#[event]
+pub struct TokensRedeemedEvent {
+    pub market: Pubkey,
+    pub user: Pubkey,
+    pub amount_a: u64,
+    pub amount_b: u64,
+    pub payout: u64,
+    pub platform_fee: u64,
+    pub timestamp: i64,
+}
@@ -0,0 +1,68 @@
+use anchor_lang::prelude::*;
+
+use crate::state::*;
+use crate::error::*;
+
+#[derive(Accounts)]
+#[instruction(oracle_id: u64)]
+pub struct RegisterOracle<'info> {
+    #[account(
+        init,
+        payer = authority,
+        space = OracleAccount::LEN,
+        seeds = [b"oracle", oracle_id.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub oracle_account: Account<'info, OracleAccount>,
+
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// Adds the signer to the oracle registry. Markets reference the registry
+/// entry, and only its authority can resolve them.
+pub fn register_oracle(
+    ctx: Context<RegisterOracle>,
+    oracle_id: u64,
+    name: String,
+    description: String,
+) -> Result<()> {
+    require!(name.len() <= MAX_ORACLE_NAME_LEN, PredictionMarketError::OracleNameTooLong);
+    require!(
+        description.len() <= MAX_ORACLE_DESCRIPTION_LEN,
+        PredictionMarketError::OracleDescriptionTooLong
+    );
+
+    let oracle_account = &mut ctx.accounts.oracle_account;
+    oracle_account.authority = ctx.accounts.authority.key();
+    oracle_account.oracle_id = oracle_id;
+    oracle_account.name = name;
+    oracle_account.description = description;
+    oracle_account.reputation_score = 0;
+    oracle_account.total_resolutions = 0;
+    oracle_account.correct_resolutions = 0;
+    oracle_account.is_active = true;
+    oracle_account.creation_time = Clock::get()?.unix_timestamp;
+    oracle_account.bump = ctx.bumps.oracle_account;
+
+    emit!(OracleRegisteredEvent {
+        oracle_account: oracle_account.key(),
+        authority: oracle_account.authority,
+        oracle_id,
+        name: oracle_account.name.clone(),
+        timestamp: oracle_account.creation_time,
+    });
+
+    Ok(())
+}
+
+#[event]
+pub struct OracleRegisteredEvent {
+    pub oracle_account: Pubkey,
+    pub authority: Pubkey,
+    pub oracle_id: u64,
+    pub name: String,
+    pub timestamp: i64,
+}
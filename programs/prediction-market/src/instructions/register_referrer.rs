@@ -0,0 +1,100 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::Mint;
+
+use crate::state::*;
+use crate::error::*;
+
+#[derive(Accounts)]
+pub struct RegisterReferrer<'info> {
+    #[account(
+        init,
+        payer = authority,
+        space = Referrer::LEN,
+        seeds = [b"referrer", quote_mint.key().as_ref(), authority.key().as_ref()],
+        bump
+    )]
+    pub referrer: Account<'info, Referrer>,
+
+    pub quote_mint: Account<'info, Mint>,
+
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// Registers a partner as a referrer for markets quoted in `quote_mint`.
+/// Trades that pass the referrer are attributed to it, and it earns the
+/// configured share of the platform fee when those positions pay out.
+pub fn register_referrer(ctx: Context<RegisterReferrer>) -> Result<()> {
+    let referrer = &mut ctx.accounts.referrer;
+    referrer.authority = ctx.accounts.authority.key();
+    referrer.mint = ctx.accounts.quote_mint.key();
+    referrer.total_volume = 0;
+    referrer.total_trades = 0;
+    referrer.rewards_earned = 0;
+    referrer.rewards_claimed = 0;
+    referrer.bump = ctx.bumps.referrer;
+
+    emit!(ReferrerRegisteredEvent {
+        referrer: referrer.key(),
+        authority: referrer.authority,
+        mint: referrer.mint,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    Ok(())
+}
+
+/// Attributes a bet or position to `referrer` and credits it with the
+/// trade's volume. Shared by every trade instruction that accepts a referrer.
+pub fn record_referred_trade(
+    referrer: &mut Account<Referrer>,
+    attributed: &mut Pubkey,
+    user: Pubkey,
+    market: Pubkey,
+    volume: u64,
+) -> Result<()> {
+    require!(referrer.authority != user, PredictionMarketError::SelfReferral);
+    referrer.attribute(attributed, referrer.key())?;
+    referrer.record_trade(volume)?;
+
+    emit!(ReferredTradeEvent {
+        referrer: referrer.key(),
+        user,
+        market,
+        volume,
+        total_volume: referrer.total_volume,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    Ok(())
+}
+
+#[event]
+pub struct ReferrerRegisteredEvent {
+    pub referrer: Pubkey,
+    pub authority: Pubkey,
+    pub mint: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct ReferredTradeEvent {
+    pub referrer: Pubkey,
+    pub user: Pubkey,
+    pub market: Pubkey,
+    pub volume: u64,
+    pub total_volume: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct ReferralRewardEvent {
+    pub referrer: Pubkey,
+    pub user: Pubkey,
+    pub market: Pubkey,
+    pub platform_fee: u64,
+    pub reward: u64,
+    pub timestamp: i64,
+}
@@ -1,169 +1,269 @@
-```rust
 use anchor_lang::prelude::*;
-use anchor_spl::token::{self, Token, TokenAccount, Transfer};
+use anchor_spl::token::{self, Mint, Token, TokenAccount, Transfer};
+use crate::amm;
 use crate::state::*;
-use crate::errors::*;
+use crate::error::*;
+use crate::instructions::claim_liquidity_rewards::settle_liquidity_rewards;
 
 #[derive(Accounts)]
 pub struct RemoveLiquidity<'info> {
+    #[account(
+        seeds = [b"global_state"],
+        bump = global_state.bump,
+        constraint = !global_state.is_halted(PAUSE_LIQUIDITY) @ PredictionMarketError::MarketPaused,
+    )]
+    pub global_state: Box<Account<'info, GlobalState>>,
+
     #[account(mut)]
     pub user: Signer<'info>,
-    
+
     #[account(
         mut,
         seeds = [b"market", market.market_id.to_le_bytes().as_ref()],
         bump = market.bump,
+        constraint = !market.is_halted(PAUSE_LIQUIDITY) @ PredictionMarketError::MarketPaused,
+        constraint = market.status == MarketStatus::Active @ PredictionMarketError::MarketNotActive,
+        has_one = lp_mint,
     )]
-    pub market: Account<'info, Market>,
-    
+    pub market: Box<Account<'info, PredictionMarket>>,
+
     #[account(
         mut,
         seeds = [b"pool", market.key().as_ref()],
         bump = pool.bump,
     )]
-    pub pool: Account<'info, LiquidityPool>,
-    
+    pub pool: Box<Account<'info, LiquidityPool>>,
+
     #[account(
         mut,
+        seeds = [b"liquidity_provider", market.key().as_ref(), user.key().as_ref()],
+        bump = liquidity_provider.bump,
+        constraint = liquidity_provider.user == user.key() @ PredictionMarketError::InvalidUser,
+    )]
+    pub liquidity_provider: Box<Account<'info, LiquidityProvider>>,
+
+    #[account(
+        init_if_needed,
+        payer = user,
+        space = UserPosition::LEN,
         seeds = [b"user_position", user.key().as_ref(), market.key().as_ref()],
-        bump = user_position.bump,
-        constraint = user_position.user == user.key() @ PredictionMarketError::InvalidUser,
+        bump
     )]
-    pub user_position: Account<'info, UserPosition>,
-    
+    pub user_position: Box<Account<'info, UserPosition>>,
+
     #[account(
         mut,
-        constraint = pool_token_account.mint == pool.token_mint @ PredictionMarketError::InvalidTokenMint,
-        constraint = pool_token_account.owner == pool.key() @ PredictionMarketError::InvalidTokenOwner,
+        seeds = [b"market_vault", market.key().as_ref()],
+        bump = market.vault_bump,
     )]
-    pub pool_token_account: Account<'info, TokenAccount>,
-    
+    pub market_vault: Box<Account<'info, TokenAccount>>,
+
+    #[account(
+        mut,
+        constraint = user_token_account.mint == market.quote_mint @ PredictionMarketError::InvalidTokenMint,
+        constraint = user_token_account.owner == user.key() @ PredictionMarketError::InvalidTokenAccountOwner,
+    )]
+    pub user_token_account: Box<Account<'info, TokenAccount>>,
+
+    #[account(
+        mut,
+        seeds = [b"lp_escrow", market.key().as_ref()],
+        bump = pool.lp_escrow_bump,
+    )]
+    pub lp_escrow: Box<Account<'info, TokenAccount>>,
+
+    #[account(mut)]
+    pub lp_mint: Box<Account<'info, Mint>>,
+
+    /// Required once the market runs a liquidity mining program
     #[account(
         mut,
-        constraint = user_token_account.mint == pool.token_mint @ PredictionMarketError::InvalidTokenMint,
-        constraint = user_token_account.owner == user.key() @ PredictionMarketError::InvalidTokenOwner,
+        seeds = [b"liquidity_rewards", market.key().as_ref()],
+        bump = liquidity_rewards.bump,
     )]
-    pub user_token_account: Account<'info, TokenAccount>,
-    
+    pub liquidity_rewards: Option<Box<Account<'info, LiquidityRewards>>>,
+
     pub token_program: Program<'info, Token>,
     pub system_program: Program<'info, System>,
 }
 
+/// Burns `lp_amount` LP tokens for the same fraction of each reserve.
+/// Matched pairs of outcome shares are merged back into collateral and paid
+/// out, less the withdrawal fee, together with every trading fee the
+/// provider has earned so far; the unmatched remainder of the larger side is
+/// credited to the provider's position as outcome shares. The withdrawal fee
+/// is shared among the providers who stay in the pool, so the last one out
+/// pays none, and rises as the market approaches resolution; pools
+/// configured with a lock reject withdrawals entirely once trading has
+/// closed.
 pub fn remove_liquidity(
     ctx: Context<RemoveLiquidity>,
-    liquidity_amount: u64,
+    lp_amount: u64,
 ) -> Result<()> {
     let market = &mut ctx.accounts.market;
     let pool = &mut ctx.accounts.pool;
+    let liquidity_provider = &mut ctx.accounts.liquidity_provider;
     let user_position = &mut ctx.accounts.user_position;
-    
-    // Validate market is active
+    let clock = Clock::get()?;
+
     require!(
-        market.status == MarketStatus::Active,
-        PredictionMarketError::MarketNotActive
+        lp_amount > 0,
+        PredictionMarketError::InvalidAmount
     );
-    
-    // Validate liquidity amount
     require!(
-        liquidity_amount > 0,
-        PredictionMarketError::InvalidAmount
+        !(pool.lock_after_cutoff && clock.unix_timestamp >= market.end_time),
+        PredictionMarketError::LiquidityLocked
     );
-    
     require!(
-        user_position.liquidity_provided >= liquidity_amount,
+        liquidity_provider.lp_tokens >= lp_amount,
         PredictionMarketError::InsufficientLiquidity
     );
-    
-    // Calculate withdrawal amount based on pool share
-    let total_liquidity = pool.total_liquidity;
+
+    let lp_supply = ctx.accounts.lp_mint.supply;
     require!(
-        total_liquidity > 0,
+        lp_supply > 0,
         PredictionMarketError::NoLiquidityInPool
     );
-    
-    let pool_balance = ctx.accounts.pool_token_account.amount;
-    let withdrawal_amount = (liquidity_amount as u128)
-        .checked_mul(pool_balance as u128)
-        .ok_or(PredictionMarketError::MathOverflow)?
-        .checked_div(total_liquidity as u128)
-        .ok_or(PredictionMarketError::MathOverflow)? as u64;
-    
+
+    // Bank fees and rewards earned so far before the LP balance changes
+    liquidity_provider.settle_fees(pool.fee_growth_per_share)?;
+    settle_liquidity_rewards(pool, ctx.accounts.liquidity_rewards.as_mut(), liquidity_provider)?;
+    let fees_earned = std::mem::take(&mut liquidity_provider.fees_owed);
+
+    // The provider's fraction of everything the LP tokens represent
+    let share_a = amm::pro_rata(pool.reserve_a, lp_amount, lp_supply)?;
+    let share_b = amm::pro_rata(pool.reserve_b, lp_amount, lp_supply)?;
+    let liquidity_share = amm::pro_rata(pool.total_liquidity, lp_amount, lp_supply)?;
+    let deposit_share = amm::pro_rata(
+        liquidity_provider.total_deposited,
+        lp_amount,
+        liquidity_provider.lp_tokens,
+    )?;
+
+    let merged = share_a.min(share_b);
+    let remaining_shares = [share_a - merged, share_b - merged];
+
     require!(
-        withdrawal_amount > 0,
+        merged > 0 || remaining_shares.iter().any(|shares| *shares > 0),
         PredictionMarketError::InvalidWithdrawalAmount
     );
-    
+
+    // Calculate fees (if any); they stay in the pool for remaining providers,
+    // so the last provider out has nobody to pay and is not charged
+    let withdrawal_fee_rate = pool.withdrawal_fee_rate_at(clock.unix_timestamp, market.resolution_time);
+    let fee_amount = if lp_amount < pool.total_supply {
+        amm::pro_rata(merged, withdrawal_fee_rate as u64, 10000)?
+    } else {
+        0
+    };
+    let net_withdrawal = (merged - fee_amount)
+        .checked_add(fees_earned)
+        .ok_or(PredictionMarketError::ArithmeticOverflow)?;
+
     require!(
-        pool_balance >= withdrawal_amount,
+        ctx.accounts.market_vault.amount >= net_withdrawal,
         PredictionMarketError::InsufficientPoolBalance
     );
-    
-    // Calculate fees (if any)
-    let fee_rate = pool.withdrawal_fee_rate;
-    let fee_amount = (withdrawal_amount as u128)
-        .checked_mul(fee_rate as u128)
-        .ok_or(PredictionMarketError::MathOverflow)?
-        .checked_div(10000u128)
-        .ok_or(PredictionMarketError::MathOverflow)? as u64;
-    
-    let net_withdrawal = withdrawal_amount
-        .checked_sub(fee_amount)
-        .ok_or(PredictionMarketError::MathOverflow)?;
-    
-    // Transfer tokens from pool to user
-    let market_key = market.key();
-    let pool_seeds = &[
-        b"pool",
-        market_key.as_ref(),
-        &[pool.bump],
+
+    let market_id_bytes = market.market_id.to_le_bytes();
+    let market_seeds = &[
+        b"market",
+        market_id_bytes.as_ref(),
+        &[market.bump],
     ];
-    let pool_signer = &[&pool_seeds[..]];
-    
-    let transfer_ctx = CpiContext::new_with_signer(
+    let signer_seeds = &[&market_seeds[..]];
+
+    // Burn the LP tokens being redeemed out of escrow
+    let burn_ctx = CpiContext::new_with_signer(
         ctx.accounts.token_program.to_account_info(),
-        Transfer {
-            from: ctx.accounts.pool_token_account.to_account_info(),
-            to: ctx.accounts.user_token_account.to_account_info(),
-            authority: pool.to_account_info(),
+        token::Burn {
+            mint: ctx.accounts.lp_mint.to_account_info(),
+            from: ctx.accounts.lp_escrow.to_account_info(),
+            authority: market.to_account_info(),
         },
-        pool_signer,
+        signer_seeds,
     );
-    
-    token::transfer(transfer_ctx, net_withdrawal)?;
-    
-    // Update user position
-    user_position.liquidity_provided = user_position.liquidity_provided
-        .checked_sub(liquidity_amount)
-        .ok_or(PredictionMarketError::MathOverflow)?;
-    
-    user_position.total_withdrawn = user_position.total_withdrawn
-        .checked_add(net_withdrawal)
-        .ok_or(PredictionMarketError::MathOverflow)?;
-    
+    token::burn(burn_ctx, lp_amount)?;
+
+    // Transfer collateral from the market vault to user
+    if net_withdrawal > 0 {
+        let transfer_ctx = CpiContext::new_with_signer(
+            ctx.accounts.token_program.to_account_info(),
+            Transfer {
+                from: ctx.accounts.market_vault.to_account_info(),
+                to: ctx.accounts.user_token_account.to_account_info(),
+                authority: market.to_account_info(),
+            },
+            signer_seeds,
+        );
+        token::transfer(transfer_ctx, net_withdrawal)?;
+    }
+
+    // Credit the unmatched outcome shares to the user's position
+    if user_position.user == Pubkey::default() {
+        user_position.user = ctx.accounts.user.key();
+        user_position.market = market.key();
+        user_position.bump = ctx.bumps.user_position;
+
+        market.open_positions = market.open_positions
+            .checked_add(1)
+            .ok_or(PredictionMarketError::ArithmeticOverflow)?;
+    }
+    for (index, shares) in remaining_shares.iter().enumerate() {
+        user_position.shares[index] = user_position.shares[index]
+            .checked_add(*shares)
+            .ok_or(PredictionMarketError::ArithmeticOverflow)?;
+        market.outcomes[index].shares = market.outcomes[index].shares
+            .checked_add(*shares)
+            .ok_or(PredictionMarketError::ArithmeticOverflow)?;
+    }
+    user_position.last_update_time = clock.unix_timestamp;
+
+    // Update liquidity provider account
+    liquidity_provider.lp_tokens -= lp_amount;
+    liquidity_provider.total_deposited -= deposit_share;
+    liquidity_provider.fees_claimed = liquidity_provider.fees_claimed
+        .checked_add(fees_earned)
+        .ok_or(PredictionMarketError::ArithmeticOverflow)?;
+
     // Update pool state
+    pool.reserve_a -= share_a;
+    pool.reserve_b -= share_b;
+    pool.fee_collected = pool.fee_collected
+        .checked_sub(fees_earned)
+        .ok_or(PredictionMarketError::ArithmeticUnderflow)?;
+    pool.total_supply = pool.total_supply
+        .checked_sub(lp_amount)
+        .ok_or(PredictionMarketError::ArithmeticUnderflow)?;
+    pool.accrue_fee(fee_amount)?;
     pool.total_liquidity = pool.total_liquidity
-        .checked_sub(liquidity_amount)
-        .ok_or(PredictionMarketError::MathOverflow)?;
-    
-    pool.total_fees_collected = pool.total_fees_collected
-        .checked_add(fee_amount)
-        .ok_or(PredictionMarketError::MathOverflow)?;
-    
-    // Update market liquidity
+        .checked_sub(liquidity_share)
+        .ok_or(PredictionMarketError::ArithmeticUnderflow)?;
+
+    // Update market liquidity and prices
     market.total_liquidity = market.total_liquidity
-        .checked_sub(liquidity_amount)
-        .ok_or(PredictionMarketError::MathOverflow)?;
-    
+        .checked_sub(liquidity_share)
+        .ok_or(PredictionMarketError::ArithmeticUnderflow)?;
+    if pool.reserve_a > 0 && pool.reserve_b > 0 {
+        let (price_a, price_b) = amm::spot_prices(pool.reserve_a, pool.reserve_b)?;
+        market.outcomes[OUTCOME_A as usize].price = price_a;
+        market.outcomes[OUTCOME_B as usize].price = price_b;
+    }
+
     // Emit event
     emit!(LiquidityRemovedEvent {
         user: ctx.accounts.user.key(),
         market: market.key(),
-        liquidity_amount,
+        lp_amount,
         withdrawal_amount: net_withdrawal,
         fee_amount,
-        timestamp: Clock::get()?.unix_timestamp,
+        withdrawal_fee_rate,
+        fees_earned,
+        shares_a: remaining_shares[0],
+        shares_b: remaining_shares[1],
+        timestamp: clock.unix_timestamp,
     });
-    
+
     Ok(())
 }
 
@@ -171,9 +271,12 @@ pub fn remove_liquidity(
 pub struct LiquidityRemovedEvent {
     pub user: Pubkey,
     pub market: Pubkey,
-    pub liquidity_amount: u64,
+    pub lp_amount: u64,
     pub withdrawal_amount: u64,
     pub fee_amount: u64,
+    pub withdrawal_fee_rate: u16,
+    pub fees_earned: u64,
+    pub shares_a: u64,
+    pub shares_b: u64,
     pub timestamp: i64,
 }
-```
\ No newline at end of file
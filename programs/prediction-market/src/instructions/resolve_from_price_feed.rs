@@ -0,0 +1,87 @@
+use anchor_lang::prelude::*;
+
+use crate::instructions::resolve_market::MarketResolvedEvent;
+use crate::price_feed;
+use crate::state::*;
+use crate::error::*;
+
+#[derive(Accounts)]
+pub struct ResolveFromPriceFeed<'info> {
+    #[account(
+        mut,
+        seeds = [b"market", market.market_id.to_le_bytes().as_ref()],
+        bump = market.bump,
+        has_one = price_feed @ PredictionMarketError::InvalidPriceFeed,
+        constraint = market.status == MarketStatus::Active @ PredictionMarketError::MarketNotActive,
+        constraint = market.end_time <= Clock::get()?.unix_timestamp @ PredictionMarketError::MarketNotExpired,
+        constraint = !market.price_window_closed(Clock::get()?.unix_timestamp) @ PredictionMarketError::StalePrice,
+    )]
+    pub market: Box<Account<'info, PredictionMarket>>,
+
+    /// CHECK: pinned by `has_one` and parsed by `price_feed::load_price`
+    pub price_feed: UncheckedAccount<'info>,
+
+    pub resolver: Signer<'info>,
+}
+
+/// Permissionless resolution of a price threshold market. The feed price
+/// must have been published at or after `end_time` and within the market's
+/// staleness and confidence limits. Whichever update the feed holds when the
+/// first caller lands settles the market, so any price published in that
+/// window may be picked. Once the window has closed the market can no longer
+/// be resolved this way, and anyone may cancel it instead (see
+/// `cancel_market`).
+pub fn resolve_from_price_feed(ctx: Context<ResolveFromPriceFeed>) -> Result<()> {
+    let market = &mut ctx.accounts.market;
+    let now = Clock::get()?.unix_timestamp;
+
+    let price_data = price_feed::load_price(&ctx.accounts.price_feed.to_account_info())?;
+    let outcome = price_feed::threshold_outcome(
+        &price_data,
+        market.strike_price,
+        market.end_time,
+        market.max_price_staleness,
+        market.max_price_confidence_bps,
+    )?;
+    let params = ResolutionParams {
+        outcome,
+        resolution_price: 0,
+    };
+
+    market.oracle_data = price_data.try_to_vec()?;
+    let (winning_pool, losing_pool) = market.settle(&params, 0, now)?;
+
+    emit!(PriceFeedResolvedEvent {
+        market: market.key(),
+        resolver: ctx.accounts.resolver.key(),
+        price: price_data.price,
+        confidence: price_data.confidence,
+        price_timestamp: price_data.timestamp,
+        strike_price: market.strike_price,
+        outcome,
+    });
+
+    emit!(MarketResolvedEvent {
+        market: market.key(),
+        outcome: market.winning_outcome,
+        resolution_price: market.resolution_price,
+        winning_pool,
+        losing_pool,
+        oracle_fee: 0,
+        resolution_timestamp: market.resolution_timestamp,
+        dispute_end_time: market.dispute_end_time,
+    });
+
+    Ok(())
+}
+
+#[event]
+pub struct PriceFeedResolvedEvent {
+    pub market: Pubkey,
+    pub resolver: Pubkey,
+    pub price: u64,
+    pub confidence: u64,
+    pub price_timestamp: i64,
+    pub strike_price: u64,
+    pub outcome: u8,
+}
@@ -0,0 +1,163 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::{self, Token, TokenAccount, Transfer};
+
+use crate::state::*;
+use crate::error::*;
+use crate::utils::calculate_platform_fee;
+use crate::instructions::register_referrer::record_referred_trade;
+
+#[derive(Accounts)]
+pub struct SellBet<'info> {
+    #[account(
+        mut,
+        seeds = [b"market", market.market_id.to_le_bytes().as_ref()],
+        bump = market.bump,
+        constraint = !market.is_halted(PAUSE_TRADING) @ PredictionMarketError::MarketPaused,
+        constraint = market.status == MarketStatus::Active @ PredictionMarketError::MarketNotActive,
+        constraint = market.pricing_model == PricingModel::Parimutuel @ PredictionMarketError::InvalidPricingModel,
+    )]
+    pub market: Account<'info, PredictionMarket>,
+
+    #[account(
+        mut,
+        seeds = [b"bet", market.key().as_ref(), user.key().as_ref()],
+        bump = bet.bump,
+        constraint = bet.bettor == user.key() @ PredictionMarketError::NoPredictionFound,
+    )]
+    pub bet: Account<'info, Bet>,
+
+    #[account(
+        mut,
+        seeds = [b"market_vault", market.key().as_ref()],
+        bump = market.vault_bump,
+    )]
+    pub market_vault: Account<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        constraint = user_token_account.mint == market.quote_mint @ PredictionMarketError::InvalidTokenMint,
+        constraint = user_token_account.owner == user.key() @ PredictionMarketError::InvalidTokenAccountOwner,
+    )]
+    pub user_token_account: Account<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        seeds = [b"global_state"],
+        bump = global_state.bump,
+        constraint = !global_state.is_halted(PAUSE_TRADING) @ PredictionMarketError::MarketPaused,
+    )]
+    pub global_state: Box<Account<'info, GlobalState>>,
+
+    pub user: Signer<'info>,
+
+    /// Partner frontend that referred this trade
+    #[account(
+        mut,
+        seeds = [b"referrer", market.quote_mint.as_ref(), referrer.authority.as_ref()],
+        bump = referrer.bump,
+    )]
+    pub referrer: Option<Box<Account<'info, Referrer>>>,
+
+    pub token_program: Program<'info, Token>,
+}
+
+/// Sells `amount` of a parimutuel stake back to the market before trading
+/// closes. At the pool's implied price a stake is worth exactly what it
+/// contributes to its outcome pool, so the outcome pool shrinks by the full
+/// amount. The seller receives it back less the creator fee and the
+/// market's exit fee; the exit fee stays in the pot for the eventual
+/// winners, so backing out of a stake after the odds turn is not free.
+pub fn sell_bet(ctx: Context<SellBet>, amount: u64) -> Result<()> {
+    let market = &mut ctx.accounts.market;
+    let bet = &mut ctx.accounts.bet;
+    let clock = Clock::get()?;
+
+    require!(amount > 0, PredictionMarketError::InvalidAmount);
+    require!(
+        clock.unix_timestamp < market.end_time,
+        PredictionMarketError::MarketExpired
+    );
+    require!(bet.amount >= amount, PredictionMarketError::InsufficientPosition);
+
+    let market_outcome = market.outcome_mut(bet.outcome)?;
+    market_outcome.pool = market_outcome.pool
+        .checked_sub(amount)
+        .ok_or(PredictionMarketError::InsufficientLiquidity)?;
+
+    let market_id_bytes = market.market_id.to_le_bytes();
+    let market_seeds = &[
+        b"market",
+        market_id_bytes.as_ref(),
+        &[market.bump],
+    ];
+    let signer_seeds = &[&market_seeds[..]];
+
+    let exit_fee = calculate_platform_fee(amount, market.exit_fee_rate)?;
+    let creator_fee = market.accrue_creator_fee(amount)?;
+    let proceeds = amount
+        .checked_sub(exit_fee)
+        .and_then(|amount| amount.checked_sub(creator_fee))
+        .ok_or(PredictionMarketError::ArithmeticUnderflow)?;
+    market.forfeited_stakes = market.forfeited_stakes
+        .checked_add(exit_fee)
+        .ok_or(PredictionMarketError::ArithmeticOverflow)?;
+
+    let transfer_ctx = CpiContext::new_with_signer(
+        ctx.accounts.token_program.to_account_info(),
+        Transfer {
+            from: ctx.accounts.market_vault.to_account_info(),
+            to: ctx.accounts.user_token_account.to_account_info(),
+            authority: market.to_account_info(),
+        },
+        signer_seeds,
+    );
+    token::transfer(transfer_ctx, proceeds)?;
+
+    if let Some(referrer) = ctx.accounts.referrer.as_mut() {
+        record_referred_trade(referrer, &mut bet.referrer, ctx.accounts.user.key(), market.key(), amount)?;
+    }
+
+    bet.amount -= amount;
+    if bet.amount == 0 {
+        // A fully sold bet no longer counts as open; buying back in reopens it
+        market.total_bets = market.total_bets
+            .checked_sub(1)
+            .ok_or(PredictionMarketError::ArithmeticUnderflow)?;
+        market.open_positions = market.open_positions
+            .checked_sub(1)
+            .ok_or(PredictionMarketError::ArithmeticUnderflow)?;
+    }
+    market.refresh_parimutuel_prices()?;
+
+    let global_state = &mut ctx.accounts.global_state;
+    global_state.total_volume = global_state.total_volume
+        .checked_add(amount)
+        .ok_or(PredictionMarketError::ArithmeticOverflow)?;
+
+    emit!(BetSoldEvent {
+        market: market.key(),
+        bettor: bet.bettor,
+        outcome: bet.outcome,
+        amount,
+        exit_fee,
+        creator_fee,
+        remaining: bet.amount,
+        price: market.outcome(bet.outcome)?.price,
+        timestamp: clock.unix_timestamp,
+    });
+
+    Ok(())
+}
+
+#[event]
+pub struct BetSoldEvent {
+    pub market: Pubkey,
+    pub bettor: Pubkey,
+    pub outcome: u8,
+    pub amount: u64,
+    pub exit_fee: u64,
+    pub creator_fee: u64,
+    pub remaining: u64,
+    pub price: u64,
+    pub timestamp: i64,
+}
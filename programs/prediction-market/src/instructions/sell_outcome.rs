@@ -0,0 +1,217 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::{self, Token, TokenAccount, Transfer};
+
+use crate::amm;
+use crate::state::*;
+use crate::error::*;
+use crate::instructions::register_referrer::record_referred_trade;
+
+#[derive(Accounts)]
+pub struct SellOutcome<'info> {
+    #[account(
+        mut,
+        seeds = [b"market", market.market_id.to_le_bytes().as_ref()],
+        bump = market.bump,
+        constraint = !market.is_halted(PAUSE_TRADING) @ PredictionMarketError::MarketPaused,
+        constraint = market.status == MarketStatus::Active @ PredictionMarketError::MarketNotActive,
+        constraint = market.pricing_model == PricingModel::ConstantProduct @ PredictionMarketError::InvalidPricingModel,
+    )]
+    pub market: Box<Account<'info, PredictionMarket>>,
+
+    #[account(
+        mut,
+        seeds = [b"pool", market.key().as_ref()],
+        bump = pool.bump,
+    )]
+    pub pool: Box<Account<'info, LiquidityPool>>,
+
+    #[account(
+        mut,
+        seeds = [b"user_position", user.key().as_ref(), market.key().as_ref()],
+        bump = user_position.bump,
+        constraint = user_position.user == user.key() @ PredictionMarketError::InvalidUser,
+    )]
+    pub user_position: Box<Account<'info, UserPosition>>,
+
+    #[account(
+        mut,
+        seeds = [b"market_vault", market.key().as_ref()],
+        bump = market.vault_bump,
+    )]
+    pub market_vault: Account<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        constraint = user_token_account.mint == market.quote_mint @ PredictionMarketError::InvalidTokenMint,
+        constraint = user_token_account.owner == user.key() @ PredictionMarketError::InvalidTokenAccountOwner,
+    )]
+    pub user_token_account: Account<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        seeds = [b"global_state"],
+        bump = global_state.bump,
+        constraint = !global_state.is_halted(PAUSE_TRADING) @ PredictionMarketError::MarketPaused,
+    )]
+    pub global_state: Box<Account<'info, GlobalState>>,
+
+    pub user: Signer<'info>,
+
+    /// Partner frontend that referred this trade
+    #[account(
+        mut,
+        seeds = [b"referrer", market.quote_mint.as_ref(), referrer.authority.as_ref()],
+        bump = referrer.bump,
+    )]
+    pub referrer: Option<Box<Account<'info, Referrer>>>,
+
+    pub token_program: Program<'info, Token>,
+}
+
+/// Sells `params.amount` outcome shares back to the pool for collateral.
+pub fn sell_outcome(ctx: Context<SellOutcome>, params: TradeParams) -> Result<()> {
+    let market = &mut ctx.accounts.market;
+    let pool = &mut ctx.accounts.pool;
+    let user_position = &mut ctx.accounts.user_position;
+    let clock = Clock::get()?;
+
+    require!(params.amount > 0, PredictionMarketError::InvalidAmount);
+    require!(
+        params.outcome == OUTCOME_A || params.outcome == OUTCOME_B,
+        PredictionMarketError::InvalidOutcome
+    );
+    require!(
+        clock.unix_timestamp < market.end_time,
+        PredictionMarketError::MarketExpired
+    );
+    require!(
+        user_position.shares[params.outcome as usize] >= params.amount,
+        PredictionMarketError::InsufficientShares
+    );
+
+    let (reserve_in, reserve_other) = if params.outcome == OUTCOME_A {
+        (pool.reserve_a, pool.reserve_b)
+    } else {
+        (pool.reserve_b, pool.reserve_a)
+    };
+    let gross_amount = amm::calculate_sell_collateral(reserve_in, reserve_other, params.amount)?;
+    require!(gross_amount > 0, PredictionMarketError::InsufficientLiquidity);
+
+    let fee = (gross_amount as u128)
+        .checked_mul(pool.fee_rate as u128)
+        .ok_or(PredictionMarketError::ArithmeticOverflow)?
+        .checked_div(10000)
+        .ok_or(PredictionMarketError::DivisionByZero)? as u64;
+    let creator_fee = market.accrue_creator_fee(gross_amount)?;
+    let net_amount = gross_amount
+        .checked_sub(fee)
+        .and_then(|amount| amount.checked_sub(creator_fee))
+        .ok_or(PredictionMarketError::ArithmeticUnderflow)?;
+
+    let executed_price = amm::execution_price(net_amount, params.amount)?;
+    amm::check_slippage(params.quoted_price, executed_price, params.max_slippage, false)?;
+
+    let market_id_bytes = market.market_id.to_le_bytes();
+    let market_seeds = &[
+        b"market",
+        market_id_bytes.as_ref(),
+        &[market.bump],
+    ];
+    let signer_seeds = &[&market_seeds[..]];
+
+    let transfer_ctx = CpiContext::new_with_signer(
+        ctx.accounts.token_program.to_account_info(),
+        Transfer {
+            from: ctx.accounts.market_vault.to_account_info(),
+            to: ctx.accounts.user_token_account.to_account_info(),
+            authority: market.to_account_info(),
+        },
+        signer_seeds,
+    );
+    token::transfer(transfer_ctx, net_amount)?;
+
+    // Sold shares enter the pool, then complete sets worth the gross amount
+    // are burned from both reserves to release the collateral
+    let new_reserve_in = reserve_in
+        .checked_add(params.amount)
+        .and_then(|reserve| reserve.checked_sub(gross_amount))
+        .ok_or(PredictionMarketError::ArithmeticUnderflow)?;
+    let new_reserve_other = reserve_other
+        .checked_sub(gross_amount)
+        .ok_or(PredictionMarketError::ArithmeticUnderflow)?;
+    if params.outcome == OUTCOME_A {
+        pool.reserve_a = new_reserve_in;
+        pool.reserve_b = new_reserve_other;
+    } else {
+        pool.reserve_b = new_reserve_in;
+        pool.reserve_a = new_reserve_other;
+    }
+    pool.accrue_fee(fee)?;
+
+    user_position.shares[params.outcome as usize] = user_position.shares[params.outcome as usize]
+        .checked_sub(params.amount)
+        .ok_or(PredictionMarketError::ArithmeticUnderflow)?;
+    user_position.total_withdrawn = net_amount
+        .checked_add(creator_fee)
+        .and_then(|withdrawn| user_position.total_withdrawn.checked_add(withdrawn))
+        .ok_or(PredictionMarketError::ArithmeticOverflow)?;
+    user_position.lp_fees_paid = user_position.lp_fees_paid
+        .checked_add(fee)
+        .ok_or(PredictionMarketError::ArithmeticOverflow)?;
+    user_position.last_update_time = clock.unix_timestamp;
+
+    if let Some(referrer) = ctx.accounts.referrer.as_mut() {
+        record_referred_trade(
+            referrer,
+            &mut user_position.referrer,
+            ctx.accounts.user.key(),
+            market.key(),
+            gross_amount,
+        )?;
+    }
+
+    let market_outcome = market.outcome_mut(params.outcome)?;
+    market_outcome.shares = market_outcome.shares
+        .checked_sub(params.amount)
+        .ok_or(PredictionMarketError::ArithmeticUnderflow)?;
+
+    let (price_a, price_b) = amm::spot_prices(pool.reserve_a, pool.reserve_b)?;
+    market.outcomes[OUTCOME_A as usize].price = price_a;
+    market.outcomes[OUTCOME_B as usize].price = price_b;
+
+    let global_state = &mut ctx.accounts.global_state;
+    global_state.total_volume = global_state.total_volume
+        .checked_add(net_amount)
+        .ok_or(PredictionMarketError::ArithmeticOverflow)?;
+
+    emit!(OutcomeSoldEvent {
+        market: market.key(),
+        user: ctx.accounts.user.key(),
+        outcome: params.outcome,
+        shares: params.amount,
+        amount: net_amount,
+        fee,
+        creator_fee,
+        executed_price,
+        price_a,
+        price_b,
+        timestamp: clock.unix_timestamp,
+    });
+
+    Ok(())
+}
+
+#[event]
+pub struct OutcomeSoldEvent {
+    pub market: Pubkey,
+    pub user: Pubkey,
+    pub outcome: u8,
+    pub shares: u64,
+    pub amount: u64,
+    pub fee: u64,
+    pub creator_fee: u64,
+    pub executed_price: u64,
+    pub price_a: u64,
+    pub price_b: u64,
+    pub timestamp: i64,
+}
@@ -0,0 +1,172 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::{self, Token, TokenAccount, Transfer};
+
+use crate::lmsr;
+use crate::state::*;
+use crate::error::*;
+use crate::instructions::register_referrer::record_referred_trade;
+
+#[derive(Accounts)]
+pub struct SellShares<'info> {
+    #[account(
+        mut,
+        seeds = [b"market", market.market_id.to_le_bytes().as_ref()],
+        bump = market.bump,
+        constraint = !market.is_halted(PAUSE_TRADING) @ PredictionMarketError::MarketPaused,
+        constraint = market.status == MarketStatus::Active @ PredictionMarketError::MarketNotActive,
+        constraint = market.pricing_model == PricingModel::Lmsr @ PredictionMarketError::InvalidPricingModel,
+    )]
+    pub market: Box<Account<'info, PredictionMarket>>,
+
+    #[account(
+        mut,
+        seeds = [b"user_position", user.key().as_ref(), market.key().as_ref()],
+        bump = user_position.bump,
+        constraint = user_position.user == user.key() @ PredictionMarketError::InvalidUser,
+    )]
+    pub user_position: Box<Account<'info, UserPosition>>,
+
+    #[account(
+        mut,
+        seeds = [b"market_vault", market.key().as_ref()],
+        bump = market.vault_bump,
+    )]
+    pub market_vault: Account<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        constraint = user_token_account.mint == market.quote_mint @ PredictionMarketError::InvalidTokenMint,
+        constraint = user_token_account.owner == user.key() @ PredictionMarketError::InvalidTokenAccountOwner,
+    )]
+    pub user_token_account: Account<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        seeds = [b"global_state"],
+        bump = global_state.bump,
+        constraint = !global_state.is_halted(PAUSE_TRADING) @ PredictionMarketError::MarketPaused,
+    )]
+    pub global_state: Box<Account<'info, GlobalState>>,
+
+    pub user: Signer<'info>,
+
+    /// Partner frontend that referred this trade
+    #[account(
+        mut,
+        seeds = [b"referrer", market.quote_mint.as_ref(), referrer.authority.as_ref()],
+        bump = referrer.bump,
+    )]
+    pub referrer: Option<Box<Account<'info, Referrer>>>,
+
+    pub token_program: Program<'info, Token>,
+}
+
+pub fn sell_shares(
+    ctx: Context<SellShares>,
+    outcome: u8,
+    shares: u64,
+    min_proceeds: u64,
+) -> Result<()> {
+    let market = &mut ctx.accounts.market;
+    let user_position = &mut ctx.accounts.user_position;
+    let clock = Clock::get()?;
+
+    require!(shares > 0, PredictionMarketError::InvalidAmount);
+    require!(market.is_valid_outcome(outcome), PredictionMarketError::InvalidOutcome);
+    require!(
+        clock.unix_timestamp < market.end_time,
+        PredictionMarketError::MarketExpired
+    );
+    require!(
+        user_position.shares[outcome as usize] >= shares,
+        PredictionMarketError::InsufficientShares
+    );
+
+    let proceeds = lmsr::sell_proceeds(
+        &market.outcome_shares(),
+        market.lmsr_liquidity,
+        outcome as usize,
+        shares,
+    )?;
+    let creator_fee = market.accrue_creator_fee(proceeds)?;
+    let net_proceeds = proceeds - creator_fee;
+    require!(net_proceeds >= min_proceeds, PredictionMarketError::TradeLimitExceeded);
+
+    if net_proceeds > 0 {
+        let market_id_bytes = market.market_id.to_le_bytes();
+        let market_seeds = &[
+            b"market",
+            market_id_bytes.as_ref(),
+            &[market.bump],
+        ];
+        let signer_seeds = &[&market_seeds[..]];
+
+        let transfer_ctx = CpiContext::new_with_signer(
+            ctx.accounts.token_program.to_account_info(),
+            Transfer {
+                from: ctx.accounts.market_vault.to_account_info(),
+                to: ctx.accounts.user_token_account.to_account_info(),
+                authority: market.to_account_info(),
+            },
+            signer_seeds,
+        );
+        token::transfer(transfer_ctx, net_proceeds)?;
+    }
+
+    user_position.shares[outcome as usize] -= shares;
+    user_position.total_withdrawn = user_position.total_withdrawn
+        .checked_add(proceeds)
+        .ok_or(PredictionMarketError::ArithmeticOverflow)?;
+    user_position.last_update_time = clock.unix_timestamp;
+
+    if let Some(referrer) = ctx.accounts.referrer.as_mut() {
+        record_referred_trade(
+            referrer,
+            &mut user_position.referrer,
+            ctx.accounts.user.key(),
+            market.key(),
+            proceeds,
+        )?;
+    }
+
+    // Update outstanding shares and re-quote every outcome
+    let market_outcome = market.outcome_mut(outcome)?;
+    market_outcome.shares = market_outcome.shares
+        .checked_sub(shares)
+        .ok_or(PredictionMarketError::ArithmeticUnderflow)?;
+
+    let prices = lmsr::prices(&market.outcome_shares(), market.lmsr_liquidity)?;
+    for (market_outcome, price) in market.outcomes.iter_mut().zip(prices.iter()) {
+        market_outcome.price = *price;
+    }
+
+    let global_state = &mut ctx.accounts.global_state;
+    global_state.total_volume = global_state.total_volume
+        .checked_add(proceeds)
+        .ok_or(PredictionMarketError::ArithmeticOverflow)?;
+
+    emit!(SharesSoldEvent {
+        market: market.key(),
+        user: ctx.accounts.user.key(),
+        outcome,
+        shares,
+        proceeds: net_proceeds,
+        creator_fee,
+        prices,
+        timestamp: clock.unix_timestamp,
+    });
+
+    Ok(())
+}
+
+#[event]
+pub struct SharesSoldEvent {
+    pub market: Pubkey,
+    pub user: Pubkey,
+    pub outcome: u8,
+    pub shares: u64,
+    pub proceeds: u64,
+    pub creator_fee: u64,
+    pub prices: Vec<u64>,
+    pub timestamp: i64,
+}
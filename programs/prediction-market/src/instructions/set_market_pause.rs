@@ -0,0 +1,56 @@
+use anchor_lang::prelude::*;
+
+use crate::state::*;
+use crate::error::*;
+
+#[derive(Accounts)]
+pub struct SetMarketPause<'info> {
+    #[account(
+        mut,
+        seeds = [b"market", market.market_id.to_le_bytes().as_ref()],
+        bump = market.bump,
+    )]
+    pub market: Box<Account<'info, PredictionMarket>>,
+
+    #[account(
+        seeds = [b"global_state"],
+        bump = global_state.bump,
+    )]
+    pub global_state: Box<Account<'info, GlobalState>>,
+
+    pub signer: Signer<'info>,
+}
+
+/// Pauses instruction classes on a single market. Leaving `PAUSE_CLAIMS`
+/// out halts trading and liquidity while holders can still claim and exit.
+/// The admin can set any combination; the guardian can only add pauses.
+pub fn set_market_pause(ctx: Context<SetMarketPause>, paused_classes: u8) -> Result<()> {
+    let market = &mut ctx.accounts.market;
+    let signer = ctx.accounts.signer.key();
+
+    require!(paused_classes & !PAUSE_ALL_CLASSES == 0, PredictionMarketError::InvalidPauseFlags);
+    let lifts_pause = market.paused_classes & !paused_classes != 0;
+    require!(
+        ctx.accounts.global_state.can_set_pause(signer, lifts_pause),
+        PredictionMarketError::UnauthorizedAdmin
+    );
+
+    market.paused_classes = paused_classes;
+
+    emit!(MarketPauseUpdatedEvent {
+        market: market.key(),
+        updated_by: signer,
+        paused_classes,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    Ok(())
+}
+
+#[event]
+pub struct MarketPauseUpdatedEvent {
+    pub market: Pubkey,
+    pub updated_by: Pubkey,
+    pub paused_classes: u8,
+    pub timestamp: i64,
+}
@@ -0,0 +1,73 @@
+use anchor_lang::prelude::*;
+
+use crate::state::*;
+use crate::error::*;
+
+#[derive(Accounts)]
+#[instruction(feed_id: u64)]
+pub struct SetMockPriceFeed<'info> {
+    #[account(
+        init_if_needed,
+        payer = authority,
+        space = MockPriceFeed::LEN,
+        seeds = [b"mock_price_feed", authority.key().as_ref(), feed_id.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub mock_price_feed: Account<'info, MockPriceFeed>,
+
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// Creates or updates a mock price feed. Its authority controls the price
+/// outright, so it fails unless the program is built with the `mock-feed`
+/// feature. A `timestamp` of zero stamps the current time.
+pub fn set_mock_price_feed(
+    ctx: Context<SetMockPriceFeed>,
+    feed_id: u64,
+    price: u64,
+    confidence: u64,
+    timestamp: i64,
+) -> Result<()> {
+    require!(cfg!(feature = "mock-feed"), PredictionMarketError::MockPriceFeedDisabled);
+
+    let mock_price_feed = &mut ctx.accounts.mock_price_feed;
+
+    if mock_price_feed.authority == Pubkey::default() {
+        mock_price_feed.authority = ctx.accounts.authority.key();
+        mock_price_feed.feed_id = feed_id;
+        mock_price_feed.bump = ctx.bumps.mock_price_feed;
+    }
+    require_keys_eq!(
+        mock_price_feed.authority,
+        ctx.accounts.authority.key(),
+        PredictionMarketError::InvalidOracleAuthority
+    );
+
+    mock_price_feed.price = price;
+    mock_price_feed.confidence = confidence;
+    mock_price_feed.timestamp = if timestamp == 0 {
+        Clock::get()?.unix_timestamp
+    } else {
+        timestamp
+    };
+
+    emit!(MockPriceFeedUpdatedEvent {
+        mock_price_feed: mock_price_feed.key(),
+        price,
+        confidence,
+        timestamp: mock_price_feed.timestamp,
+    });
+
+    Ok(())
+}
+
+#[event]
+pub struct MockPriceFeedUpdatedEvent {
+    pub mock_price_feed: Pubkey,
+    pub price: u64,
+    pub confidence: u64,
+    pub timestamp: i64,
+}
@@ -0,0 +1,52 @@
+use anchor_lang::prelude::*;
+
+use crate::state::*;
+use crate::error::*;
+
+#[derive(Accounts)]
+pub struct SetProgramPause<'info> {
+    #[account(
+        mut,
+        seeds = [b"global_state"],
+        bump = global_state.bump,
+    )]
+    pub global_state: Box<Account<'info, GlobalState>>,
+
+    pub signer: Signer<'info>,
+}
+
+/// Sets the program-wide halt and the paused instruction classes
+/// (`PAUSE_TRADING`, `PAUSE_LIQUIDITY`, `PAUSE_CLAIMS`). The admin can set
+/// any combination; the guardian can only add pauses.
+pub fn set_program_pause(ctx: Context<SetProgramPause>, is_paused: bool, paused_classes: u8) -> Result<()> {
+    let global_state = &mut ctx.accounts.global_state;
+    let signer = ctx.accounts.signer.key();
+
+    require!(paused_classes & !PAUSE_ALL_CLASSES == 0, PredictionMarketError::InvalidPauseFlags);
+    let lifts_pause = (global_state.is_paused && !is_paused)
+        || global_state.paused_classes & !paused_classes != 0;
+    require!(
+        global_state.can_set_pause(signer, lifts_pause),
+        PredictionMarketError::UnauthorizedAdmin
+    );
+
+    global_state.is_paused = is_paused;
+    global_state.paused_classes = paused_classes;
+
+    emit!(ProgramPauseUpdatedEvent {
+        updated_by: signer,
+        is_paused,
+        paused_classes,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    Ok(())
+}
+
+#[event]
+pub struct ProgramPauseUpdatedEvent {
+    pub updated_by: Pubkey,
+    pub is_paused: bool,
+    pub paused_classes: u8,
+    pub timestamp: i64,
+}
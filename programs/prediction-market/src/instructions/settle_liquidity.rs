@@ -0,0 +1,213 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::{self, Mint, Token, TokenAccount, Transfer};
+
+use crate::amm;
+use crate::state::*;
+use crate::error::*;
+use crate::instructions::claim_liquidity_rewards::settle_liquidity_rewards;
+
+#[derive(Accounts)]
+pub struct SettleLiquidity<'info> {
+    #[account(
+        mut,
+        seeds = [b"market", market.market_id.to_le_bytes().as_ref()],
+        bump = market.bump,
+        has_one = lp_mint,
+        constraint = !market.is_halted(PAUSE_CLAIMS) @ PredictionMarketError::MarketPaused,
+        constraint = matches!(market.status, MarketStatus::Resolved | MarketStatus::Cancelled)
+            @ PredictionMarketError::MarketNotResolved,
+    )]
+    pub market: Box<Account<'info, PredictionMarket>>,
+
+    #[account(
+        mut,
+        seeds = [b"pool", market.key().as_ref()],
+        bump = pool.bump,
+    )]
+    pub pool: Box<Account<'info, LiquidityPool>>,
+
+    #[account(
+        mut,
+        seeds = [b"liquidity_provider", market.key().as_ref(), user.key().as_ref()],
+        bump = liquidity_provider.bump,
+        constraint = liquidity_provider.user == user.key() @ PredictionMarketError::InvalidUser,
+    )]
+    pub liquidity_provider: Box<Account<'info, LiquidityProvider>>,
+
+    #[account(
+        mut,
+        seeds = [b"market_vault", market.key().as_ref()],
+        bump = market.vault_bump,
+    )]
+    pub market_vault: Box<Account<'info, TokenAccount>>,
+
+    #[account(
+        mut,
+        constraint = user_token_account.mint == market.quote_mint @ PredictionMarketError::InvalidTokenMint,
+        constraint = user_token_account.owner == user.key() @ PredictionMarketError::InvalidTokenAccountOwner,
+    )]
+    pub user_token_account: Box<Account<'info, TokenAccount>>,
+
+    #[account(
+        mut,
+        seeds = [b"lp_escrow", market.key().as_ref()],
+        bump = pool.lp_escrow_bump,
+    )]
+    pub lp_escrow: Box<Account<'info, TokenAccount>>,
+
+    #[account(mut)]
+    pub lp_mint: Box<Account<'info, Mint>>,
+
+    #[account(
+        seeds = [b"global_state"],
+        bump = global_state.bump,
+        constraint = !global_state.is_halted(PAUSE_CLAIMS) @ PredictionMarketError::MarketPaused,
+    )]
+    pub global_state: Box<Account<'info, GlobalState>>,
+
+    pub user: Signer<'info>,
+
+    /// Required once the market runs a liquidity mining program
+    #[account(
+        mut,
+        seeds = [b"liquidity_rewards", market.key().as_ref()],
+        bump = liquidity_rewards.bump,
+    )]
+    pub liquidity_rewards: Option<Box<Account<'info, LiquidityRewards>>>,
+
+    pub token_program: Program<'info, Token>,
+}
+
+/// Exits all of a provider's liquidity once the market is over, burning
+/// their LP tokens. After resolution their share of each reserve is valued
+/// at the settled payout per share and paid out in collateral together with
+/// the trading fees they earned. After a cancellation traders are refunded
+/// their cost basis, which leaves the pool with the collateral providers put
+/// in, so each gets their pro-rata share of the remaining liquidity instead,
+/// again with the fees they earned.
+pub fn settle_liquidity(ctx: Context<SettleLiquidity>) -> Result<()> {
+    let market = &mut ctx.accounts.market;
+    let pool = &mut ctx.accounts.pool;
+    let liquidity_provider = &mut ctx.accounts.liquidity_provider;
+
+    require!(
+        market.status == MarketStatus::Cancelled
+            || market.dispute_end_time <= Clock::get()?.unix_timestamp,
+        PredictionMarketError::DisputeWindowOpen
+    );
+
+    let lp_amount = liquidity_provider.lp_tokens;
+    let lp_supply = ctx.accounts.lp_mint.supply;
+    require!(
+        lp_amount > 0 && lp_supply > 0,
+        PredictionMarketError::NoLiquidityInPool
+    );
+
+    liquidity_provider.settle_fees(pool.fee_growth_per_share)?;
+    settle_liquidity_rewards(pool, ctx.accounts.liquidity_rewards.as_mut(), liquidity_provider)?;
+    let fees_owed = std::mem::take(&mut liquidity_provider.fees_owed);
+
+    let share_a = amm::pro_rata(pool.reserve_a, lp_amount, lp_supply)?;
+    let share_b = amm::pro_rata(pool.reserve_b, lp_amount, lp_supply)?;
+    let liquidity_share = amm::pro_rata(pool.total_liquidity, lp_amount, lp_supply)?;
+
+    let reserve_value = if market.status == MarketStatus::Resolved {
+        amm::pro_rata(share_a, market.payout_per_share(OUTCOME_A)?, PRICE_PRECISION)?
+            .checked_add(amm::pro_rata(share_b, market.payout_per_share(OUTCOME_B)?, PRICE_PRECISION)?)
+            .ok_or(PredictionMarketError::ArithmeticOverflow)?
+    } else {
+        liquidity_share
+    };
+    let payout = reserve_value
+        .checked_add(fees_owed)
+        .ok_or(PredictionMarketError::ArithmeticOverflow)?;
+    require!(
+        ctx.accounts.market_vault.amount >= payout,
+        PredictionMarketError::InsufficientVaultFunds
+    );
+
+    let market_id_bytes = market.market_id.to_le_bytes();
+    let market_seeds = &[
+        b"market",
+        market_id_bytes.as_ref(),
+        &[market.bump],
+    ];
+    let signer_seeds = &[&market_seeds[..]];
+
+    // Burn the provider's LP tokens out of escrow
+    let burn_ctx = CpiContext::new_with_signer(
+        ctx.accounts.token_program.to_account_info(),
+        token::Burn {
+            mint: ctx.accounts.lp_mint.to_account_info(),
+            from: ctx.accounts.lp_escrow.to_account_info(),
+            authority: market.to_account_info(),
+        },
+        signer_seeds,
+    );
+    token::burn(burn_ctx, lp_amount)?;
+
+    if payout > 0 {
+        let transfer_ctx = CpiContext::new_with_signer(
+            ctx.accounts.token_program.to_account_info(),
+            Transfer {
+                from: ctx.accounts.market_vault.to_account_info(),
+                to: ctx.accounts.user_token_account.to_account_info(),
+                authority: market.to_account_info(),
+            },
+            signer_seeds,
+        );
+        token::transfer(transfer_ctx, payout)?;
+    }
+
+    liquidity_provider.lp_tokens = 0;
+    liquidity_provider.total_deposited = 0;
+    liquidity_provider.fees_claimed = liquidity_provider.fees_claimed
+        .checked_add(fees_owed)
+        .ok_or(PredictionMarketError::ArithmeticOverflow)?;
+
+    pool.reserve_a -= share_a;
+    pool.reserve_b -= share_b;
+    pool.fee_collected = pool.fee_collected
+        .checked_sub(fees_owed)
+        .ok_or(PredictionMarketError::ArithmeticUnderflow)?;
+    pool.total_supply = pool.total_supply
+        .checked_sub(lp_amount)
+        .ok_or(PredictionMarketError::ArithmeticUnderflow)?;
+    pool.total_liquidity = pool.total_liquidity
+        .checked_sub(liquidity_share)
+        .ok_or(PredictionMarketError::ArithmeticUnderflow)?;
+
+    market.total_liquidity = market.total_liquidity
+        .checked_sub(liquidity_share)
+        .ok_or(PredictionMarketError::ArithmeticUnderflow)?;
+    market.total_claimed = market.total_claimed
+        .checked_add(payout)
+        .ok_or(PredictionMarketError::ArithmeticOverflow)?;
+
+    emit!(LiquiditySettledEvent {
+        market: market.key(),
+        user: ctx.accounts.user.key(),
+        lp_amount,
+        shares_a: share_a,
+        shares_b: share_b,
+        reserve_value,
+        fees_paid: fees_owed,
+        cancelled: market.status == MarketStatus::Cancelled,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    Ok(())
+}
+
+#[event]
+pub struct LiquiditySettledEvent {
+    pub market: Pubkey,
+    pub user: Pubkey,
+    pub lp_amount: u64,
+    pub shares_a: u64,
+    pub shares_b: u64,
+    pub reserve_value: u64,
+    pub fees_paid: u64,
+    pub cancelled: bool,
+    pub timestamp: i64,
+}
@@ -0,0 +1,149 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::{self, Token, TokenAccount, Transfer};
+
+use crate::state::*;
+use crate::error::*;
+
+#[derive(Accounts)]
+pub struct SettleOrder<'info> {
+    #[account(
+        seeds = [b"market", market.market_id.to_le_bytes().as_ref()],
+        bump = market.bump,
+    )]
+    pub market: Box<Account<'info, PredictionMarket>>,
+
+    #[account(
+        mut,
+        seeds = [b"order_book", market.key().as_ref()],
+        bump = order_book.load()?.bump,
+    )]
+    pub order_book: AccountLoader<'info, OrderBook>,
+
+    #[account(
+        mut,
+        seeds = [b"market_vault", market.key().as_ref()],
+        bump = market.vault_bump,
+    )]
+    pub market_vault: Box<Account<'info, TokenAccount>>,
+
+    /// Market-owned escrow for the order's outcome
+    #[account(mut)]
+    pub outcome_vault: Box<Account<'info, TokenAccount>>,
+
+    #[account(
+        mut,
+        constraint = user_token_account.mint == market.quote_mint @ PredictionMarketError::InvalidTokenMint,
+        constraint = user_token_account.owner == user.key() @ PredictionMarketError::InvalidTokenAccountOwner,
+    )]
+    pub user_token_account: Box<Account<'info, TokenAccount>>,
+
+    #[account(
+        mut,
+        constraint = user_outcome_token_account.owner == user.key() @ PredictionMarketError::InvalidTokenAccountOwner,
+    )]
+    pub user_outcome_token_account: Box<Account<'info, TokenAccount>>,
+
+    pub user: Signer<'info>,
+
+    pub token_program: Program<'info, Token>,
+}
+
+/// Withdraws an order's filled proceeds. Once the order is filled, expired
+/// or cancelled, the unfilled escrow is returned too and its slot is freed.
+pub fn settle_order(ctx: Context<SettleOrder>, order_id: u64) -> Result<()> {
+    let market = &ctx.accounts.market;
+    let now = Clock::get()?.unix_timestamp;
+
+    let (outcome, shares, collateral, closed) = {
+        let mut order_book = ctx.accounts.order_book.load_mut()?;
+        let slot = order_book.find_order(order_id)?;
+        let order = &mut order_book.orders[slot];
+        require_keys_eq!(order.owner, ctx.accounts.user.key(), PredictionMarketError::InvalidUser);
+
+        // Nothing can fill once trading has ended, so live orders are closed out
+        if market.status != MarketStatus::Active || now >= market.end_time {
+            order.expires_at = now;
+        }
+        order.expire_if_due(now);
+
+        let outcome = order.outcome;
+        let (shares, collateral, closed) = order.take_settlement()?;
+        require!(shares > 0 || collateral > 0 || closed, PredictionMarketError::NothingToSettle);
+
+        if closed {
+            order_book.order_count = order_book.order_count
+                .checked_sub(1)
+                .ok_or(PredictionMarketError::ArithmeticUnderflow)?;
+        }
+        (outcome, shares, collateral, closed)
+    };
+
+    let (outcome_mint, outcome_vault) = market.outcome_token_accounts(outcome)?;
+    require_keys_eq!(
+        ctx.accounts.outcome_vault.key(),
+        outcome_vault,
+        PredictionMarketError::InvalidTokenAccountOwner
+    );
+    require_keys_eq!(
+        ctx.accounts.user_outcome_token_account.mint,
+        outcome_mint,
+        PredictionMarketError::InvalidTokenMint
+    );
+
+    let market_id_bytes = market.market_id.to_le_bytes();
+    let market_seeds = &[
+        b"market",
+        market_id_bytes.as_ref(),
+        &[market.bump],
+    ];
+    let signer_seeds = &[&market_seeds[..]];
+
+    if shares > 0 {
+        let transfer_ctx = CpiContext::new_with_signer(
+            ctx.accounts.token_program.to_account_info(),
+            Transfer {
+                from: ctx.accounts.outcome_vault.to_account_info(),
+                to: ctx.accounts.user_outcome_token_account.to_account_info(),
+                authority: market.to_account_info(),
+            },
+            signer_seeds,
+        );
+        token::transfer(transfer_ctx, shares)?;
+    }
+
+    if collateral > 0 {
+        let transfer_ctx = CpiContext::new_with_signer(
+            ctx.accounts.token_program.to_account_info(),
+            Transfer {
+                from: ctx.accounts.market_vault.to_account_info(),
+                to: ctx.accounts.user_token_account.to_account_info(),
+                authority: market.to_account_info(),
+            },
+            signer_seeds,
+        );
+        token::transfer(transfer_ctx, collateral)?;
+    }
+
+    emit!(OrderSettledEvent {
+        market: market.key(),
+        user: ctx.accounts.user.key(),
+        order_id,
+        shares,
+        collateral,
+        closed,
+        timestamp: now,
+    });
+
+    Ok(())
+}
+
+#[event]
+pub struct OrderSettledEvent {
+    pub market: Pubkey,
+    pub user: Pubkey,
+    pub order_id: u64,
+    pub shares: u64,
+    pub collateral: u64,
+    pub closed: bool,
+    pub timestamp: i64,
+}
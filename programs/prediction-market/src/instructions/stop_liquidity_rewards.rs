@@ -0,0 +1,111 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::{self, Token, TokenAccount, Transfer};
+
+use crate::state::*;
+use crate::error::*;
+
+#[derive(Accounts)]
+pub struct StopLiquidityRewards<'info> {
+    #[account(
+        seeds = [b"pool", liquidity_rewards.market.as_ref()],
+        bump = pool.bump,
+    )]
+    pub pool: Box<Account<'info, LiquidityPool>>,
+
+    #[account(
+        mut,
+        seeds = [b"liquidity_rewards", liquidity_rewards.market.as_ref()],
+        bump = liquidity_rewards.bump,
+    )]
+    pub liquidity_rewards: Box<Account<'info, LiquidityRewards>>,
+
+    #[account(
+        mut,
+        seeds = [b"reward_vault", liquidity_rewards.market.as_ref()],
+        bump = liquidity_rewards.vault_bump,
+    )]
+    pub reward_vault: Box<Account<'info, TokenAccount>>,
+
+    #[account(
+        mut,
+        constraint = sponsor_token_account.mint == liquidity_rewards.reward_mint @ PredictionMarketError::InvalidTokenMint,
+        constraint = sponsor_token_account.owner == liquidity_rewards.sponsor @ PredictionMarketError::InvalidTokenAccountOwner,
+    )]
+    pub sponsor_token_account: Box<Account<'info, TokenAccount>>,
+
+    #[account(
+        seeds = [b"global_state"],
+        bump = global_state.bump,
+    )]
+    pub global_state: Box<Account<'info, GlobalState>>,
+
+    #[account(
+        constraint = authority.key() == liquidity_rewards.sponsor
+            || authority.key() == global_state.authority @ PredictionMarketError::UnauthorizedSponsor,
+    )]
+    pub authority: Signer<'info>,
+
+    pub token_program: Program<'info, Token>,
+}
+
+/// Ends a liquidity mining program early, either by its sponsor or the
+/// program admin, and returns every reward not yet emitted to the sponsor.
+/// Rewards providers have already earned stay in the vault for them to
+/// claim. Once the program has ended on its own this only reclaims what was
+/// emitted while the pool was empty.
+pub fn stop_liquidity_rewards(ctx: Context<StopLiquidityRewards>) -> Result<()> {
+    let liquidity_rewards = &mut ctx.accounts.liquidity_rewards;
+    let clock = Clock::get()?;
+
+    liquidity_rewards.accrue(clock.unix_timestamp, ctx.accounts.pool.total_supply)?;
+    if clock.unix_timestamp < liquidity_rewards.end_time {
+        liquidity_rewards.end_time = clock.unix_timestamp.max(liquidity_rewards.start_time);
+    }
+
+    let amount = liquidity_rewards.reclaimable()?;
+    require!(amount > 0, PredictionMarketError::NoLiquidityRewards);
+
+    let market_key = liquidity_rewards.market;
+    let rewards_seeds = &[
+        b"liquidity_rewards",
+        market_key.as_ref(),
+        &[liquidity_rewards.bump],
+    ];
+    let signer_seeds = &[&rewards_seeds[..]];
+
+    let transfer_ctx = CpiContext::new_with_signer(
+        ctx.accounts.token_program.to_account_info(),
+        Transfer {
+            from: ctx.accounts.reward_vault.to_account_info(),
+            to: ctx.accounts.sponsor_token_account.to_account_info(),
+            authority: liquidity_rewards.to_account_info(),
+        },
+        signer_seeds,
+    );
+    token::transfer(transfer_ctx, amount)?;
+
+    liquidity_rewards.total_reclaimed = liquidity_rewards.total_reclaimed
+        .checked_add(amount)
+        .ok_or(PredictionMarketError::ArithmeticOverflow)?;
+
+    emit!(LiquidityRewardsStoppedEvent {
+        market: market_key,
+        liquidity_rewards: liquidity_rewards.key(),
+        stopped_by: ctx.accounts.authority.key(),
+        end_time: liquidity_rewards.end_time,
+        amount_reclaimed: amount,
+        timestamp: clock.unix_timestamp,
+    });
+
+    Ok(())
+}
+
+#[event]
+pub struct LiquidityRewardsStoppedEvent {
+    pub market: Pubkey,
+    pub liquidity_rewards: Pubkey,
+    pub stopped_by: Pubkey,
+    pub end_time: i64,
+    pub amount_reclaimed: u64,
+    pub timestamp: i64,
+}
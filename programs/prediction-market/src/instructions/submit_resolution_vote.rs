@@ -0,0 +1,92 @@
+use anchor_lang::prelude::*;
+
+use crate::instructions::resolve_market::MarketResolvedEvent;
+use crate::state::*;
+use crate::error::*;
+
+#[derive(Accounts)]
+pub struct SubmitResolutionVote<'info> {
+    #[account(
+        mut,
+        seeds = [b"market", market.market_id.to_le_bytes().as_ref()],
+        bump = market.bump,
+        constraint = market.resolution_committee == resolution_vote.key() @ PredictionMarketError::InvalidOracle,
+        constraint = market.status == MarketStatus::Active @ PredictionMarketError::MarketNotActive,
+        constraint = market.resolution_time <= Clock::get()?.unix_timestamp @ PredictionMarketError::MarketNotExpired
+    )]
+    pub market: Box<Account<'info, PredictionMarket>>,
+
+    #[account(
+        mut,
+        seeds = [b"resolution_vote", market.key().as_ref()],
+        bump = resolution_vote.bump,
+    )]
+    pub resolution_vote: Box<Account<'info, ResolutionVote>>,
+
+    pub member: Signer<'info>,
+}
+
+/// Records a committee member's vote, replacing any earlier vote of theirs.
+/// The market resolves in the same instruction once `threshold` members
+/// agree on the result.
+pub fn submit_resolution_vote(ctx: Context<SubmitResolutionVote>, params: ResolutionParams) -> Result<()> {
+    let market = &mut ctx.accounts.market;
+    let resolution_vote = &mut ctx.accounts.resolution_vote;
+    let now = Clock::get()?.unix_timestamp;
+
+    let is_scalar = market.market_type == MarketType::Scalar;
+    if !is_scalar {
+        require!(market.is_valid_outcome(params.outcome), PredictionMarketError::InvalidOutcome);
+    }
+
+    let member_index = resolution_vote
+        .members
+        .iter()
+        .position(|member| *member == ctx.accounts.member.key())
+        .ok_or(PredictionMarketError::NotCommitteeMember)?;
+    resolution_vote.votes[member_index] = CommitteeVote {
+        has_voted: true,
+        outcome: params.outcome,
+        resolution_price: params.resolution_price,
+    };
+
+    let agreeing_votes = resolution_vote.agreeing_votes(&params, is_scalar);
+
+    emit!(ResolutionVoteSubmittedEvent {
+        market: market.key(),
+        member: ctx.accounts.member.key(),
+        outcome: params.outcome,
+        resolution_price: params.resolution_price,
+        agreeing_votes: agreeing_votes as u8,
+        threshold: resolution_vote.threshold,
+        timestamp: now,
+    });
+
+    if agreeing_votes >= resolution_vote.threshold as usize {
+        let (winning_pool, losing_pool) = market.settle(&params, 0, now)?;
+
+        emit!(MarketResolvedEvent {
+            market: market.key(),
+            outcome: market.winning_outcome,
+            resolution_price: market.resolution_price,
+            winning_pool,
+            losing_pool,
+            oracle_fee: 0,
+            resolution_timestamp: market.resolution_timestamp,
+            dispute_end_time: market.dispute_end_time,
+        });
+    }
+
+    Ok(())
+}
+
+#[event]
+pub struct ResolutionVoteSubmittedEvent {
+    pub market: Pubkey,
+    pub member: Pubkey,
+    pub outcome: u8,
+    pub resolution_price: u64,
+    pub agreeing_votes: u8,
+    pub threshold: u8,
+    pub timestamp: i64,
+}
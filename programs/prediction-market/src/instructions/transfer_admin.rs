@@ -0,0 +1,40 @@
+use anchor_lang::prelude::*;
+
+use crate::state::*;
+use crate::error::*;
+
+#[derive(Accounts)]
+pub struct TransferAdmin<'info> {
+    #[account(
+        mut,
+        seeds = [b"global_state"],
+        bump = global_state.bump,
+        has_one = authority @ PredictionMarketError::UnauthorizedAdmin,
+    )]
+    pub global_state: Box<Account<'info, GlobalState>>,
+
+    pub authority: Signer<'info>,
+}
+
+/// First step of an admin handover: nominates `new_authority`, who must then
+/// call `accept_admin`. Nominating `Pubkey::default()` withdraws a pending
+/// nomination.
+pub fn transfer_admin(ctx: Context<TransferAdmin>, new_authority: Pubkey) -> Result<()> {
+    let global_state = &mut ctx.accounts.global_state;
+    global_state.pending_authority = new_authority;
+
+    emit!(AdminTransferProposedEvent {
+        authority: global_state.authority,
+        pending_authority: new_authority,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    Ok(())
+}
+
+#[event]
+pub struct AdminTransferProposedEvent {
+    pub authority: Pubkey,
+    pub pending_authority: Pubkey,
+    pub timestamp: i64,
+}
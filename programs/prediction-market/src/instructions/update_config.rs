@@ -0,0 +1,53 @@
+use anchor_lang::prelude::*;
+
+use crate::state::*;
+use crate::error::*;
+
+#[derive(Accounts)]
+pub struct UpdateConfig<'info> {
+    #[account(
+        mut,
+        seeds = [b"global_state"],
+        bump = global_state.bump,
+        has_one = authority @ PredictionMarketError::UnauthorizedAdmin,
+    )]
+    pub global_state: Box<Account<'info, GlobalState>>,
+
+    pub authority: Signer<'info>,
+}
+
+pub fn update_config(ctx: Context<UpdateConfig>, config: ProgramConfig) -> Result<()> {
+    let global_state = &mut ctx.accounts.global_state;
+    global_state.apply_config(&config)?;
+
+    emit!(ConfigUpdatedEvent {
+        authority: global_state.authority,
+        platform_fee_rate: global_state.platform_fee_rate,
+        max_creator_fee_rate: global_state.max_creator_fee_rate,
+        referral_fee_share: global_state.referral_fee_share,
+        exit_fee_rate: global_state.exit_fee_rate,
+        min_resolution_time: global_state.min_resolution_time,
+        max_resolution_time: global_state.max_resolution_time,
+        guardian: global_state.guardian,
+        arbitrator: global_state.arbitrator,
+        dispute_bond: global_state.dispute_bond,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    Ok(())
+}
+
+#[event]
+pub struct ConfigUpdatedEvent {
+    pub authority: Pubkey,
+    pub platform_fee_rate: u16,
+    pub max_creator_fee_rate: u16,
+    pub referral_fee_share: u16,
+    pub exit_fee_rate: u16,
+    pub min_resolution_time: i64,
+    pub max_resolution_time: i64,
+    pub guardian: Pubkey,
+    pub arbitrator: Pubkey,
+    pub dispute_bond: u64,
+    pub timestamp: i64,
+}
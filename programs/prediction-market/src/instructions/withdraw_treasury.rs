@@ -0,0 +1,74 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::{self, Token, TokenAccount, Transfer};
+
+use crate::state::*;
+use crate::error::*;
+
+#[derive(Accounts)]
+pub struct WithdrawTreasury<'info> {
+    #[account(
+        seeds = [b"global_state"],
+        bump = global_state.bump,
+        has_one = authority @ PredictionMarketError::UnauthorizedAdmin,
+    )]
+    pub global_state: Box<Account<'info, GlobalState>>,
+
+    #[account(
+        mut,
+        seeds = [b"treasury", treasury.mint.as_ref()],
+        bump,
+    )]
+    pub treasury: Account<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        constraint = destination.mint == treasury.mint @ PredictionMarketError::InvalidTokenMint,
+    )]
+    pub destination: Account<'info, TokenAccount>,
+
+    pub authority: Signer<'info>,
+
+    pub token_program: Program<'info, Token>,
+}
+
+/// Moves `amount` of accumulated platform fees out of a treasury.
+pub fn withdraw_treasury(ctx: Context<WithdrawTreasury>, amount: u64) -> Result<()> {
+    let global_state = &ctx.accounts.global_state;
+
+    require!(amount > 0, PredictionMarketError::InvalidAmount);
+    require!(
+        ctx.accounts.treasury.amount >= amount,
+        PredictionMarketError::InsufficientVaultFunds
+    );
+
+    let global_state_seeds = &[b"global_state".as_ref(), &[global_state.bump]];
+    let signer_seeds = &[&global_state_seeds[..]];
+
+    let transfer_ctx = CpiContext::new_with_signer(
+        ctx.accounts.token_program.to_account_info(),
+        Transfer {
+            from: ctx.accounts.treasury.to_account_info(),
+            to: ctx.accounts.destination.to_account_info(),
+            authority: global_state.to_account_info(),
+        },
+        signer_seeds,
+    );
+    token::transfer(transfer_ctx, amount)?;
+
+    emit!(TreasuryWithdrawnEvent {
+        treasury: ctx.accounts.treasury.key(),
+        destination: ctx.accounts.destination.key(),
+        amount,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    Ok(())
+}
+
+#[event]
+pub struct TreasuryWithdrawnEvent {
+    pub treasury: Pubkey,
+    pub destination: Pubkey,
+    pub amount: u64,
+    pub timestamp: i64,
+}
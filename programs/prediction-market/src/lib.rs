@@ -1,294 +1,319 @@
-```rust
 use anchor_lang::prelude::*;
-use anchor_spl::token::{self, Token, TokenAccount, Transfer, Mint};
 
-declare_id!("11111111111111111111111111111112");
+pub mod amm;
+pub mod error;
+pub mod instructions;
+pub mod lmsr;
+pub mod price_feed;
+pub mod state;
+pub mod utils;
+
+use instructions::*;
+use state::*;
+
+declare_id!("Fg6PaFpoGXkYsidMpWTK6W2BeZ7FEfcYkg476zPFsLnS");
 
 #[program]
 pub mod prediction_market {
     use super::*;
 
-    pub fn initialize_market(
-        ctx: Context<InitializeMarket>,
+    pub fn create_market(
+        ctx: Context<CreateMarket>,
         market_id: u64,
-        question: String,
-        end_time: i64,
-        oracle: Pubkey,
+        params: MarketParams,
     ) -> Result<()> {
-        let market = &mut ctx.accounts.market;
-        market.market_id = market_id;
-        market.question = question;
-        market.end_time = end_time;
-        market.oracle = oracle;
-        market.total_yes_amount = 0;
-        market.total_no_amount = 0;
-        market.is_resolved = false;
-        market.outcome = None;
-        market.authority = ctx.accounts.authority.key();
-        market.bump = ctx.bumps.market;
-        Ok(())
+        instructions::create_market::create_market(ctx, market_id, params)
     }
 
     pub fn place_bet(
         ctx: Context<PlaceBet>,
+        market_id: u64,
+        outcome: u8,
         amount: u64,
-        prediction: bool,
     ) -> Result<()> {
-        let market = &mut ctx.accounts.market;
-        let bet = &mut ctx.accounts.bet;
-        let clock = Clock::get()?;
-
-        require!(clock.unix_timestamp < market.end_time, PredictionError::MarketClosed);
-        require!(!market.is_resolved, PredictionError::MarketResolved);
-        require!(amount > 0, PredictionError::InvalidAmount);
-
-        // Transfer tokens from user to market vault
-        let cpi_accounts = Transfer {
-            from: ctx.accounts.user_token_account.to_account_info(),
-            to: ctx.accounts.market_vault.to_account_info(),
-            authority: ctx.accounts.user.to_account_info(),
-        };
-        let cpi_program = ctx.accounts.token_program.to_account_info();
-        let cpi_ctx = CpiContext::new(cpi_program, cpi_accounts);
-        token::transfer(cpi_ctx, amount)?;
-
-        // Update market totals
-        if prediction {
-            market.total_yes_amount = market.total_yes_amount.checked_add(amount).unwrap();
-        } else {
-            market.total_no_amount = market.total_no_amount.checked_add(amount).unwrap();
-        }
-
-        // Initialize bet account
-        bet.user = ctx.accounts.user.key();
-        bet.market = ctx.accounts.market.key();
-        bet.amount = amount;
-        bet.prediction = prediction;
-        bet.is_claimed = false;
-        bet.bump = ctx.bumps.bet;
-
-        Ok(())
+        instructions::place_bet::place_bet(ctx, market_id, outcome, amount)
     }
 
     pub fn resolve_market(
         ctx: Context<ResolveMarket>,
-        outcome: bool,
+        params: ResolutionParams,
+        oracle_data: Vec<u8>,
     ) -> Result<()> {
-        let market = &mut ctx.accounts.market;
-        let clock = Clock::get()?;
+        instructions::resolve_market::resolve_market(ctx, params, oracle_data)
+    }
 
-        require!(ctx.accounts.oracle.key() == market.oracle, PredictionError::UnauthorizedOracle);
-        require!(clock.unix_timestamp >= market.end_time, PredictionError::MarketNotEnded);
-        require!(!market.is_resolved, PredictionError::MarketAlreadyResolved);
+    pub fn claim_winnings(ctx: Context<ClaimWinnings>) -> Result<()> {
+        instructions::claim_winnings::claim_winnings(ctx)
+    }
 
-        market.is_resolved = true;
-        market.outcome = Some(outcome);
+    pub fn add_liquidity(ctx: Context<AddLiquidity>, amount: u64) -> Result<()> {
+        ctx.accounts.add_liquidity(amount, &ctx.bumps)
+    }
 
-        Ok(())
+    pub fn remove_liquidity(
+        ctx: Context<RemoveLiquidity>,
+        lp_amount: u64,
+    ) -> Result<()> {
+        instructions::remove_liquidity::remove_liquidity(ctx, lp_amount)
     }
 
-    pub fn claim_winnings(ctx: Context<ClaimWinnings>) -> Result<()> {
-        let market = &ctx.accounts.market;
-        let bet = &mut ctx.accounts.bet;
-
-        require!(market.is_resolved, PredictionError::MarketNotResolved);
-        require!(!bet.is_claimed, PredictionError::AlreadyClaimed);
-        require!(bet.user == ctx.accounts.user.key(), PredictionError::UnauthorizedUser);
-
-        let outcome = market.outcome.unwrap();
-        require!(bet.prediction == outcome, PredictionError::LosingBet);
-
-        // Calculate winnings
-        let total_winning_pool = if outcome {
-            market.total_yes_amount
-        } else {
-            market.total_no_amount
-        };
-
-        let total_losing_pool = if outcome {
-            market.total_no_amount
-        } else {
-            market.total_yes_amount
-        };
-
-        let total_pool = market.total_yes_amount.checked_add(market.total_no_amount).unwrap();
-        let user_share = (bet.amount as u128)
-            .checked_mul(total_pool as u128).unwrap()
-            .checked_div(total_winning_pool as u128).unwrap() as u64;
-
-        // Transfer winnings
-        let seeds = &[
-            b"market",
-            &market.market_id.to_le_bytes(),
-            &[market.bump],
-        ];
-        let signer = &[&seeds[..]];
-
-        let cpi_accounts = Transfer {
-            from: ctx.accounts.market_vault.to_account_info(),
-            to: ctx.accounts.user_token_account.to_account_info(),
-            authority: ctx.accounts.market.to_account_info(),
-        };
-        let cpi_program = ctx.accounts.token_program.to_account_info();
-        let cpi_ctx = CpiContext::new_with_signer(cpi_program, cpi_accounts, signer);
-        token::transfer(cpi_ctx, user_share)?;
-
-        bet.is_claimed = true;
-
-        Ok(())
+    pub fn initialize_lmsr(ctx: Context<InitializeLmsr>, liquidity: u64) -> Result<()> {
+        instructions::initialize_lmsr::initialize_lmsr(ctx, liquidity)
     }
-}
 
-#[derive(Accounts)]
-#[instruction(market_id: u64)]
-pub struct InitializeMarket<'info> {
-    #[account(
-        init,
-        payer = authority,
-        space = 8 + Market::INIT_SPACE,
-        seeds = [b"market", &market_id.to_le_bytes()],
-        bump
-    )]
-    pub market: Account<'info, Market>,
-    
-    #[account(
-        init,
-        payer = authority,
-        token::mint = mint,
-        token::authority = market,
-        seeds = [b"vault", market.key().as_ref()],
-        bump
-    )]
-    pub market_vault: Account<'info, TokenAccount>,
-    
-    pub mint: Account<'info, Mint>,
-    
-    #[account(mut)]
-    pub authority: Signer<'info>,
-    
-    pub system_program: Program<'info, System>,
-    pub token_program: Program<'info, Token>,
-    pub rent: Sysvar<'info, Rent>,
-}
+    pub fn buy_shares(
+        ctx: Context<BuyShares>,
+        outcome: u8,
+        shares: u64,
+        max_cost: u64,
+    ) -> Result<()> {
+        instructions::buy_shares::buy_shares(ctx, outcome, shares, max_cost)
+    }
 
-#[derive(Accounts)]
-pub struct PlaceBet<'info> {
-    #[account(mut)]
-    pub market: Account<'info, Market>,
-    
-    #[account(
-        init,
-        payer = user,
-        space = 8 + Bet::INIT_SPACE,
-        seeds = [b"bet", user.key().as_ref(), market.key().as_ref()],
-        bump
-    )]
-    pub bet: Account<'info, Bet>,
-    
-    #[account(mut)]
-    pub market_vault: Account<'info, TokenAccount>,
-    
-    #[account(
-        mut,
-        token::mint = market_vault.mint,
-        token::authority = user
-    )]
-    pub user_token_account: Account<'info, TokenAccount>,
-    
-    #[account(mut)]
-    pub user: Signer<'info>,
-    
-    pub system_program: Program<'info, System>,
-    pub token_program: Program<'info, Token>,
-}
+    pub fn sell_shares(
+        ctx: Context<SellShares>,
+        outcome: u8,
+        shares: u64,
+        min_proceeds: u64,
+    ) -> Result<()> {
+        instructions::sell_shares::sell_shares(ctx, outcome, shares, min_proceeds)
+    }
 
-#[derive(Accounts)]
-pub struct ResolveMarket<'info> {
-    #[account(mut)]
-    pub market: Account<'info, Market>,
-    
-    pub oracle: Signer<'info>,
-}
+    pub fn claim_shares(ctx: Context<ClaimShares>) -> Result<()> {
+        instructions::claim_shares::claim_shares(ctx)
+    }
 
-#[derive(Accounts)]
-pub struct ClaimWinnings<'info> {
-    pub market: Account<'info, Market>,
-    
-    #[account(
-        mut,
-        seeds = [b"bet", user.key().as_ref(), market.key().as_ref()],
-        bump = bet.bump
-    )]
-    pub bet: Account<'info, Bet>,
-    
-    #[account(
-        mut,
-        seeds = [b"vault", market.key().as_ref()],
-        bump
-    )]
-    pub market_vault: Account<'info, TokenAccount>,
-    
-    #[account(
-        mut,
-        token::mint = market_vault.mint,
-        token::authority = user
-    )]
-    pub user_token_account: Account<'info, TokenAccount>,
-    
-    #[account(mut)]
-    pub user: Signer<'info>,
-    
-    pub token_program: Program<'info, Token>,
-}
+    pub fn initialize_pool(
+        ctx: Context<InitializePool>,
+        amount: u64,
+        fee_rate: u16,
+    ) -> Result<()> {
+        instructions::initialize_pool::initialize_pool(ctx, amount, fee_rate)
+    }
 
-#[account]
-#[derive(InitSpace)]
-pub struct Market {
-    pub market_id: u64,
-    #[max_len(200)]
-    pub question: String,
-    pub end_time: i64,
-    pub oracle: Pubkey,
-    pub total_yes_amount: u64,
-    pub total_no_amount: u64,
-    pub is_resolved: bool,
-    pub outcome: Option<bool>,
-    pub authority: Pubkey,
-    pub bump: u8,
-}
+    pub fn buy_outcome(ctx: Context<BuyOutcome>, params: TradeParams) -> Result<()> {
+        instructions::buy_outcome::buy_outcome(ctx, params)
+    }
 
-#[account]
-#[derive(InitSpace)]
-pub struct Bet {
-    pub user: Pubkey,
-    pub market: Pubkey,
-    pub amount: u64,
-    pub prediction: bool,
-    pub is_claimed: bool,
-    pub bump: u8,
-}
+    pub fn sell_outcome(ctx: Context<SellOutcome>, params: TradeParams) -> Result<()> {
+        instructions::sell_outcome::sell_outcome(ctx, params)
+    }
+
+    pub fn sell_bet(ctx: Context<SellBet>, amount: u64) -> Result<()> {
+        instructions::sell_bet::sell_bet(ctx, amount)
+    }
+
+    pub fn split_collateral(ctx: Context<SplitCollateral>, amount: u64) -> Result<()> {
+        instructions::split_collateral::split_collateral(ctx, amount)
+    }
+
+    pub fn merge_positions(ctx: Context<MergePositions>, amount: u64) -> Result<()> {
+        instructions::merge_positions::merge_positions(ctx, amount)
+    }
+
+    pub fn redeem(ctx: Context<Redeem>) -> Result<()> {
+        instructions::redeem::redeem(ctx)
+    }
+
+    pub fn initialize_order_book(ctx: Context<InitializeOrderBook>) -> Result<()> {
+        instructions::initialize_order_book::initialize_order_book(ctx)
+    }
+
+    pub fn place_order(ctx: Context<PlaceOrder>, params: OrderParams) -> Result<()> {
+        instructions::place_order::place_order(ctx, params)
+    }
+
+    pub fn cancel_order(ctx: Context<CancelOrder>, order_id: u64) -> Result<()> {
+        instructions::cancel_order::cancel_order(ctx, order_id)
+    }
+
+    pub fn match_orders(ctx: Context<MatchOrders>, outcome: u8, max_fills: u8) -> Result<()> {
+        instructions::match_orders::match_orders(ctx, outcome, max_fills)
+    }
+
+    pub fn settle_order(ctx: Context<SettleOrder>, order_id: u64) -> Result<()> {
+        instructions::settle_order::settle_order(ctx, order_id)
+    }
+
+    pub fn cancel_market(ctx: Context<CancelMarket>) -> Result<()> {
+        instructions::cancel_market::cancel_market(ctx)
+    }
+
+    pub fn claim_refund(ctx: Context<ClaimRefund>) -> Result<()> {
+        instructions::claim_refund::claim_refund(ctx)
+    }
+
+    pub fn dispute_resolution(ctx: Context<DisputeResolution>, params: ResolutionParams) -> Result<()> {
+        instructions::dispute_resolution::dispute_resolution(ctx, params)
+    }
+
+    pub fn arbitrate_dispute(ctx: Context<ArbitrateDispute>, params: ResolutionParams) -> Result<()> {
+        instructions::arbitrate_dispute::arbitrate_dispute(ctx, params)
+    }
+
+    pub fn register_oracle(
+        ctx: Context<RegisterOracle>,
+        oracle_id: u64,
+        name: String,
+        description: String,
+    ) -> Result<()> {
+        instructions::register_oracle::register_oracle(ctx, oracle_id, name, description)
+    }
+
+    pub fn deactivate_oracle(ctx: Context<DeactivateOracle>) -> Result<()> {
+        instructions::deactivate_oracle::deactivate_oracle(ctx)
+    }
+
+    pub fn configure_oracle_committee(
+        ctx: Context<ConfigureOracleCommittee>,
+        members: Vec<Pubkey>,
+        threshold: u8,
+    ) -> Result<()> {
+        instructions::configure_oracle_committee::configure_oracle_committee(ctx, members, threshold)
+    }
+
+    pub fn submit_resolution_vote(ctx: Context<SubmitResolutionVote>, params: ResolutionParams) -> Result<()> {
+        instructions::submit_resolution_vote::submit_resolution_vote(ctx, params)
+    }
+
+    pub fn set_mock_price_feed(
+        ctx: Context<SetMockPriceFeed>,
+        feed_id: u64,
+        price: u64,
+        confidence: u64,
+        timestamp: i64,
+    ) -> Result<()> {
+        instructions::set_mock_price_feed::set_mock_price_feed(ctx, feed_id, price, confidence, timestamp)
+    }
+
+    pub fn configure_price_feed(
+        ctx: Context<ConfigurePriceFeed>,
+        strike_price: u64,
+        max_price_staleness: i64,
+        max_price_confidence_bps: u16,
+    ) -> Result<()> {
+        instructions::configure_price_feed::configure_price_feed(
+            ctx,
+            strike_price,
+            max_price_staleness,
+            max_price_confidence_bps,
+        )
+    }
+
+    pub fn resolve_from_price_feed(ctx: Context<ResolveFromPriceFeed>) -> Result<()> {
+        instructions::resolve_from_price_feed::resolve_from_price_feed(ctx)
+    }
+
+    pub fn configure_optimistic_oracle(
+        ctx: Context<ConfigureOptimisticOracle>,
+        arbiter: Pubkey,
+        bond: u64,
+        liveness: i64,
+    ) -> Result<()> {
+        instructions::configure_optimistic_oracle::configure_optimistic_oracle(ctx, arbiter, bond, liveness)
+    }
+
+    pub fn propose_outcome(ctx: Context<ProposeOutcome>, params: ResolutionParams) -> Result<()> {
+        instructions::propose_outcome::propose_outcome(ctx, params)
+    }
+
+    pub fn challenge_proposal(ctx: Context<ChallengeProposal>) -> Result<()> {
+        instructions::challenge_proposal::challenge_proposal(ctx)
+    }
+
+    pub fn finalize_proposal(ctx: Context<FinalizeProposal>) -> Result<()> {
+        instructions::finalize_proposal::finalize_proposal(ctx)
+    }
+
+    pub fn arbitrate_proposal(ctx: Context<ArbitrateProposal>, params: ResolutionParams) -> Result<()> {
+        instructions::arbitrate_proposal::arbitrate_proposal(ctx, params)
+    }
+
+    pub fn initialize_program(ctx: Context<InitializeProgram>, config: ProgramConfig) -> Result<()> {
+        instructions::initialize_program::initialize_program(ctx, config)
+    }
+
+    pub fn update_config(ctx: Context<UpdateConfig>, config: ProgramConfig) -> Result<()> {
+        instructions::update_config::update_config(ctx, config)
+    }
+
+    pub fn transfer_admin(ctx: Context<TransferAdmin>, new_authority: Pubkey) -> Result<()> {
+        instructions::transfer_admin::transfer_admin(ctx, new_authority)
+    }
+
+    pub fn accept_admin(ctx: Context<AcceptAdmin>) -> Result<()> {
+        instructions::accept_admin::accept_admin(ctx)
+    }
+
+    pub fn set_program_pause(ctx: Context<SetProgramPause>, is_paused: bool, paused_classes: u8) -> Result<()> {
+        instructions::set_program_pause::set_program_pause(ctx, is_paused, paused_classes)
+    }
+
+    pub fn set_market_pause(ctx: Context<SetMarketPause>, paused_classes: u8) -> Result<()> {
+        instructions::set_market_pause::set_market_pause(ctx, paused_classes)
+    }
+
+    pub fn initialize_treasury(ctx: Context<InitializeTreasury>) -> Result<()> {
+        instructions::initialize_treasury::initialize_treasury(ctx)
+    }
+
+    pub fn withdraw_treasury(ctx: Context<WithdrawTreasury>, amount: u64) -> Result<()> {
+        instructions::withdraw_treasury::withdraw_treasury(ctx, amount)
+    }
+
+    pub fn claim_creator_fees(ctx: Context<ClaimCreatorFees>) -> Result<()> {
+        instructions::claim_creator_fees::claim_creator_fees(ctx)
+    }
+
+    pub fn register_referrer(ctx: Context<RegisterReferrer>) -> Result<()> {
+        instructions::register_referrer::register_referrer(ctx)
+    }
+
+    pub fn claim_referral_rewards(ctx: Context<ClaimReferralRewards>) -> Result<()> {
+        instructions::claim_referral_rewards::claim_referral_rewards(ctx)
+    }
+
+    pub fn close_bet(ctx: Context<CloseBet>) -> Result<()> {
+        instructions::close_bet::close_bet(ctx)
+    }
+
+    pub fn close_position(ctx: Context<ClosePosition>) -> Result<()> {
+        instructions::close_position::close_position(ctx)
+    }
 
-#[error_code]
-pub enum PredictionError {
-    #[msg("Market has already closed")]
-    MarketClosed,
-    #[msg("Market has already been resolved")]
-    MarketResolved,
-    #[msg("Invalid bet amount")]
-    InvalidAmount,
-    #[msg("Unauthorized oracle")]
-    UnauthorizedOracle,
-    #[msg("Market has not ended yet")]
-    MarketNotEnded,
-    #[msg("Market already resolved")]
-    MarketAlreadyResolved,
-    #[msg("Market not resolved yet")]
-    MarketNotResolved,
-    #[msg("Winnings already claimed")]
-    AlreadyClaimed,
-    #[msg("Unauthorized user")]
-    UnauthorizedUser,
-    #[msg("This is a losing bet")]
-    LosingBet,
+    pub fn close_market(ctx: Context<CloseMarket>) -> Result<()> {
+        instructions::close_market::close_market(ctx)
+    }
+
+    pub fn claim_lp_fees(ctx: Context<ClaimLpFees>) -> Result<()> {
+        instructions::claim_lp_fees::claim_lp_fees(ctx)
+    }
+
+    pub fn settle_liquidity(ctx: Context<SettleLiquidity>) -> Result<()> {
+        instructions::settle_liquidity::settle_liquidity(ctx)
+    }
+
+    pub fn configure_withdrawal_fee(
+        ctx: Context<ConfigureWithdrawalFee>,
+        params: WithdrawalFeeParams,
+    ) -> Result<()> {
+        instructions::configure_withdrawal_fee::configure_withdrawal_fee(ctx, params)
+    }
+
+    pub fn fund_liquidity_rewards(
+        ctx: Context<FundLiquidityRewards>,
+        emission_rate: u64,
+        start_time: i64,
+        end_time: i64,
+    ) -> Result<()> {
+        instructions::fund_liquidity_rewards::fund_liquidity_rewards(ctx, emission_rate, start_time, end_time)
+    }
+
+    pub fn claim_liquidity_rewards(ctx: Context<ClaimLiquidityRewards>) -> Result<()> {
+        instructions::claim_liquidity_rewards::claim_liquidity_rewards(ctx)
+    }
+
+    pub fn stop_liquidity_rewards(ctx: Context<StopLiquidityRewards>) -> Result<()> {
+        instructions::stop_liquidity_rewards::stop_liquidity_rewards(ctx)
+    }
 }
-```
\ No newline at end of file
@@ -0,0 +1,325 @@
+use anchor_lang::prelude::*;
+
+use crate::error::PredictionMarketError;
+use crate::state::PRICE_PRECISION;
+
+/// Fixed-point scale used by the LMSR math (1.0 == 10^12).
+pub const FIXED_ONE: i128 = 1_000_000_000_000;
+
+/// ln(2) scaled by `FIXED_ONE`.
+const LN_2: i128 = 693_147_180_560;
+
+/// Below this exponent `exp(x)` is smaller than one fixed-point unit.
+const EXP_MIN_INPUT: i128 = -28 * FIXED_ONE;
+
+/// Series terms are bounded so the loops have a fixed worst-case cost.
+const MAX_SERIES_TERMS: i128 = 24;
+
+/// `exp(x)` for a fixed-point `x <= 0`.
+///
+/// Reduces `x = r - k * ln(2)` with `r` in `(-ln 2, 0]`, evaluates `exp(r)`
+/// with a Taylor series and shifts the result right by `k`.
+pub fn exp_fixed(x: i128) -> Result<i128> {
+    require!(x <= 0, PredictionMarketError::ArithmeticOverflow);
+    if x < EXP_MIN_INPUT {
+        return Ok(0);
+    }
+
+    let k = (-x) / LN_2;
+    let r = x + k * LN_2;
+
+    let mut sum = FIXED_ONE;
+    let mut term = FIXED_ONE;
+    for i in 1..=MAX_SERIES_TERMS {
+        term = term * r / (FIXED_ONE * i);
+        if term == 0 {
+            break;
+        }
+        sum += term;
+    }
+
+    Ok(sum >> k)
+}
+
+/// `ln(y)` for a fixed-point `y >= 1`.
+///
+/// Reduces `y = m * 2^k` with `m` in `[1, 2)` and evaluates
+/// `ln(m) = 2 * atanh((m - 1) / (m + 1))`, which converges quickly since the
+/// argument never exceeds 1/3.
+pub fn ln_fixed(y: i128) -> Result<i128> {
+    require!(y >= FIXED_ONE, PredictionMarketError::ArithmeticUnderflow);
+
+    let mut k = 0i128;
+    let mut m = y;
+    while m >= 2 * FIXED_ONE {
+        m /= 2;
+        k += 1;
+    }
+
+    let z = (m - FIXED_ONE) * FIXED_ONE / (m + FIXED_ONE);
+    let z_squared = z * z / FIXED_ONE;
+
+    let mut sum = 0i128;
+    let mut term = z;
+    let mut n = 1i128;
+    while term != 0 && n < 2 * MAX_SERIES_TERMS {
+        sum += term / n;
+        term = term * z_squared / FIXED_ONE;
+        n += 2;
+    }
+
+    Ok(2 * sum + k * LN_2)
+}
+
+/// Normalised weights `exp((q_i - max_q) / b)` and their sum, all scaled by
+/// `FIXED_ONE`. Subtracting the largest quantity keeps every exponent
+/// non-positive so `exp_fixed` never overflows.
+fn weights(quantities: &[u64], liquidity: u64) -> Result<(u64, Vec<i128>, i128)> {
+    require!(liquidity > 0, PredictionMarketError::InvalidLiquidityParameter);
+    require!(!quantities.is_empty(), PredictionMarketError::InvalidOutcomeCount);
+
+    let max_quantity = quantities.iter().copied().max().unwrap_or(0);
+    let mut weights = Vec::with_capacity(quantities.len());
+    let mut sum = 0i128;
+    for quantity in quantities {
+        let scaled_gap = ((max_quantity - quantity) as i128)
+            .checked_mul(FIXED_ONE)
+            .ok_or(PredictionMarketError::ArithmeticOverflow)?;
+        let weight = exp_fixed(-(scaled_gap / liquidity as i128))?;
+        sum += weight;
+        weights.push(weight);
+    }
+
+    Ok((max_quantity, weights, sum))
+}
+
+/// LMSR cost function `C(q) = b * ln(sum(exp(q_i / b)))`, returned in
+/// collateral base units scaled by `FIXED_ONE` so callers can round the
+/// difference of two costs in the market's favour.
+pub fn cost(quantities: &[u64], liquidity: u64) -> Result<i128> {
+    let (max_quantity, _, sum) = weights(quantities, liquidity)?;
+    let log_sum = ln_fixed(sum)?;
+
+    (max_quantity as i128)
+        .checked_mul(FIXED_ONE)
+        .and_then(|base| base.checked_add((liquidity as i128).checked_mul(log_sum)?))
+        .ok_or(PredictionMarketError::ArithmeticOverflow.into())
+}
+
+/// Instantaneous price of every outcome, scaled by `PRICE_PRECISION`.
+pub fn prices(quantities: &[u64], liquidity: u64) -> Result<Vec<u64>> {
+    let (_, weights, sum) = weights(quantities, liquidity)?;
+
+    Ok(weights
+        .iter()
+        .map(|weight| (weight * PRICE_PRECISION as i128 / sum) as u64)
+        .collect())
+}
+
+/// Collateral a trader pays to move `outcome` from `quantities` up by
+/// `shares`, rounded up and never less than one base unit.
+pub fn buy_cost(quantities: &[u64], liquidity: u64, outcome: usize, shares: u64) -> Result<u64> {
+    let mut after = quantities.to_vec();
+    let quantity = after
+        .get_mut(outcome)
+        .ok_or(PredictionMarketError::InvalidOutcome)?;
+    *quantity = quantity
+        .checked_add(shares)
+        .ok_or(PredictionMarketError::ArithmeticOverflow)?;
+
+    let delta = cost(&after, liquidity)? - cost(quantities, liquidity)?;
+    let rounded = ((delta + FIXED_ONE - 1) / FIXED_ONE).max(1);
+    u64::try_from(rounded).map_err(|_| PredictionMarketError::ArithmeticOverflow.into())
+}
+
+/// Collateral a trader receives for returning `shares` of `outcome`,
+/// rounded down.
+pub fn sell_proceeds(quantities: &[u64], liquidity: u64, outcome: usize, shares: u64) -> Result<u64> {
+    let mut after = quantities.to_vec();
+    let quantity = after
+        .get_mut(outcome)
+        .ok_or(PredictionMarketError::InvalidOutcome)?;
+    *quantity = quantity
+        .checked_sub(shares)
+        .ok_or(PredictionMarketError::InsufficientShares)?;
+
+    let delta = cost(quantities, liquidity)? - cost(&after, liquidity)?;
+    u64::try_from(delta.max(0) / FIXED_ONE)
+        .map_err(|_| PredictionMarketError::ArithmeticOverflow.into())
+}
+
+/// Worst-case market maker loss `b * ln(n)`, rounded up. This is the subsidy
+/// the creator deposits so every winning share can always be paid out.
+pub fn max_loss(outcome_count: usize, liquidity: u64) -> Result<u64> {
+    let log_outcomes = ln_fixed(outcome_count as i128 * FIXED_ONE)?;
+    let loss = (liquidity as i128)
+        .checked_mul(log_outcomes)
+        .ok_or(PredictionMarketError::ArithmeticOverflow)?;
+    let rounded = (loss + FIXED_ONE - 1) / FIXED_ONE;
+    u64::try_from(rounded).map_err(|_| PredictionMarketError::ArithmeticOverflow.into())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Fixed-point tolerance for the series approximations (1e-9).
+    const TOLERANCE: i128 = 1_000;
+
+    fn assert_close(actual: i128, expected: i128) {
+        assert!(
+            (actual - expected).abs() <= TOLERANCE,
+            "{} is not within {} of {}",
+            actual,
+            TOLERANCE,
+            expected
+        );
+    }
+
+    #[test]
+    fn exp_of_zero_is_one() {
+        assert_eq!(exp_fixed(0).unwrap(), FIXED_ONE);
+    }
+
+    #[test]
+    fn exp_matches_known_values() {
+        assert_close(exp_fixed(-LN_2).unwrap(), FIXED_ONE / 2);
+        assert_close(exp_fixed(-FIXED_ONE).unwrap(), 367_879_441_171);
+        assert_close(exp_fixed(-10 * FIXED_ONE).unwrap(), 45_399_930);
+    }
+
+    #[test]
+    fn exp_underflows_to_zero() {
+        assert_eq!(exp_fixed(EXP_MIN_INPUT - 1).unwrap(), 0);
+        assert_eq!(exp_fixed(i128::MIN).unwrap(), 0);
+        assert!(exp_fixed(EXP_MIN_INPUT).unwrap() >= 0);
+    }
+
+    #[test]
+    fn exp_rejects_positive_input() {
+        assert!(exp_fixed(1).is_err());
+    }
+
+    #[test]
+    fn exp_is_monotonic() {
+        let mut previous = 0;
+        for step in (0..=56).rev() {
+            let value = exp_fixed(-step * FIXED_ONE / 2).unwrap();
+            assert!(value >= previous);
+            previous = value;
+        }
+    }
+
+    #[test]
+    fn ln_of_one_is_zero() {
+        assert_eq!(ln_fixed(FIXED_ONE).unwrap(), 0);
+    }
+
+    #[test]
+    fn ln_matches_known_values() {
+        assert_close(ln_fixed(2 * FIXED_ONE).unwrap(), LN_2);
+        assert_close(ln_fixed(10 * FIXED_ONE).unwrap(), 2_302_585_092_994);
+        assert_close(ln_fixed(16 * FIXED_ONE).unwrap(), 4 * LN_2);
+    }
+
+    #[test]
+    fn ln_handles_large_input() {
+        // ln(2^64) with a 2^64 fixed-point argument
+        let value = ln_fixed((1i128 << 64) * FIXED_ONE).unwrap();
+        assert!((value - 64 * LN_2).abs() <= 64 * TOLERANCE);
+    }
+
+    #[test]
+    fn ln_rejects_input_below_one() {
+        assert!(ln_fixed(FIXED_ONE - 1).is_err());
+        assert!(ln_fixed(0).is_err());
+    }
+
+    #[test]
+    fn cost_of_empty_market_is_max_loss() {
+        let liquidity = 1_000_000;
+        let cost = cost(&[0, 0], liquidity).unwrap();
+        assert!((cost - liquidity as i128 * LN_2).abs() <= liquidity as i128 * TOLERANCE);
+        assert_eq!(max_loss(2, liquidity).unwrap(), 693_148);
+    }
+
+    #[test]
+    fn cost_rejects_invalid_parameters() {
+        assert!(cost(&[0, 0], 0).is_err());
+        assert!(cost(&[], 1_000).is_err());
+    }
+
+    #[test]
+    fn prices_split_evenly_and_sum_to_one() {
+        assert_eq!(prices(&[0, 0], 1_000).unwrap(), vec![PRICE_PRECISION / 2; 2]);
+
+        let prices = prices(&[5_000, 1_000, 0], 2_000).unwrap();
+        let total: u64 = prices.iter().sum();
+        assert!((PRICE_PRECISION - 3..=PRICE_PRECISION).contains(&total));
+        assert!(prices[0] > prices[1] && prices[1] > prices[2]);
+    }
+
+    #[test]
+    fn prices_stay_finite_at_extreme_imbalance() {
+        let prices = prices(&[u64::MAX, 0], 1).unwrap();
+        assert_eq!(prices, vec![PRICE_PRECISION, 0]);
+    }
+
+    #[test]
+    fn buy_cost_charges_at_least_one_unit() {
+        assert_eq!(buy_cost(&[0, 0], 1_000_000_000, 0, 1).unwrap(), 1);
+        assert!(buy_cost(&[0, 0], 1_000_000_000, 0, 0).unwrap() >= 1);
+    }
+
+    #[test]
+    fn buy_cost_rounds_up_and_sell_rounds_down() {
+        let quantities = [0, 0];
+        let liquidity = 1_000_000;
+        let shares = 333_333;
+
+        let paid = buy_cost(&quantities, liquidity, 0, shares).unwrap();
+        let received = sell_proceeds(&[shares, 0], liquidity, 0, shares).unwrap();
+        assert!(received <= paid);
+        assert!(paid - received <= 1);
+    }
+
+    #[test]
+    fn buy_cost_is_bounded_by_shares() {
+        // A share never costs more than the one unit it can pay out
+        let paid = buy_cost(&[0, 0], 1_000, 0, 1_000_000).unwrap();
+        assert!(paid <= 1_000_000);
+        assert!(paid > 1_000_000 - max_loss(2, 1_000).unwrap());
+    }
+
+    #[test]
+    fn subsidy_covers_worst_case_payout() {
+        let liquidity = 50_000;
+        let mut quantities = vec![0u64; 4];
+        let mut collected = 0u64;
+        for _ in 0..20 {
+            collected += buy_cost(&quantities, liquidity, 2, 25_000).unwrap();
+            quantities[2] += 25_000;
+        }
+        assert!(collected + max_loss(4, liquidity).unwrap() >= quantities[2]);
+    }
+
+    #[test]
+    fn trades_reject_bad_outcomes_and_amounts() {
+        assert!(buy_cost(&[0, 0], 1_000, 2, 1).is_err());
+        assert!(buy_cost(&[u64::MAX, 0], 1_000, 0, 1).is_err());
+        assert!(sell_proceeds(&[10, 0], 1_000, 0, 11).is_err());
+        assert!(sell_proceeds(&[10, 0], 1_000, 5, 1).is_err());
+    }
+
+    #[test]
+    fn sell_of_nothing_pays_nothing() {
+        assert_eq!(sell_proceeds(&[10, 0], 1_000, 0, 0).unwrap(), 0);
+    }
+
+    #[test]
+    fn max_loss_rounds_up() {
+        assert_eq!(max_loss(1, 1_000).unwrap(), 0);
+        assert_eq!(max_loss(2, 1).unwrap(), 1);
+        assert_eq!(max_loss(16, u32::MAX as u64).unwrap(), 11_908_177_885);
+    }
+}
@@ -0,0 +1,238 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::pubkey;
+
+use crate::error::PredictionMarketError;
+#[cfg(feature = "mock-feed")]
+use crate::state::MockPriceFeed;
+use crate::state::{OUTCOME_A, OUTCOME_B};
+use crate::utils::{validate_price_data, PriceData};
+
+/// Pyth oracle program on mainnet-beta. Only price accounts it owns are
+/// accepted as Pyth feeds.
+pub const PYTH_PROGRAM_ID: Pubkey = pubkey!("FsJ3A3u2vn5cTVofAjvy6y5kwABJAqYWpe4975bi2epH");
+
+/// Decodes the raw data of a price feed account into `PriceData`. Each
+/// supported feed provider gets its own implementation.
+pub trait PriceFeedParser {
+    fn parse_price(data: &[u8]) -> Result<PriceData>;
+}
+
+/// Reads Pyth v2 price accounts. Only the aggregate price is used, and only
+/// while the feed reports it as trading; the exponent is left to the market,
+/// whose strike is quoted in the feed's own units.
+pub struct PythFeedParser;
+
+impl PythFeedParser {
+    const MAGIC: u32 = 0xa1b2_c3d4;
+    const VERSION: u32 = 2;
+    const PRICE_ACCOUNT: u32 = 3;
+    const STATUS_TRADING: u32 = 1;
+
+    // Offsets into the price account
+    const TIMESTAMP_OFFSET: usize = 96;
+    const AGG_PRICE_OFFSET: usize = 208;
+    const AGG_CONF_OFFSET: usize = 216;
+    const AGG_STATUS_OFFSET: usize = 224;
+    const MIN_LEN: usize = 240; // up to the end of the aggregate price
+
+    fn read_u32(data: &[u8], offset: usize) -> u32 {
+        u32::from_le_bytes(data[offset..offset + 4].try_into().unwrap())
+    }
+
+    fn read_u64(data: &[u8], offset: usize) -> u64 {
+        u64::from_le_bytes(data[offset..offset + 8].try_into().unwrap())
+    }
+}
+
+impl PriceFeedParser for PythFeedParser {
+    fn parse_price(data: &[u8]) -> Result<PriceData> {
+        require!(
+            data.len() >= Self::MIN_LEN
+                && Self::read_u32(data, 0) == Self::MAGIC
+                && Self::read_u32(data, 4) == Self::VERSION
+                && Self::read_u32(data, 8) == Self::PRICE_ACCOUNT,
+            PredictionMarketError::InvalidPriceFeed
+        );
+        require!(
+            Self::read_u32(data, Self::AGG_STATUS_OFFSET) == Self::STATUS_TRADING,
+            PredictionMarketError::InvalidPriceFeed
+        );
+
+        // The price is signed; a threshold market has no meaning below zero
+        let price = Self::read_u64(data, Self::AGG_PRICE_OFFSET);
+        require!(price as i64 > 0, PredictionMarketError::InvalidPriceFeed);
+
+        Ok(PriceData {
+            price,
+            confidence: Self::read_u64(data, Self::AGG_CONF_OFFSET),
+            timestamp: Self::read_u64(data, Self::TIMESTAMP_OFFSET) as i64,
+        })
+    }
+}
+
+/// Reads the hand-set `MockPriceFeed` accounts owned by this program. Only
+/// built with the `mock-feed` feature, since whoever creates a mock feed
+/// controls its price.
+#[cfg(feature = "mock-feed")]
+pub struct MockFeedParser;
+
+#[cfg(feature = "mock-feed")]
+impl PriceFeedParser for MockFeedParser {
+    fn parse_price(data: &[u8]) -> Result<PriceData> {
+        let feed = MockPriceFeed::try_deserialize(&mut &data[..])?;
+        Ok(PriceData {
+            price: feed.price,
+            confidence: feed.confidence,
+            timestamp: feed.timestamp,
+        })
+    }
+}
+
+/// Picks the parser from the program that owns `feed` and reads its price.
+pub fn load_price(feed: &AccountInfo) -> Result<PriceData> {
+    let data = feed.try_borrow_data()?;
+    if feed.owner == &PYTH_PROGRAM_ID {
+        return PythFeedParser::parse_price(&data);
+    }
+    #[cfg(feature = "mock-feed")]
+    if feed.owner == &crate::ID {
+        return MockFeedParser::parse_price(&data);
+    }
+    err!(PredictionMarketError::InvalidPriceFeed)
+}
+
+/// Settles a price threshold market ending at `end_time` against
+/// `price_data`: "Yes" (outcome A) if the price is at or above the strike.
+/// The price must be published at or after `end_time`, within
+/// `max_staleness` of it and with a tight enough confidence interval. Any
+/// update in that window is accepted, so the first resolver decides which
+/// one settles the market.
+pub fn threshold_outcome(
+    price_data: &PriceData,
+    strike_price: u64,
+    end_time: i64,
+    max_staleness: i64,
+    max_confidence_bps: u16,
+) -> Result<u8> {
+    require!(price_data.timestamp >= end_time, PredictionMarketError::StalePrice);
+    validate_price_data(price_data, max_staleness, end_time, max_confidence_bps)?;
+
+    Ok(if price_data.price >= strike_price {
+        OUTCOME_A
+    } else {
+        OUTCOME_B
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const END_TIME: i64 = 1_700_000_000;
+
+    fn pyth_account(price: i64, conf: u64, timestamp: i64, status: u32) -> Vec<u8> {
+        let mut data = vec![0u8; 3312];
+        data[0..4].copy_from_slice(&PythFeedParser::MAGIC.to_le_bytes());
+        data[4..8].copy_from_slice(&PythFeedParser::VERSION.to_le_bytes());
+        data[8..12].copy_from_slice(&PythFeedParser::PRICE_ACCOUNT.to_le_bytes());
+        data[96..104].copy_from_slice(&timestamp.to_le_bytes());
+        data[208..216].copy_from_slice(&price.to_le_bytes());
+        data[216..224].copy_from_slice(&conf.to_le_bytes());
+        data[224..228].copy_from_slice(&status.to_le_bytes());
+        data
+    }
+
+    fn load(owner: &Pubkey, data: &mut [u8]) -> Result<PriceData> {
+        let key = Pubkey::new_unique();
+        let mut lamports = 0;
+        let info = AccountInfo::new(&key, false, false, &mut lamports, data, owner, false, 0);
+        load_price(&info)
+    }
+
+    #[test]
+    fn parses_trading_pyth_price() {
+        let data = pyth_account(2_500_000, 1_000, END_TIME, 1);
+        let price_data = PythFeedParser::parse_price(&data).unwrap();
+        assert_eq!(
+            price_data,
+            PriceData { price: 2_500_000, confidence: 1_000, timestamp: END_TIME }
+        );
+    }
+
+    #[test]
+    fn rejects_unusable_pyth_accounts() {
+        // Halted or unknown status
+        assert!(PythFeedParser::parse_price(&pyth_account(2_500_000, 1_000, END_TIME, 0)).is_err());
+        assert!(PythFeedParser::parse_price(&pyth_account(2_500_000, 1_000, END_TIME, 2)).is_err());
+        // Zero and negative prices
+        assert!(PythFeedParser::parse_price(&pyth_account(0, 1_000, END_TIME, 1)).is_err());
+        assert!(PythFeedParser::parse_price(&pyth_account(-5, 1_000, END_TIME, 1)).is_err());
+        // Wrong magic, wrong account type, truncated
+        let mut data = pyth_account(2_500_000, 1_000, END_TIME, 1);
+        data[0] ^= 1;
+        assert!(PythFeedParser::parse_price(&data).is_err());
+        let mut data = pyth_account(2_500_000, 1_000, END_TIME, 1);
+        data[8] = 2;
+        assert!(PythFeedParser::parse_price(&data).is_err());
+        let data = pyth_account(2_500_000, 1_000, END_TIME, 1);
+        assert!(PythFeedParser::parse_price(&data[..PythFeedParser::MIN_LEN - 1]).is_err());
+    }
+
+    #[test]
+    fn load_price_checks_the_owner() {
+        let mut data = pyth_account(2_500_000, 1_000, END_TIME, 1);
+        assert_eq!(load(&PYTH_PROGRAM_ID, &mut data).unwrap().price, 2_500_000);
+        // Same bytes under any other owner are rejected
+        assert!(load(&Pubkey::new_unique(), &mut data).is_err());
+        assert!(load(&crate::ID, &mut data).is_err());
+    }
+
+    #[test]
+    fn threshold_outcome_compares_to_strike() {
+        let at_strike = PriceData { price: 10_000, confidence: 1, timestamp: END_TIME };
+        let below = PriceData { price: 9_999, ..at_strike.clone() };
+        assert_eq!(threshold_outcome(&at_strike, 10_000, END_TIME, 60, 100).unwrap(), OUTCOME_A);
+        assert_eq!(threshold_outcome(&below, 10_000, END_TIME, 60, 100).unwrap(), OUTCOME_B);
+    }
+
+    #[test]
+    fn threshold_outcome_rejects_stale_or_loose_prices() {
+        let price = PriceData { price: 10_000, confidence: 10, timestamp: END_TIME };
+        // Published before the market ended
+        let early = PriceData { timestamp: END_TIME - 1, ..price.clone() };
+        assert!(threshold_outcome(&early, 100, END_TIME, 60, 100).is_err());
+        // Published too long after
+        let late = PriceData { timestamp: END_TIME + 61, ..price.clone() };
+        assert!(threshold_outcome(&late, 100, END_TIME, 60, 100).is_err());
+        let edge = PriceData { timestamp: END_TIME + 60, ..price.clone() };
+        assert!(threshold_outcome(&edge, 100, END_TIME, 60, 100).is_ok());
+        // Confidence wider than 1% of the price, or missing
+        let loose = PriceData { confidence: 101, ..price.clone() };
+        assert!(threshold_outcome(&loose, 100, END_TIME, 60, 100).is_err());
+        let missing = PriceData { confidence: 0, ..price };
+        assert!(threshold_outcome(&missing, 100, END_TIME, 60, 100).is_err());
+    }
+
+    #[cfg(feature = "mock-feed")]
+    #[test]
+    fn resolves_through_mock_feed() {
+        let feed = MockPriceFeed {
+            authority: Pubkey::new_unique(),
+            feed_id: 7,
+            price: 15_000,
+            confidence: 1,
+            timestamp: END_TIME + 5,
+            bump: 255,
+        };
+        let mut data = Vec::new();
+        feed.try_serialize(&mut data).unwrap();
+
+        let price_data = load(&crate::ID, &mut data).unwrap();
+        assert_eq!(price_data.price, 15_000);
+        assert_eq!(threshold_outcome(&price_data, 15_000, END_TIME, 60, 100).unwrap(), OUTCOME_A);
+        assert_eq!(threshold_outcome(&price_data, 15_001, END_TIME, 60, 100).unwrap(), OUTCOME_B);
+
+        // A mock feed is only trusted when this program owns it
+        assert!(load(&Pubkey::new_unique(), &mut data).is_err());
+    }
+}
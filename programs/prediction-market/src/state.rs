@@ -1,6 +1,57 @@
-```rust
 use anchor_lang::prelude::*;
 
+use crate::error::PredictionMarketError;
+use crate::utils::{calculate_platform_fee, calculate_scalar_long_ratio};
+
+pub const MAX_TITLE_LEN: usize = 200;
+pub const MAX_DESCRIPTION_LEN: usize = 500;
+pub const MAX_CATEGORY_LEN: usize = 50;
+pub const MAX_ORACLE_DATA_LEN: usize = 256;
+pub const MAX_OUTCOMES: usize = 16;
+pub const MAX_OUTCOME_LABEL_LEN: usize = 32;
+pub const MAX_ORACLE_NAME_LEN: usize = 100;
+pub const MAX_ORACLE_DESCRIPTION_LEN: usize = 300;
+
+/// Fixed-point scale for outcome prices (1.0 == 100_000_000).
+pub const PRICE_PRECISION: u64 = 100_000_000;
+
+/// Fixed-point scale for the pool's fee growth and liquidity mining rewards
+/// per LP token.
+pub const FEE_GROWTH_PRECISION: u128 = 1_000_000_000_000;
+
+/// Index of outcome A (the "yes" side of a binary market).
+pub const OUTCOME_A: u8 = 0;
+/// Index of outcome B (the "no" side of a binary market).
+pub const OUTCOME_B: u8 = 1;
+
+/// Index of the LONG side of a scalar market.
+pub const SCALAR_LONG: u8 = 0;
+/// Index of the SHORT side of a scalar market.
+pub const SCALAR_SHORT: u8 = 1;
+
+/// Instruction classes that can be paused independently, as bit flags.
+pub const PAUSE_TRADING: u8 = 1 << 0;
+pub const PAUSE_LIQUIDITY: u8 = 1 << 1;
+pub const PAUSE_CLAIMS: u8 = 1 << 2;
+pub const PAUSE_ALL_CLASSES: u8 = PAUSE_TRADING | PAUSE_LIQUIDITY | PAUSE_CLAIMS;
+
+/// Longest dispute window a market can be created with (30 days).
+pub const MAX_DISPUTE_PERIOD: i64 = 30 * 24 * 60 * 60;
+
+/// How long an arbitrator or arbiter has to rule on an escalated market
+/// before anyone may cancel it instead (14 days).
+pub const ARBITRATION_PERIOD: i64 = 14 * 24 * 60 * 60;
+
+/// How long after settlement winners have to claim before the creator may
+/// close the market and sweep what is left to the treasury (180 days).
+pub const CLAIM_PERIOD: i64 = 180 * 24 * 60 * 60;
+
+/// Largest oracle committee a market can be resolved by.
+pub const MAX_COMMITTEE_MEMBERS: usize = 10;
+
+/// Number of order slots in a market's order book.
+pub const MAX_ORDERS: usize = 64;
+
 #[account]
 pub struct PredictionMarket {
     pub authority: Pubkey,
@@ -9,39 +60,491 @@ pub struct PredictionMarket {
     pub description: String,
     pub category: String,
     pub oracle: Pubkey,
+    pub oracle_account: Pubkey,
+    pub resolution_committee: Pubkey,
+    pub price_feed: Pubkey,
+    pub strike_price: u64,
+    pub max_price_staleness: i64,
+    pub max_price_confidence_bps: u16,
+    pub arbiter: Pubkey,
+    pub proposal_bond: u64,
+    pub proposal_liveness: i64,
+    pub quote_mint: Pubkey,
+    pub market_vault: Pubkey,
+    pub outcome_a_mint: Pubkey,
+    pub outcome_b_mint: Pubkey,
+    pub outcome_a_vault: Pubkey,
+    pub outcome_b_vault: Pubkey,
+    pub lp_mint: Pubkey,
+    pub end_time: i64,
     pub resolution_time: i64,
     pub creation_time: i64,
+    pub resolution_timestamp: i64,
+    pub dispute_period: i64,
+    pub dispute_end_time: i64,
+    pub arbitration_deadline: i64,
     pub total_liquidity: u64,
-    pub outcome_a_pool: u64,
-    pub outcome_b_pool: u64,
-    pub outcome_a_shares: u64,
-    pub outcome_b_shares: u64,
+    pub liquidity_providers_count: u64,
+    pub market_type: MarketType,
+    pub outcomes: Vec<MarketOutcome>,
+    pub lower_bound: u64,
+    pub upper_bound: u64,
+    pub pricing_model: PricingModel,
+    pub lmsr_liquidity: u64,
+    pub lmsr_subsidy: u64,
+    pub total_bets: u64,
+    pub open_positions: u64,
+    pub winning_pool: u64,
+    pub total_payout_pool: u64,
+    pub total_claimed: u64,
+    pub creator_fees: u64,
+    pub forfeited_stakes: u64,
     pub fee_rate: u16,
+    pub platform_fee_rate: u16,
+    pub exit_fee_rate: u16,
+    pub oracle_fee_bps: u16,
     pub status: MarketStatus,
-    pub resolved_outcome: Option<bool>,
+    pub paused_classes: u8,
+    pub winning_outcome: Option<u8>,
     pub resolution_price: Option<u64>,
+    pub oracle_data: Vec<u8>,
     pub bump: u8,
+    pub vault_bump: u8,
+    pub outcome_a_mint_bump: u8,
+    pub outcome_b_mint_bump: u8,
+    pub outcome_a_vault_bump: u8,
+    pub outcome_b_vault_bump: u8,
+    pub lp_mint_bump: u8,
 }
 
 impl PredictionMarket {
     pub const LEN: usize = 8 + // discriminator
         32 + // authority
         8 + // market_id
-        4 + 200 + // title (max 200 chars)
-        4 + 500 + // description (max 500 chars)
-        4 + 50 + // category (max 50 chars)
+        4 + MAX_TITLE_LEN + // title
+        4 + MAX_DESCRIPTION_LEN + // description
+        4 + MAX_CATEGORY_LEN + // category
         32 + // oracle
+        32 + // oracle_account
+        32 + // resolution_committee
+        32 + // price_feed
+        8 + // strike_price
+        8 + // max_price_staleness
+        2 + // max_price_confidence_bps
+        32 + // arbiter
+        8 + // proposal_bond
+        8 + // proposal_liveness
+        32 + // quote_mint
+        32 + // market_vault
+        32 + // outcome_a_mint
+        32 + // outcome_b_mint
+        32 + // outcome_a_vault
+        32 + // outcome_b_vault
+        32 + // lp_mint
+        8 + // end_time
         8 + // resolution_time
         8 + // creation_time
+        8 + // resolution_timestamp
+        8 + // dispute_period
+        8 + // dispute_end_time
+        8 + // arbitration_deadline
         8 + // total_liquidity
-        8 + // outcome_a_pool
-        8 + // outcome_b_pool
-        8 + // outcome_a_shares
-        8 + // outcome_b_shares
+        8 + // liquidity_providers_count
+        1 + // market_type
+        4 + MAX_OUTCOMES * MarketOutcome::LEN + // outcomes
+        8 + // lower_bound
+        8 + // upper_bound
+        1 + // pricing_model
+        8 + // lmsr_liquidity
+        8 + // lmsr_subsidy
+        8 + // total_bets
+        8 + // open_positions
+        8 + // winning_pool
+        8 + // total_payout_pool
+        8 + // total_claimed
+        8 + // creator_fees
+        8 + // forfeited_stakes
         2 + // fee_rate
+        2 + // platform_fee_rate
+        2 + // exit_fee_rate
+        2 + // oracle_fee_bps
         1 + // status
-        1 + 1 + // resolved_outcome (Option<bool>)
+        1 + // paused_classes
+        1 + 1 + // winning_outcome (Option<u8>)
         1 + 8 + // resolution_price (Option<u64>)
+        4 + MAX_ORACLE_DATA_LEN + // oracle_data
+        1 + // bump
+        1 + // vault_bump
+        1 + // outcome_a_mint_bump
+        1 + // outcome_b_mint_bump
+        1 + // outcome_a_vault_bump
+        1 + // outcome_b_vault_bump
+        1; // lp_mint_bump
+
+    pub fn total_pool(&self) -> Result<u64> {
+        self.outcomes
+            .iter()
+            .try_fold(0u64, |total, outcome| total.checked_add(outcome.pool))
+            .ok_or(PredictionMarketError::ArithmeticOverflow.into())
+    }
+
+    pub fn outcome(&self, outcome: u8) -> Result<&MarketOutcome> {
+        self.outcomes
+            .get(outcome as usize)
+            .ok_or(PredictionMarketError::InvalidOutcome.into())
+    }
+
+    pub fn outcome_mut(&mut self, outcome: u8) -> Result<&mut MarketOutcome> {
+        self.outcomes
+            .get_mut(outcome as usize)
+            .ok_or(PredictionMarketError::InvalidOutcome.into())
+    }
+
+    pub fn is_valid_outcome(&self, outcome: u8) -> bool {
+        (outcome as usize) < self.outcomes.len()
+    }
+
+    /// Re-quotes every outcome at its share of the parimutuel pool.
+    pub fn refresh_parimutuel_prices(&mut self) -> Result<()> {
+        let total_pool = self.total_pool()?;
+        let outcome_count = self.outcomes.len() as u64;
+        for outcome in self.outcomes.iter_mut() {
+            outcome.price = if total_pool == 0 {
+                PRICE_PRECISION / outcome_count
+            } else {
+                ((outcome.pool as u128)
+                    .checked_mul(PRICE_PRECISION as u128)
+                    .ok_or(PredictionMarketError::ArithmeticOverflow)?
+                    / total_pool as u128) as u64
+            };
+        }
+        Ok(())
+    }
+
+    pub fn outcome_shares(&self) -> Vec<u64> {
+        self.outcomes.iter().map(|outcome| outcome.shares).collect()
+    }
+
+    /// Records the reported outcome (or settlement price for scalar markets)
+    /// and returns the resulting winning pool.
+    pub fn apply_resolution(&mut self, params: &ResolutionParams) -> Result<u64> {
+        if self.market_type == MarketType::Scalar {
+            // Scalar markets have no single winner; each side's share is
+            // derived from the price at claim time
+            self.winning_outcome = None;
+            self.resolution_price = Some(params.resolution_price);
+            self.winning_pool = 0;
+        } else {
+            require!(self.is_valid_outcome(params.outcome), PredictionMarketError::InvalidOutcome);
+            self.winning_outcome = Some(params.outcome);
+            self.resolution_price = None;
+            self.winning_pool = self.outcome(params.outcome)?.pool;
+        }
+        Ok(self.winning_pool)
+    }
+
+    /// Moves an active market to `Resolved` on `params`, opening the dispute
+    /// window. Winners split whatever is left in the pools, plus exit fees
+    /// forfeited by sellers, after `oracle_fee`. Returns the winning and
+    /// losing pools.
+    pub fn settle(&mut self, params: &ResolutionParams, oracle_fee: u64, now: i64) -> Result<(u64, u64)> {
+        let total_pool = self.total_pool()?;
+
+        self.status = MarketStatus::Resolved;
+        self.resolution_timestamp = now;
+        self.dispute_end_time = now
+            .checked_add(self.dispute_period)
+            .ok_or(PredictionMarketError::ArithmeticOverflow)?;
+
+        let winning_pool = self.apply_resolution(params)?;
+        let losing_pool = if self.winning_outcome.is_some() {
+            total_pool
+                .checked_sub(winning_pool)
+                .ok_or(PredictionMarketError::ArithmeticUnderflow)?
+        } else {
+            0
+        };
+
+        self.total_payout_pool = total_pool
+            .checked_add(self.forfeited_stakes)
+            .and_then(|pot| pot.checked_sub(oracle_fee))
+            .ok_or(PredictionMarketError::ArithmeticUnderflow)?;
+
+        Ok((winning_pool, losing_pool))
+    }
+
+    /// Freezes the market as `Disputed` until an arbitrator rules, or until
+    /// `ARBITRATION_PERIOD` passes and it can be cancelled instead.
+    pub fn escalate(&mut self, now: i64) -> Result<()> {
+        self.status = MarketStatus::Disputed;
+        self.arbitration_deadline = now
+            .checked_add(ARBITRATION_PERIOD)
+            .ok_or(PredictionMarketError::ArithmeticOverflow)?;
+        Ok(())
+    }
+
+    /// Charges the creator fee on `amount` of traded collateral and credits
+    /// it to the creator's claimable balance. The fee stays in the market
+    /// vault until `claim_creator_fees`.
+    pub fn accrue_creator_fee(&mut self, amount: u64) -> Result<u64> {
+        let fee = calculate_platform_fee(amount, self.fee_rate)?;
+        self.creator_fees = self.creator_fees
+            .checked_add(fee)
+            .ok_or(PredictionMarketError::ArithmeticOverflow)?;
+        Ok(fee)
+    }
+
+    /// Settled value of a share position, in collateral.
+    pub fn position_payout(&self, shares: &[u64]) -> Result<u64> {
+        let mut payout = 0u128;
+        for (index, shares) in shares.iter().enumerate().take(self.outcomes.len()) {
+            if *shares == 0 {
+                continue;
+            }
+            let payout_per_share = self.payout_per_share(index as u8)?;
+            payout = payout
+                .checked_add(
+                    (*shares as u128)
+                        .checked_mul(payout_per_share as u128)
+                        .ok_or(PredictionMarketError::ArithmeticOverflow)?
+                        / PRICE_PRECISION as u128,
+                )
+                .ok_or(PredictionMarketError::ArithmeticOverflow)?;
+        }
+        u64::try_from(payout).map_err(|_| PredictionMarketError::ArithmeticOverflow.into())
+    }
+
+    /// Time after which unclaimed funds may be swept by `close_market`.
+    pub fn claim_deadline(&self) -> Result<i64> {
+        self.resolution_timestamp
+            .max(self.dispute_end_time)
+            .checked_add(CLAIM_PERIOD)
+            .ok_or(PredictionMarketError::ArithmeticOverflow.into())
+    }
+
+    /// Whether nothing more is owed on `bet`: it was claimed or refunded,
+    /// sold down to zero, or lost once the dispute window closed.
+    pub fn bet_settled(&self, bet: &Bet, now: i64) -> Result<bool> {
+        if bet.is_claimed || bet.amount == 0 {
+            return Ok(true);
+        }
+        Ok(self.status == MarketStatus::Resolved
+            && self.dispute_end_time <= now
+            && self.calculate_payout(bet.outcome, bet.amount)? == 0)
+    }
+
+    /// Whether nothing more is owed on `position`: its shares were claimed
+    /// or are worthless after resolution, or its stake was refunded.
+    pub fn position_settled(&self, position: &UserPosition, now: i64) -> Result<bool> {
+        match self.status {
+            MarketStatus::Resolved => Ok(self.dispute_end_time <= now
+                && self.position_payout(&position.shares)? == 0),
+            MarketStatus::Cancelled => Ok(position.total_withdrawn >= position.total_invested),
+            _ => Ok(false),
+        }
+    }
+
+    /// Whether `class` instructions are paused on this market.
+    pub fn is_halted(&self, class: u8) -> bool {
+        self.paused_classes & class != 0
+    }
+
+    /// Whether anyone has taken a position in the market: a bet, shares, a
+    /// share position or liquidity. Outcome tokens are not tracked here, so
+    /// callers also check the outcome mint supplies.
+    pub fn has_positions(&self) -> Result<bool> {
+        Ok(self.total_pool()? > 0
+            || self.outcome_shares().iter().any(|shares| *shares > 0)
+            || self.open_positions > 0
+            || self.total_liquidity > 0)
+    }
+
+    /// Whether the registered oracle (rather than a committee, a price feed
+    /// or optimistic proposals) is the one resolving this market.
+    pub fn resolved_by_oracle(&self) -> bool {
+        self.resolution_committee == Pubkey::default()
+            && self.price_feed == Pubkey::default()
+            && self.arbiter == Pubkey::default()
+    }
+
+    /// Whether a price threshold market's resolution window has passed. A
+    /// feed only ever holds its latest price, so once no price published
+    /// within `max_price_staleness` of `end_time` can be read any more, the
+    /// market can never be resolved from it.
+    pub fn price_window_closed(&self, now: i64) -> bool {
+        self.price_feed != Pubkey::default()
+            && self.end_time.saturating_add(self.max_price_staleness) < now
+    }
+
+    /// Checks that `canceller` may void this market while it is active. The
+    /// oracle may do so at any time, but only on a market it resolves itself;
+    /// otherwise only the creator may, and only while trading is open. A
+    /// threshold market left unresolved past its price window may be
+    /// cancelled by anyone.
+    pub fn check_cancel_authority(&self, canceller: &Pubkey, now: i64) -> Result<()> {
+        if self.price_window_closed(now) {
+            return Ok(());
+        }
+        let is_oracle = self.resolved_by_oracle() && *canceller == self.oracle;
+        require!(
+            is_oracle || *canceller == self.authority,
+            PredictionMarketError::UnauthorizedResolution
+        );
+        require!(
+            is_oracle || now < self.end_time,
+            PredictionMarketError::MarketExpired
+        );
+        Ok(())
+    }
+
+    /// Whether `params` reports the same result the market is resolved to.
+    pub fn matches_resolution(&self, params: &ResolutionParams) -> bool {
+        if self.market_type == MarketType::Scalar {
+            self.resolution_price == Some(params.resolution_price)
+        } else {
+            self.winning_outcome == Some(params.outcome)
+        }
+    }
+
+    /// SPL mint and market-owned escrow vault of an outcome token. Only the
+    /// two sides of a 2-outcome market are tokenised.
+    pub fn outcome_token_accounts(&self, outcome: u8) -> Result<(Pubkey, Pubkey)> {
+        require!(self.outcomes.len() == 2, PredictionMarketError::InvalidOutcomeCount);
+        match outcome {
+            OUTCOME_A => Ok((self.outcome_a_mint, self.outcome_a_vault)),
+            OUTCOME_B => Ok((self.outcome_b_mint, self.outcome_b_vault)),
+            _ => err!(PredictionMarketError::InvalidOutcome),
+        }
+    }
+
+    /// Collateral paid out per outcome share after resolution, scaled by
+    /// `PRICE_PRECISION`.
+    pub fn payout_per_share(&self, outcome: u8) -> Result<u64> {
+        require!(self.is_valid_outcome(outcome), PredictionMarketError::InvalidOutcome);
+
+        match self.market_type {
+            MarketType::Scalar => {
+                let resolution_price = self
+                    .resolution_price
+                    .ok_or(PredictionMarketError::MarketNotResolved)?;
+                let long_ratio = calculate_scalar_long_ratio(
+                    self.lower_bound,
+                    self.upper_bound,
+                    resolution_price,
+                )?;
+                Ok(if outcome == SCALAR_LONG {
+                    long_ratio
+                } else {
+                    PRICE_PRECISION - long_ratio
+                })
+            }
+            MarketType::Binary | MarketType::Categorical => {
+                let winning_outcome = self
+                    .winning_outcome
+                    .ok_or(PredictionMarketError::MarketNotResolved)?;
+                Ok(if outcome == winning_outcome { PRICE_PRECISION } else { 0 })
+            }
+        }
+    }
+
+    /// Collateral an outcome token redeems for, scaled by `PRICE_PRECISION`.
+    /// Tokens are only minted in complete sets, so after a cancellation each
+    /// side returns half of the collateral that minted it.
+    pub fn token_redemption_value(&self, outcome: u8) -> Result<u64> {
+        if self.status == MarketStatus::Cancelled {
+            require!(self.is_valid_outcome(outcome), PredictionMarketError::InvalidOutcome);
+            return Ok(PRICE_PRECISION / 2);
+        }
+        self.payout_per_share(outcome)
+    }
+
+    /// Payout owed to a stake of `amount` on `outcome` once the market is
+    /// resolved. Categorical markets pay the winning outcome pro-rata; scalar
+    /// markets split the payout pool between LONG and SHORT according to
+    /// where `resolution_price` landed inside the bounds.
+    pub fn calculate_payout(&self, outcome: u8, amount: u64) -> Result<u64> {
+        let (side_payout_pool, side_pool) = match self.market_type {
+            MarketType::Scalar => {
+                let resolution_price = self
+                    .resolution_price
+                    .ok_or(PredictionMarketError::MarketNotResolved)?;
+                let long_ratio = calculate_scalar_long_ratio(
+                    self.lower_bound,
+                    self.upper_bound,
+                    resolution_price,
+                )?;
+                let long_payout_pool = (self.total_payout_pool as u128)
+                    .checked_mul(long_ratio as u128)
+                    .ok_or(PredictionMarketError::ArithmeticOverflow)?
+                    .checked_div(PRICE_PRECISION as u128)
+                    .ok_or(PredictionMarketError::DivisionByZero)? as u64;
+                let side_payout_pool = match outcome {
+                    SCALAR_LONG => long_payout_pool,
+                    SCALAR_SHORT => self
+                        .total_payout_pool
+                        .checked_sub(long_payout_pool)
+                        .ok_or(PredictionMarketError::ArithmeticUnderflow)?,
+                    _ => return err!(PredictionMarketError::InvalidOutcome),
+                };
+                (side_payout_pool, self.outcome(outcome)?.pool)
+            }
+            MarketType::Binary | MarketType::Categorical => {
+                let winning_outcome = self
+                    .winning_outcome
+                    .ok_or(PredictionMarketError::MarketNotResolved)?;
+                require!(outcome == winning_outcome, PredictionMarketError::NotAWinner);
+                (self.total_payout_pool, self.winning_pool)
+            }
+        };
+
+        require!(side_pool > 0, PredictionMarketError::InvalidWinningPool);
+
+        Ok((amount as u128)
+            .checked_mul(side_payout_pool as u128)
+            .ok_or(PredictionMarketError::ArithmeticOverflow)?
+            .checked_div(side_pool as u128)
+            .ok_or(PredictionMarketError::DivisionByZero)? as u64)
+    }
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct MarketOutcome {
+    pub label: String,
+    pub pool: u64,
+    pub shares: u64,
+    pub price: u64,
+}
+
+impl MarketOutcome {
+    pub const LEN: usize = 4 + MAX_OUTCOME_LABEL_LEN + // label
+        8 + // pool
+        8 + // shares
+        8; // price
+}
+
+#[account]
+pub struct Bet {
+    pub bettor: Pubkey,
+    pub market: Pubkey,
+    pub outcome: u8,
+    pub amount: u64,
+    pub timestamp: i64,
+    pub is_claimed: bool,
+    pub winnings_claimed: u64,
+    pub referrer: Pubkey,
+    pub bump: u8,
+}
+
+impl Bet {
+    pub const LEN: usize = 8 + // discriminator
+        32 + // bettor
+        32 + // market
+        1 + // outcome
+        8 + // amount
+        8 + // timestamp
+        1 + // is_claimed
+        8 + // winnings_claimed
+        32 + // referrer
         1; // bump
 }
 
@@ -49,10 +552,12 @@ impl PredictionMarket {
 pub struct UserPosition {
     pub user: Pubkey,
     pub market: Pubkey,
-    pub outcome_a_shares: u64,
-    pub outcome_b_shares: u64,
+    pub shares: [u64; MAX_OUTCOMES],
     pub total_invested: u64,
+    pub total_withdrawn: u64,
+    pub lp_fees_paid: u64,
     pub last_update_time: i64,
+    pub referrer: Pubkey,
     pub bump: u8,
 }
 
@@ -60,24 +565,103 @@ impl UserPosition {
     pub const LEN: usize = 8 + // discriminator
         32 + // user
         32 + // market
-        8 + // outcome_a_shares
-        8 + // outcome_b_shares
+        8 * MAX_OUTCOMES + // shares
         8 + // total_invested
+        8 + // total_withdrawn
+        8 + // lp_fees_paid
         8 + // last_update_time
+        32 + // referrer
         1; // bump
 }
 
+/// A provider's stake in a market's pool. LP tokens are minted into the
+/// market's `lp_escrow` and never leave it, so `lp_tokens` is the only record
+/// of who owns them and fee and reward accounting can't be sidestepped by
+/// moving tokens around.
+#[account]
+pub struct LiquidityProvider {
+    pub user: Pubkey,
+    pub market: Pubkey,
+    pub lp_tokens: u64,
+    pub total_deposited: u64,
+    pub fee_growth_checkpoint: u128,
+    pub fees_owed: u64,
+    pub fees_claimed: u64,
+    pub reward_checkpoint: u128,
+    pub rewards_owed: u64,
+    pub rewards_claimed: u64,
+    pub bump: u8,
+}
+
+impl LiquidityProvider {
+    pub const LEN: usize = 8 + // discriminator
+        32 + // user
+        32 + // market
+        8 + // lp_tokens
+        8 + // total_deposited
+        16 + // fee_growth_checkpoint
+        8 + // fees_owed
+        8 + // fees_claimed
+        16 + // reward_checkpoint
+        8 + // rewards_owed
+        8 + // rewards_claimed
+        1; // bump
+
+    /// Credits the fees earned by the provider's current LP tokens since the
+    /// last checkpoint. Must run before `lp_tokens` changes so each fee is
+    /// attributed to the tokens that were in the pool when it was charged.
+    pub fn settle_fees(&mut self, fee_growth_per_share: u128) -> Result<()> {
+        let earned = fee_growth_per_share
+            .checked_sub(self.fee_growth_checkpoint)
+            .and_then(|growth| growth.checked_mul(self.lp_tokens as u128))
+            .ok_or(PredictionMarketError::ArithmeticOverflow)?
+            / FEE_GROWTH_PRECISION;
+        self.fees_owed = u64::try_from(earned)
+            .ok()
+            .and_then(|earned| self.fees_owed.checked_add(earned))
+            .ok_or(PredictionMarketError::ArithmeticOverflow)?;
+        self.fee_growth_checkpoint = fee_growth_per_share;
+        Ok(())
+    }
+
+    /// Liquidity mining counterpart of `settle_fees`, with the same rule
+    /// about running before `lp_tokens` changes.
+    pub fn settle_rewards(&mut self, reward_per_share: u128) -> Result<()> {
+        let earned = reward_per_share
+            .checked_sub(self.reward_checkpoint)
+            .and_then(|growth| growth.checked_mul(self.lp_tokens as u128))
+            .ok_or(PredictionMarketError::ArithmeticOverflow)?
+            / FEE_GROWTH_PRECISION;
+        self.rewards_owed = u64::try_from(earned)
+            .ok()
+            .and_then(|earned| self.rewards_owed.checked_add(earned))
+            .ok_or(PredictionMarketError::ArithmeticOverflow)?;
+        self.reward_checkpoint = reward_per_share;
+        Ok(())
+    }
+}
+
 #[account]
 pub struct LiquidityPool {
     pub market: Pubkey,
     pub token_mint: Pubkey,
     pub token_vault: Pubkey,
     pub total_supply: u64,
+    pub total_liquidity: u64,
     pub reserve_a: u64,
     pub reserve_b: u64,
     pub fee_collected: u64,
+    pub total_fees_collected: u64,
+    pub fee_growth_per_share: u128,
+    pub fee_rate: u16,
+    pub withdrawal_fee_rate: u16,
+    pub max_withdrawal_fee_rate: u16,
+    pub withdrawal_fee_ramp: i64,
+    pub lock_after_cutoff: bool,
+    pub rewards: Pubkey,
     pub bump: u8,
     pub vault_bump: u8,
+    pub lp_escrow_bump: u8,
 }
 
 impl LiquidityPool {
@@ -86,11 +670,300 @@ impl LiquidityPool {
         32 + // token_mint
         32 + // token_vault
         8 + // total_supply
+        8 + // total_liquidity
         8 + // reserve_a
         8 + // reserve_b
         8 + // fee_collected
+        8 + // total_fees_collected
+        16 + // fee_growth_per_share
+        2 + // fee_rate
+        2 + // withdrawal_fee_rate
+        2 + // max_withdrawal_fee_rate
+        8 + // withdrawal_fee_ramp
+        1 + // lock_after_cutoff
+        32 + // rewards
+        1 + // bump
+        1 + // vault_bump
+        1; // lp_escrow_bump
+
+    /// Withdrawal fee in basis points at `now`. Flat at `withdrawal_fee_rate`
+    /// until `withdrawal_fee_ramp` seconds before `resolution_time`, then
+    /// rising linearly to `max_withdrawal_fee_rate` at resolution time.
+    pub fn withdrawal_fee_rate_at(&self, now: i64, resolution_time: i64) -> u16 {
+        let ramp_start = resolution_time.saturating_sub(self.withdrawal_fee_ramp);
+        if self.withdrawal_fee_ramp <= 0 || now <= ramp_start {
+            return self.withdrawal_fee_rate;
+        }
+        if now >= resolution_time {
+            return self.max_withdrawal_fee_rate;
+        }
+
+        // Widened to i128 so no time range can overflow; the result is
+        // below `spread` and always fits back into u16
+        let spread = self.max_withdrawal_fee_rate.saturating_sub(self.withdrawal_fee_rate);
+        let elapsed = now as i128 - ramp_start as i128;
+        let increase = spread as i128 * elapsed / self.withdrawal_fee_ramp as i128;
+        self.withdrawal_fee_rate
+            .saturating_add(u16::try_from(increase).unwrap_or(spread))
+    }
+
+    /// Whether every provider has left the pool. Whatever `fee_collected`
+    /// still holds then is rounding dust owed to nobody, which
+    /// `close_market` sweeps to the treasury with the rest of the vault.
+    pub fn is_drained(&self) -> bool {
+        self.total_supply == 0
+    }
+
+    /// Books `fee` as owed to the current LP token holders, pro rata.
+    /// `fee_collected` tracks fees held in the vault but not yet paid out.
+    /// With no LP tokens outstanding nobody could ever claim the fee, so it
+    /// is refused; callers waive it instead.
+    pub fn accrue_fee(&mut self, fee: u64) -> Result<()> {
+        if fee == 0 {
+            return Ok(());
+        }
+        require!(self.total_supply > 0, PredictionMarketError::InsufficientLiquidity);
+
+        self.fee_collected = self.fee_collected
+            .checked_add(fee)
+            .ok_or(PredictionMarketError::ArithmeticOverflow)?;
+        self.total_fees_collected = self.total_fees_collected
+            .checked_add(fee)
+            .ok_or(PredictionMarketError::ArithmeticOverflow)?;
+        self.fee_growth_per_share = (fee as u128)
+            .checked_mul(FEE_GROWTH_PRECISION)
+            .map(|growth| growth / self.total_supply as u128)
+            .and_then(|growth| self.fee_growth_per_share.checked_add(growth))
+            .ok_or(PredictionMarketError::ArithmeticOverflow)?;
+        Ok(())
+    }
+}
+
+#[account]
+pub struct LiquidityRewards {
+    pub market: Pubkey,
+    pub sponsor: Pubkey,
+    pub reward_mint: Pubkey,
+    pub reward_vault: Pubkey,
+    pub emission_rate: u64,
+    pub start_time: i64,
+    pub end_time: i64,
+    pub last_update_time: i64,
+    pub reward_per_share: u128,
+    pub total_funded: u64,
+    pub total_distributed: u64,
+    pub total_claimed: u64,
+    pub total_reclaimed: u64,
+    pub bump: u8,
+    pub vault_bump: u8,
+}
+
+impl LiquidityRewards {
+    pub const LEN: usize = 8 + // discriminator
+        32 + // market
+        32 + // sponsor
+        32 + // reward_mint
+        32 + // reward_vault
+        8 + // emission_rate
+        8 + // start_time
+        8 + // end_time
+        8 + // last_update_time
+        16 + // reward_per_share
+        8 + // total_funded
+        8 + // total_distributed
+        8 + // total_claimed
+        8 + // total_reclaimed
         1 + // bump
         1; // vault_bump
+
+    /// Emits rewards for the time elapsed since the last update, shared
+    /// across `lp_supply`. Whatever is emitted while the pool is empty is
+    /// never distributed and stays reclaimable by the sponsor.
+    pub fn accrue(&mut self, now: i64, lp_supply: u64) -> Result<()> {
+        let from = self.last_update_time.max(self.start_time);
+        let until = now.min(self.end_time);
+        if until <= from {
+            return Ok(());
+        }
+
+        if lp_supply > 0 {
+            let emitted = (until as i128 - from as i128) as u128 * self.emission_rate as u128;
+            let growth = emitted
+                .checked_mul(FEE_GROWTH_PRECISION)
+                .ok_or(PredictionMarketError::ArithmeticOverflow)?
+                / lp_supply as u128;
+            self.reward_per_share = self.reward_per_share
+                .checked_add(growth)
+                .ok_or(PredictionMarketError::ArithmeticOverflow)?;
+            self.total_distributed = u64::try_from(emitted)
+                .ok()
+                .and_then(|emitted| self.total_distributed.checked_add(emitted))
+                .ok_or(PredictionMarketError::ArithmeticOverflow)?;
+        }
+        self.last_update_time = until;
+        Ok(())
+    }
+
+    /// Funded rewards that have not been emitted to providers or returned
+    /// to the sponsor yet.
+    pub fn reclaimable(&self) -> Result<u64> {
+        self.total_funded
+            .checked_sub(self.total_distributed)
+            .and_then(|remaining| remaining.checked_sub(self.total_reclaimed))
+            .ok_or(PredictionMarketError::ArithmeticUnderflow.into())
+    }
+}
+
+#[account(zero_copy)]
+pub struct OrderBook {
+    pub market: Pubkey,
+    pub next_order_id: u64,
+    pub order_count: u32,
+    pub bump: u8,
+    pub _padding: [u8; 3],
+    pub orders: [Order; MAX_ORDERS],
+}
+
+impl OrderBook {
+    pub const LEN: usize = 8 + // discriminator
+        32 + // market
+        8 + // next_order_id
+        4 + // order_count
+        1 + // bump
+        3 + // padding
+        Order::LEN * MAX_ORDERS; // orders
+
+    /// Slot index of the order with `order_id`.
+    pub fn find_order(&self, order_id: u64) -> Result<usize> {
+        self.orders
+            .iter()
+            .position(|order| order.status != OrderStatus::Empty as u8 && order.order_id == order_id)
+            .ok_or(PredictionMarketError::OrderNotFound.into())
+    }
+
+    /// Index of the first unused slot.
+    pub fn free_slot(&self) -> Result<usize> {
+        self.orders
+            .iter()
+            .position(|order| order.status == OrderStatus::Empty as u8)
+            .ok_or(PredictionMarketError::OrderBookFull.into())
+    }
+
+    /// Marks every live order whose expiry has passed as expired.
+    pub fn expire_orders(&mut self, now: i64) {
+        for order in self.orders.iter_mut() {
+            order.expire_if_due(now);
+        }
+    }
+
+    /// Best live order on one side of an outcome: the highest bid or the
+    /// lowest ask, with ties going to the oldest order.
+    pub fn best_order(&self, outcome: u8, side: OrderSide) -> Option<usize> {
+        let mut best: Option<usize> = None;
+        for (index, order) in self.orders.iter().enumerate() {
+            if !order.is_live() || order.outcome != outcome || order.side != side as u8 {
+                continue;
+            }
+            let better = match best.map(|current| &self.orders[current]) {
+                None => true,
+                Some(current) => {
+                    let improves = match side {
+                        OrderSide::Buy => order.price > current.price,
+                        OrderSide::Sell => order.price < current.price,
+                    };
+                    improves || (order.price == current.price && order.order_id < current.order_id)
+                }
+            };
+            if better {
+                best = Some(index);
+            }
+        }
+        best
+    }
+}
+
+#[zero_copy]
+pub struct Order {
+    pub owner: Pubkey,
+    pub order_id: u64,
+    pub price: u64,
+    pub shares: u64,
+    pub filled: u64,
+    pub escrow: u64,
+    pub claimable_shares: u64,
+    pub claimable_collateral: u64,
+    pub created_at: i64,
+    pub expires_at: i64,
+    pub outcome: u8,
+    pub side: u8,
+    pub status: u8,
+    pub _padding: [u8; 5],
+}
+
+impl Order {
+    pub const LEN: usize = 32 + // owner
+        8 + // order_id
+        8 + // price
+        8 + // shares
+        8 + // filled
+        8 + // escrow
+        8 + // claimable_shares
+        8 + // claimable_collateral
+        8 + // created_at
+        8 + // expires_at
+        1 + // outcome
+        1 + // side
+        1 + // status
+        5; // padding
+
+    pub fn is_live(&self) -> bool {
+        self.status == OrderStatus::Open as u8 || self.status == OrderStatus::PartiallyFilled as u8
+    }
+
+    pub fn remaining(&self) -> u64 {
+        self.shares.saturating_sub(self.filled)
+    }
+
+    pub fn expire_if_due(&mut self, now: i64) {
+        if self.is_live() && self.expires_at != 0 && self.expires_at <= now {
+            self.status = OrderStatus::Expired as u8;
+        }
+    }
+
+    /// Collateral for `shares` at `price`, either rounded up (what a bid must
+    /// escrow) or down (what a fill moves between the two sides).
+    pub fn collateral_for(shares: u64, price: u64, round_up: bool) -> Result<u64> {
+        let value = (shares as u128)
+            .checked_mul(price as u128)
+            .ok_or(PredictionMarketError::ArithmeticOverflow)?;
+        let rounding = if round_up { PRICE_PRECISION as u128 - 1 } else { 0 };
+        u64::try_from((value + rounding) / PRICE_PRECISION as u128)
+            .map_err(|_| PredictionMarketError::ArithmeticOverflow.into())
+    }
+
+    /// Takes everything the owner can withdraw right now as
+    /// `(outcome_shares, collateral, closed)`. Once the order is no longer
+    /// live the unfilled escrow is returned as well and the slot is freed.
+    pub fn take_settlement(&mut self) -> Result<(u64, u64, bool)> {
+        let mut shares = std::mem::take(&mut self.claimable_shares);
+        let mut collateral = std::mem::take(&mut self.claimable_collateral);
+
+        let closed = !self.is_live();
+        if closed {
+            if self.side == OrderSide::Buy as u8 {
+                collateral = collateral
+                    .checked_add(std::mem::take(&mut self.escrow))
+                    .ok_or(PredictionMarketError::ArithmeticOverflow)?;
+            } else {
+                shares = shares
+                    .checked_add(self.remaining())
+                    .ok_or(PredictionMarketError::ArithmeticOverflow)?;
+            }
+            self.status = OrderStatus::Empty as u8;
+        }
+
+        Ok((shares, collateral, closed))
+    }
 }
 
 #[account]
@@ -111,39 +984,338 @@ impl OracleAccount {
     pub const LEN: usize = 8 + // discriminator
         32 + // authority
         8 + // oracle_id
-        4 + 100 + // name (max 100 chars)
-        4 + 300 + // description (max 300 chars)
+        4 + MAX_ORACLE_NAME_LEN + // name
+        4 + MAX_ORACLE_DESCRIPTION_LEN + // description
         8 + // reputation_score
         8 + // total_resolutions
         8 + // correct_resolutions
         1 + // is_active
         8 + // creation_time
         1; // bump
+
+    /// Counts a new resolution. It is presumed correct until a dispute
+    /// overturns it.
+    pub fn record_resolution(&mut self) -> Result<()> {
+        self.total_resolutions = self.total_resolutions
+            .checked_add(1)
+            .ok_or(PredictionMarketError::ArithmeticOverflow)?;
+        self.correct_resolutions = self.correct_resolutions
+            .checked_add(1)
+            .ok_or(PredictionMarketError::ArithmeticOverflow)?;
+        self.refresh_reputation()
+    }
+
+    /// Takes back the credit for a resolution the arbitrator overturned.
+    pub fn record_overturned(&mut self) -> Result<()> {
+        self.correct_resolutions = self.correct_resolutions
+            .checked_sub(1)
+            .ok_or(PredictionMarketError::ArithmeticUnderflow)?;
+        self.refresh_reputation()
+    }
+
+    /// Reputation is the share of resolutions that held up, in basis points.
+    fn refresh_reputation(&mut self) -> Result<()> {
+        self.reputation_score = self.correct_resolutions
+            .checked_mul(10000)
+            .ok_or(PredictionMarketError::ArithmeticOverflow)?
+            .checked_div(self.total_resolutions)
+            .unwrap_or(0);
+        Ok(())
+    }
+}
+
+/// A bond to hand back: who posted it and how much.
+pub type BondRefund = (Pubkey, u64);
+
+#[account]
+pub struct Proposal {
+    pub market: Pubkey,
+    pub proposer: Pubkey,
+    pub challenger: Pubkey,
+    pub outcome: u8,
+    pub resolution_price: u64,
+    pub bond: u64,
+    pub proposed_at: i64,
+    pub liveness_end: i64,
+    pub status: ProposalStatus,
+    pub bump: u8,
+}
+
+impl Proposal {
+    pub const LEN: usize = 8 + // discriminator
+        32 + // market
+        32 + // proposer
+        32 + // challenger
+        1 + // outcome
+        8 + // resolution_price
+        8 + // bond
+        8 + // proposed_at
+        8 + // liveness_end
+        1 + // status
+        1; // bump
+
+    pub fn params(&self) -> ResolutionParams {
+        ResolutionParams {
+            outcome: self.outcome,
+            resolution_price: self.resolution_price,
+        }
+    }
+
+    /// Voids a proposal whose market is cancelled before it settled and
+    /// returns the bonds still escrowed for it: the proposer's, and the
+    /// challenger's once challenged. A settled proposal is left as it is.
+    pub fn void(&mut self) -> (Option<BondRefund>, Option<BondRefund>) {
+        let refunds = match self.status {
+            ProposalStatus::Pending => (Some((self.proposer, self.bond)), None),
+            ProposalStatus::Challenged => (
+                Some((self.proposer, self.bond)),
+                Some((self.challenger, self.bond)),
+            ),
+            _ => return (None, None),
+        };
+        self.status = ProposalStatus::Voided;
+        refunds
+    }
+}
+
+/// Price feed account owned by this program whose price is set by hand.
+/// Lets threshold markets be exercised locally without an external feed;
+/// only read or written in builds with the `mock-feed` feature.
+#[account]
+pub struct MockPriceFeed {
+    pub authority: Pubkey,
+    pub feed_id: u64,
+    pub price: u64,
+    pub confidence: u64,
+    pub timestamp: i64,
+    pub bump: u8,
+}
+
+impl MockPriceFeed {
+    pub const LEN: usize = 8 + // discriminator
+        32 + // authority
+        8 + // feed_id
+        8 + // price
+        8 + // confidence
+        8 + // timestamp
+        1; // bump
+}
+
+#[account]
+pub struct ResolutionVote {
+    pub market: Pubkey,
+    pub members: Vec<Pubkey>,
+    pub votes: Vec<CommitteeVote>,
+    pub threshold: u8,
+    pub bump: u8,
+}
+
+impl ResolutionVote {
+    pub const LEN: usize = 8 + // discriminator
+        32 + // market
+        4 + MAX_COMMITTEE_MEMBERS * 32 + // members
+        4 + MAX_COMMITTEE_MEMBERS * CommitteeVote::LEN + // votes
+        1 + // threshold
+        1; // bump
+
+    /// Number of members whose current vote reports the same result as
+    /// `params`.
+    pub fn agreeing_votes(&self, params: &ResolutionParams, is_scalar: bool) -> usize {
+        self.votes
+            .iter()
+            .filter(|vote| {
+                vote.has_voted
+                    && if is_scalar {
+                        vote.resolution_price == params.resolution_price
+                    } else {
+                        vote.outcome == params.outcome
+                    }
+            })
+            .count()
+    }
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Default)]
+pub struct CommitteeVote {
+    pub has_voted: bool,
+    pub outcome: u8,
+    pub resolution_price: u64,
+}
+
+impl CommitteeVote {
+    pub const LEN: usize = 1 + // has_voted
+        1 + // outcome
+        8; // resolution_price
 }
 
 #[account]
 pub struct GlobalState {
     pub authority: Pubkey,
+    pub pending_authority: Pubkey,
     pub total_markets: u64,
     pub total_volume: u64,
     pub total_fees_collected: u64,
     pub platform_fee_rate: u16,
+    pub max_creator_fee_rate: u16,
+    pub referral_fee_share: u16,
+    pub exit_fee_rate: u16,
     pub min_resolution_time: i64,
     pub max_resolution_time: i64,
     pub is_paused: bool,
+    pub paused_classes: u8,
+    pub guardian: Pubkey,
+    pub arbitrator: Pubkey,
+    pub dispute_bond: u64,
     pub bump: u8,
 }
 
 impl GlobalState {
     pub const LEN: usize = 8 + // discriminator
         32 + // authority
+        32 + // pending_authority
         8 + // total_markets
         8 + // total_volume
         8 + // total_fees_collected
         2 + // platform_fee_rate
+        2 + // max_creator_fee_rate
+        2 + // referral_fee_share
+        2 + // exit_fee_rate
         8 + // min_resolution_time
         8 + // max_resolution_time
         1 + // is_paused
+        1 + // paused_classes
+        32 + // guardian
+        32 + // arbitrator
+        8 + // dispute_bond
+        1; // bump
+
+    pub fn apply_config(&mut self, config: &ProgramConfig) -> Result<()> {
+        require!(
+            config.platform_fee_rate <= 10000
+                && config.max_creator_fee_rate <= 10000
+                && config.referral_fee_share <= 10000
+                && config.exit_fee_rate <= 10000,
+            PredictionMarketError::InvalidFeePercentage
+        );
+        require!(
+            config.min_resolution_time >= 0
+                && config.min_resolution_time <= config.max_resolution_time,
+            PredictionMarketError::InvalidMarketDuration
+        );
+
+        self.platform_fee_rate = config.platform_fee_rate;
+        self.max_creator_fee_rate = config.max_creator_fee_rate;
+        self.referral_fee_share = config.referral_fee_share;
+        self.exit_fee_rate = config.exit_fee_rate;
+        self.min_resolution_time = config.min_resolution_time;
+        self.max_resolution_time = config.max_resolution_time;
+        self.guardian = config.guardian;
+        self.arbitrator = config.arbitrator;
+        self.dispute_bond = config.dispute_bond;
+        Ok(())
+    }
+
+    /// Whether `class` instructions are paused program-wide. A program halt
+    /// stops everything except claims, which only stop when their class is
+    /// paused explicitly so users can always exit.
+    pub fn is_halted(&self, class: u8) -> bool {
+        (self.is_paused && class != PAUSE_CLAIMS) || self.paused_classes & class != 0
+    }
+
+    /// Referrer's cut of a platform fee.
+    pub fn referral_cut(&self, platform_fee: u64) -> Result<u64> {
+        calculate_platform_fee(platform_fee, self.referral_fee_share)
+    }
+
+    /// Whether `signer` may change pause flags. The guardian can only add
+    /// pauses; lifting them is left to the admin.
+    pub fn can_set_pause(&self, signer: Pubkey, lifts_pause: bool) -> bool {
+        signer == self.authority || (signer == self.guardian && !lifts_pause)
+    }
+}
+
+#[account]
+pub struct Referrer {
+    pub authority: Pubkey,
+    pub mint: Pubkey,
+    pub total_volume: u64,
+    pub total_trades: u64,
+    pub rewards_earned: u64,
+    pub rewards_claimed: u64,
+    pub bump: u8,
+}
+
+impl Referrer {
+    pub const LEN: usize = 8 + // discriminator
+        32 + // authority
+        32 + // mint
+        8 + // total_volume
+        8 + // total_trades
+        8 + // rewards_earned
+        8 + // rewards_claimed
+        1; // bump
+
+    /// Ties a bet or position to this referrer on its first referred trade.
+    /// Later trades must name the same referrer.
+    pub fn attribute(&self, attributed: &mut Pubkey, key: Pubkey) -> Result<()> {
+        if *attributed == Pubkey::default() {
+            *attributed = key;
+        }
+        require!(*attributed == key, PredictionMarketError::ReferrerMismatch);
+        Ok(())
+    }
+
+    pub fn record_trade(&mut self, volume: u64) -> Result<()> {
+        self.total_volume = self.total_volume
+            .checked_add(volume)
+            .ok_or(PredictionMarketError::ArithmeticOverflow)?;
+        self.total_trades = self.total_trades
+            .checked_add(1)
+            .ok_or(PredictionMarketError::ArithmeticOverflow)?;
+        Ok(())
+    }
+
+    pub fn credit_reward(&mut self, amount: u64) -> Result<()> {
+        self.rewards_earned = self.rewards_earned
+            .checked_add(amount)
+            .ok_or(PredictionMarketError::ArithmeticOverflow)?;
+        Ok(())
+    }
+
+    pub fn claimable(&self) -> Result<u64> {
+        self.rewards_earned
+            .checked_sub(self.rewards_claimed)
+            .ok_or(PredictionMarketError::ArithmeticUnderflow.into())
+    }
+}
+
+#[account]
+pub struct Dispute {
+    pub market: Pubkey,
+    pub challenger: Pubkey,
+    pub bond: u64,
+    pub original_outcome: Option<u8>,
+    pub original_price: Option<u64>,
+    pub proposed_outcome: u8,
+    pub proposed_price: u64,
+    pub created_at: i64,
+    pub resolved_at: i64,
+    pub status: DisputeStatus,
+    pub bump: u8,
+}
+
+impl Dispute {
+    pub const LEN: usize = 8 + // discriminator
+        32 + // market
+        32 + // challenger
+        8 + // bond
+        1 + 1 + // original_outcome (Option<u8>)
+        1 + 8 + // original_price (Option<u64>)
+        1 + // proposed_outcome
+        8 + // proposed_price
+        8 + // created_at
+        8 + // resolved_at
+        1 + // status
         1; // bump
 }
 
@@ -155,25 +1327,467 @@ pub enum MarketStatus {
     Disputed,
 }
 
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq)]
+pub enum DisputeStatus {
+    Pending,
+    Upheld,
+    Overturned,
+    Voided,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq)]
+pub enum ProposalStatus {
+    Pending,
+    Challenged,
+    Accepted,
+    Rejected,
+    Voided,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq)]
+pub enum MarketType {
+    Binary,
+    Categorical,
+    Scalar,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq)]
+pub enum PricingModel {
+    Parimutuel,
+    Lmsr,
+    ConstantProduct,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq)]
+pub enum OrderSide {
+    Buy,
+    Sell,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq)]
+pub enum OrderStatus {
+    Empty,
+    Open,
+    PartiallyFilled,
+    Filled,
+    Cancelled,
+    Expired,
+}
+
 #[derive(AnchorSerialize, AnchorDeserialize, Clone)]
 pub struct MarketParams {
     pub title: String,
     pub description: String,
     pub category: String,
+    pub market_type: MarketType,
+    pub outcome_labels: Vec<String>,
+    pub lower_bound: u64,
+    pub upper_bound: u64,
+    pub end_time: i64,
     pub resolution_time: i64,
+    pub dispute_period: i64,
     pub fee_rate: u16,
+    pub oracle_fee_bps: u16,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct ProgramConfig {
+    pub platform_fee_rate: u16,
+    pub max_creator_fee_rate: u16,
+    pub referral_fee_share: u16,
+    pub exit_fee_rate: u16,
+    pub min_resolution_time: i64,
+    pub max_resolution_time: i64,
+    pub guardian: Pubkey,
+    pub arbitrator: Pubkey,
+    pub dispute_bond: u64,
 }
 
 #[derive(AnchorSerialize, AnchorDeserialize, Clone)]
 pub struct TradeParams {
-    pub outcome: bool,
+    pub outcome: u8,
     pub amount: u64,
+    pub quoted_price: u64,
     pub max_slippage: u16,
 }
 
 #[derive(AnchorSerialize, AnchorDeserialize, Clone)]
 pub struct ResolutionParams {
-    pub outcome: bool,
+    pub outcome: u8,
     pub resolution_price: u64,
 }
-```
\ No newline at end of file
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct WithdrawalFeeParams {
+    pub base_rate: u16,
+    pub max_rate: u16,
+    pub ramp_period: i64,
+    pub lock_after_cutoff: bool,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct OrderParams {
+    pub outcome: u8,
+    pub side: OrderSide,
+    pub price: u64,
+    pub shares: u64,
+    pub expires_at: i64,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn provider(lp_tokens: u64) -> LiquidityProvider {
+        LiquidityProvider {
+            user: Pubkey::default(),
+            market: Pubkey::default(),
+            lp_tokens,
+            total_deposited: lp_tokens,
+            fee_growth_checkpoint: 0,
+            fees_owed: 0,
+            fees_claimed: 0,
+            reward_checkpoint: 0,
+            rewards_owed: 0,
+            rewards_claimed: 0,
+            bump: 0,
+        }
+    }
+
+    fn pool(total_supply: u64) -> LiquidityPool {
+        LiquidityPool {
+            market: Pubkey::default(),
+            token_mint: Pubkey::default(),
+            token_vault: Pubkey::default(),
+            total_supply,
+            total_liquidity: total_supply,
+            reserve_a: total_supply,
+            reserve_b: total_supply,
+            fee_collected: 0,
+            total_fees_collected: 0,
+            fee_growth_per_share: 0,
+            fee_rate: 0,
+            withdrawal_fee_rate: 0,
+            max_withdrawal_fee_rate: 0,
+            withdrawal_fee_ramp: 0,
+            lock_after_cutoff: false,
+            rewards: Pubkey::default(),
+            bump: 0,
+            vault_bump: 0,
+            lp_escrow_bump: 0,
+        }
+    }
+
+    #[test]
+    fn settle_fees_without_growth_is_a_no_op() {
+        let mut lp = provider(1_000);
+        lp.settle_fees(0).unwrap();
+        assert_eq!(lp.fees_owed, 0);
+
+        let mut empty = provider(0);
+        empty.settle_fees(5 * FEE_GROWTH_PRECISION).unwrap();
+        assert_eq!(empty.fees_owed, 0);
+        assert_eq!(empty.fee_growth_checkpoint, 5 * FEE_GROWTH_PRECISION);
+    }
+
+    #[test]
+    fn settle_fees_pays_pro_rata() {
+        let mut pool = pool(1_000);
+        pool.accrue_fee(100).unwrap();
+
+        let mut small = provider(250);
+        let mut large = provider(750);
+        small.settle_fees(pool.fee_growth_per_share).unwrap();
+        large.settle_fees(pool.fee_growth_per_share).unwrap();
+        assert_eq!(small.fees_owed, 25);
+        assert_eq!(large.fees_owed, 75);
+
+        // Settling again at the same growth credits nothing twice
+        small.settle_fees(pool.fee_growth_per_share).unwrap();
+        assert_eq!(small.fees_owed, 25);
+    }
+
+    #[test]
+    fn settle_fees_rounds_down_and_never_overpays() {
+        let mut pool = pool(3);
+        pool.accrue_fee(10).unwrap();
+
+        let mut paid = 0;
+        for _ in 0..3 {
+            let mut lp = provider(1);
+            lp.settle_fees(pool.fee_growth_per_share).unwrap();
+            assert_eq!(lp.fees_owed, 3);
+            paid += lp.fees_owed;
+        }
+        assert!(paid <= pool.fee_collected);
+    }
+
+    #[test]
+    fn late_provider_earns_only_later_fees() {
+        let mut pool = pool(1_000);
+        pool.accrue_fee(1_000).unwrap();
+
+        // Joins after the first fee: checkpoint first, then take the tokens
+        let mut late = provider(0);
+        late.settle_fees(pool.fee_growth_per_share).unwrap();
+        late.lp_tokens = 1_000;
+        pool.total_supply = 2_000;
+
+        pool.accrue_fee(1_000).unwrap();
+        late.settle_fees(pool.fee_growth_per_share).unwrap();
+        assert_eq!(late.fees_owed, 500);
+
+        let mut early = provider(1_000);
+        early.settle_fees(pool.fee_growth_per_share).unwrap();
+        assert_eq!(early.fees_owed, 1_500);
+    }
+
+    #[test]
+    fn accrue_fee_without_supply_keeps_growth() {
+        // A fee nobody could claim is refused rather than stranded in the pool
+        let mut pool = pool(0);
+        assert!(pool.accrue_fee(50).is_err());
+        assert_eq!(pool.fee_growth_per_share, 0);
+        assert_eq!(pool.fee_collected, 0);
+        assert_eq!(pool.total_fees_collected, 0);
+
+        // Waived fees book nothing
+        pool.accrue_fee(0).unwrap();
+        assert_eq!(pool.fee_collected, 0);
+    }
+
+    #[test]
+    fn pool_is_drained_once_providers_leave_dust_behind() {
+        let mut pool = pool(3);
+        pool.accrue_fee(10).unwrap();
+
+        // Both providers withdraw everything, as `remove_liquidity` books it
+        for lp_tokens in [1, 2] {
+            let mut lp = provider(lp_tokens);
+            lp.settle_fees(pool.fee_growth_per_share).unwrap();
+            pool.fee_collected -= lp.fees_owed;
+            pool.total_supply -= lp_tokens;
+        }
+
+        assert_eq!(pool.fee_collected, 1);
+        assert!(pool.is_drained());
+    }
+
+    #[test]
+    fn settle_fees_rejects_overflow_and_stale_growth() {
+        // Growth below the checkpoint
+        let mut lp = provider(1);
+        lp.fee_growth_checkpoint = 10;
+        assert!(lp.settle_fees(9).is_err());
+
+        // Product overflows u128
+        let mut lp = provider(2);
+        assert!(lp.settle_fees(u128::MAX).is_err());
+
+        // Earned amount doesn't fit in u64
+        let mut lp = provider(u64::MAX);
+        assert!(lp.settle_fees(2 * FEE_GROWTH_PRECISION).is_err());
+        let mut lp = provider(u64::MAX);
+        lp.settle_fees(FEE_GROWTH_PRECISION).unwrap();
+        assert_eq!(lp.fees_owed, u64::MAX);
+    }
+
+    fn ramped_pool(base: u16, max: u16, ramp: i64) -> LiquidityPool {
+        let mut pool = pool(1_000);
+        pool.withdrawal_fee_rate = base;
+        pool.max_withdrawal_fee_rate = max;
+        pool.withdrawal_fee_ramp = ramp;
+        pool
+    }
+
+    #[test]
+    fn withdrawal_fee_is_flat_without_a_ramp() {
+        let pool = ramped_pool(50, 500, 0);
+        assert_eq!(pool.withdrawal_fee_rate_at(0, 1_000), 50);
+        assert_eq!(pool.withdrawal_fee_rate_at(999, 1_000), 50);
+        assert_eq!(pool.withdrawal_fee_rate_at(5_000, 1_000), 50);
+    }
+
+    #[test]
+    fn withdrawal_fee_ramps_between_start_and_end() {
+        let pool = ramped_pool(100, 1_100, 1_000);
+        let resolution_time = 10_000;
+
+        // Flat up to and including the ramp start
+        assert_eq!(pool.withdrawal_fee_rate_at(0, resolution_time), 100);
+        assert_eq!(pool.withdrawal_fee_rate_at(9_000, resolution_time), 100);
+        // Linear in between, rounding down
+        assert_eq!(pool.withdrawal_fee_rate_at(9_001, resolution_time), 101);
+        assert_eq!(pool.withdrawal_fee_rate_at(9_500, resolution_time), 600);
+        assert_eq!(pool.withdrawal_fee_rate_at(9_999, resolution_time), 1_099);
+        // Capped at the maximum from resolution time on
+        assert_eq!(pool.withdrawal_fee_rate_at(10_000, resolution_time), 1_100);
+        assert_eq!(pool.withdrawal_fee_rate_at(i64::MAX, resolution_time), 1_100);
+    }
+
+    #[test]
+    fn withdrawal_fee_rounds_down_on_short_ramps() {
+        let pool = ramped_pool(0, 1, 3);
+        assert_eq!(pool.withdrawal_fee_rate_at(8, 10), 0);
+        assert_eq!(pool.withdrawal_fee_rate_at(9, 10), 0);
+        assert_eq!(pool.withdrawal_fee_rate_at(10, 10), 1);
+    }
+
+    #[test]
+    fn withdrawal_fee_handles_extreme_inputs() {
+        // Ramp longer than all of time before resolution
+        let pool = ramped_pool(0, u16::MAX, i64::MAX);
+        assert_eq!(pool.withdrawal_fee_rate_at(i64::MIN, i64::MIN), 0);
+        assert_eq!(pool.withdrawal_fee_rate_at(i64::MIN + 1, i64::MIN + 1), u16::MAX);
+        assert_eq!(pool.withdrawal_fee_rate_at(0, i64::MAX), 0);
+        assert_eq!(pool.withdrawal_fee_rate_at(i64::MAX / 2, i64::MAX), u16::MAX / 2);
+        assert_eq!(pool.withdrawal_fee_rate_at(i64::MAX - 1, i64::MAX), u16::MAX - 1);
+
+        // A maximum below the base never lowers the fee mid-ramp
+        let inverted = ramped_pool(500, 100, 1_000);
+        assert_eq!(inverted.withdrawal_fee_rate_at(9_500, 10_000), 500);
+    }
+
+    fn rewards(emission_rate: u64, start_time: i64, end_time: i64) -> LiquidityRewards {
+        LiquidityRewards {
+            market: Pubkey::default(),
+            sponsor: Pubkey::default(),
+            reward_mint: Pubkey::default(),
+            reward_vault: Pubkey::default(),
+            emission_rate,
+            start_time,
+            end_time,
+            last_update_time: start_time,
+            reward_per_share: 0,
+            total_funded: emission_rate * (end_time - start_time) as u64,
+            total_distributed: 0,
+            total_claimed: 0,
+            total_reclaimed: 0,
+            bump: 0,
+            vault_bump: 0,
+        }
+    }
+
+    #[test]
+    fn rewards_accrue_only_inside_the_window() {
+        let mut program = rewards(10, 100, 200);
+        program.accrue(50, 1_000).unwrap();
+        assert_eq!(program.total_distributed, 0);
+
+        program.accrue(150, 1_000).unwrap();
+        assert_eq!(program.total_distributed, 500);
+        program.accrue(10_000, 1_000).unwrap();
+        assert_eq!(program.total_distributed, 1_000);
+        assert_eq!(program.reclaimable().unwrap(), 0);
+
+        let mut lp = provider(250);
+        lp.settle_rewards(program.reward_per_share).unwrap();
+        assert_eq!(lp.rewards_owed, 250);
+    }
+
+    #[test]
+    fn rewards_emitted_into_an_empty_pool_stay_reclaimable() {
+        let mut program = rewards(10, 100, 200);
+        program.accrue(150, 0).unwrap();
+        assert_eq!(program.total_distributed, 0);
+        assert_eq!(program.reclaimable().unwrap(), 1_000);
+
+        program.accrue(200, 1_000).unwrap();
+        assert_eq!(program.reclaimable().unwrap(), 500);
+    }
+
+    #[test]
+    fn reclaimable_rejects_inconsistent_totals() {
+        let mut program = rewards(10, 100, 200);
+        program.total_reclaimed = 600;
+        program.total_distributed = 500;
+        assert!(program.reclaimable().is_err());
+    }
+
+    /// A blank market: every field zeroed, which borsh reads as empty strings
+    /// and vectors, default keys and the first variant of each enum.
+    fn market() -> PredictionMarket {
+        PredictionMarket::deserialize(&mut &[0u8; PredictionMarket::LEN][..]).unwrap()
+    }
+
+    fn proposal(status: ProposalStatus) -> Proposal {
+        Proposal {
+            market: Pubkey::default(),
+            proposer: Pubkey::new_unique(),
+            challenger: Pubkey::new_unique(),
+            outcome: OUTCOME_A,
+            resolution_price: 0,
+            bond: 500,
+            proposed_at: 0,
+            liveness_end: 0,
+            status,
+            bump: 0,
+        }
+    }
+
+    #[test]
+    fn oracle_cancels_only_markets_it_resolves() {
+        let mut market = market();
+        market.authority = Pubkey::new_unique();
+        market.oracle = Pubkey::new_unique();
+        market.end_time = 1_000;
+        let (creator, oracle, anyone) = (market.authority, market.oracle, Pubkey::new_unique());
+
+        // Oracle-resolved: the oracle at any time, the creator until trading ends
+        assert!(market.check_cancel_authority(&oracle, 5_000).is_ok());
+        assert!(market.check_cancel_authority(&creator, 999).is_ok());
+        assert!(market.check_cancel_authority(&creator, 1_000).is_err());
+        assert!(market.check_cancel_authority(&anyone, 0).is_err());
+
+        // Optimistic: the oracle has no say, the creator only until trading ends
+        market.arbiter = Pubkey::new_unique();
+        assert!(market.check_cancel_authority(&oracle, 0).is_err());
+        assert!(market.check_cancel_authority(&oracle, 5_000).is_err());
+        assert!(market.check_cancel_authority(&creator, 999).is_ok());
+        assert!(market.check_cancel_authority(&creator, 1_000).is_err());
+    }
+
+    #[test]
+    fn voiding_a_pending_proposal_refunds_the_proposer() {
+        let mut pending = proposal(ProposalStatus::Pending);
+        assert_eq!(pending.void(), (Some((pending.proposer, 500)), None));
+        assert!(pending.status == ProposalStatus::Voided);
+        // Nothing is refunded twice
+        assert_eq!(pending.void(), (None, None));
+
+        let mut challenged = proposal(ProposalStatus::Challenged);
+        assert_eq!(
+            challenged.void(),
+            (Some((challenged.proposer, 500)), Some((challenged.challenger, 500)))
+        );
+
+        for status in [ProposalStatus::Accepted, ProposalStatus::Rejected] {
+            let mut settled = proposal(status);
+            assert_eq!(settled.void(), (None, None));
+            assert!(settled.status == status);
+        }
+    }
+
+    #[test]
+    fn anyone_cancels_a_threshold_market_past_its_price_window() {
+        let mut market = market();
+        market.authority = Pubkey::new_unique();
+        market.price_feed = Pubkey::new_unique();
+        market.end_time = 1_000;
+        market.max_price_staleness = 60;
+        let anyone = Pubkey::new_unique();
+
+        // Still resolvable from the feed up to end_time + max_price_staleness
+        assert!(!market.price_window_closed(1_060));
+        assert!(market.check_cancel_authority(&anyone, 1_060).is_err());
+        assert!(market.check_cancel_authority(&market.authority, 1_060).is_err());
+
+        assert!(market.price_window_closed(1_061));
+        assert!(market.check_cancel_authority(&anyone, 1_061).is_ok());
+
+        // Markets without a feed have no such window
+        market.price_feed = Pubkey::default();
+        assert!(!market.price_window_closed(i64::MAX));
+        assert!(market.check_cancel_authority(&anyone, i64::MAX).is_err());
+    }
+}
@@ -1,8 +1,11 @@
-```rust
 use anchor_lang::prelude::*;
 use anchor_spl::token::{self, Token, TokenAccount, Transfer};
 use std::convert::TryInto;
 
+use crate::error::PredictionMarketError;
+use crate::instructions::register_referrer::ReferralRewardEvent;
+use crate::state::{GlobalState, Referrer, PRICE_PRECISION};
+
 #[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, PartialEq)]
 pub struct PriceData {
     pub price: u64,
@@ -17,7 +20,7 @@ pub fn calculate_payout_ratio(
 ) -> Result<u64> {
     let total_pool = total_yes_amount
         .checked_add(total_no_amount)
-        .ok_or(ErrorCode::MathOverflow)?;
+        .ok_or(PredictionMarketError::ArithmeticOverflow)?;
     
     if total_pool == 0 {
         return Ok(0);
@@ -37,9 +40,9 @@ pub fn calculate_payout_ratio(
     // Using 10000 as basis points for precision
     let ratio = total_pool
         .checked_mul(10000)
-        .ok_or(ErrorCode::MathOverflow)?
+        .ok_or(PredictionMarketError::ArithmeticOverflow)?
         .checked_div(winning_amount)
-        .ok_or(ErrorCode::DivisionByZero)?;
+        .ok_or(PredictionMarketError::DivisionByZero)?;
 
     Ok(ratio)
 }
@@ -50,24 +53,48 @@ pub fn calculate_user_payout(
 ) -> Result<u64> {
     let payout = user_bet_amount
         .checked_mul(payout_ratio)
-        .ok_or(ErrorCode::MathOverflow)?
+        .ok_or(PredictionMarketError::ArithmeticOverflow)?
         .checked_div(10000)
-        .ok_or(ErrorCode::DivisionByZero)?;
+        .ok_or(PredictionMarketError::DivisionByZero)?;
 
     Ok(payout)
 }
 
+/// Share of a scalar market's payout owed to the LONG side, scaled by
+/// `PRICE_PRECISION`. Prices outside `[lower_bound, upper_bound]` are clamped,
+/// so LONG receives everything at or above the upper bound and nothing at or
+/// below the lower bound.
+pub fn calculate_scalar_long_ratio(
+    lower_bound: u64,
+    upper_bound: u64,
+    resolution_price: u64,
+) -> Result<u64> {
+    let range = upper_bound
+        .checked_sub(lower_bound)
+        .ok_or(PredictionMarketError::InvalidScalarBounds)?;
+    require!(range > 0, PredictionMarketError::InvalidScalarBounds);
+
+    let clamped_price = resolution_price.clamp(lower_bound, upper_bound);
+    let ratio = ((clamped_price - lower_bound) as u128)
+        .checked_mul(PRICE_PRECISION as u128)
+        .ok_or(PredictionMarketError::ArithmeticOverflow)?
+        .checked_div(range as u128)
+        .ok_or(PredictionMarketError::DivisionByZero)?;
+
+    Ok(ratio as u64)
+}
+
 pub fn calculate_platform_fee(
     total_amount: u64,
     fee_basis_points: u16,
 ) -> Result<u64> {
     let fee = (total_amount as u128)
         .checked_mul(fee_basis_points as u128)
-        .ok_or(ErrorCode::MathOverflow)?
+        .ok_or(PredictionMarketError::ArithmeticOverflow)?
         .checked_div(10000)
-        .ok_or(ErrorCode::DivisionByZero)?;
+        .ok_or(PredictionMarketError::DivisionByZero)?;
 
-    Ok(fee.try_into().map_err(|_| ErrorCode::MathOverflow)?)
+    Ok(fee.try_into().map_err(|_| PredictionMarketError::ArithmeticOverflow)?)
 }
 
 pub fn is_market_expired(
@@ -83,17 +110,25 @@ pub fn is_market_resolved(
     resolution_timestamp > 0
 }
 
+/// Rejects a price published more than `max_staleness` seconds away from
+/// `reference_timestamp`, or whose confidence interval is missing or wider
+/// than `max_confidence_bps` of the price.
 pub fn validate_price_data(
     price_data: &PriceData,
     max_staleness: i64,
-    current_timestamp: i64,
+    reference_timestamp: i64,
+    max_confidence_bps: u16,
 ) -> Result<()> {
-    if current_timestamp - price_data.timestamp > max_staleness {
-        return Err(ErrorCode::StalePrice.into());
+    let age = reference_timestamp
+        .checked_sub(price_data.timestamp)
+        .ok_or(PredictionMarketError::ArithmeticOverflow)?;
+    if age.unsigned_abs() > max_staleness.unsigned_abs() {
+        return Err(PredictionMarketError::StalePrice.into());
     }
 
-    if price_data.confidence == 0 {
-        return Err(ErrorCode::InvalidPriceConfidence.into());
+    let max_confidence = (price_data.price as u128) * (max_confidence_bps as u128) / 10000;
+    if price_data.confidence == 0 || price_data.confidence as u128 > max_confidence {
+        return Err(PredictionMarketError::InvalidPriceConfidence.into());
     }
 
     Ok(())
@@ -151,13 +186,104 @@ pub fn transfer_tokens_with_signer<'info>(
     Ok(())
 }
 
+/// Closes an account owned by this program that isn't held as a typed
+/// `Account`, sending its rent to `destination`. Mirrors Anchor's `close`.
+pub fn close_program_account<'info>(
+    account: &AccountInfo<'info>,
+    destination: &AccountInfo<'info>,
+) -> Result<()> {
+    let rent = account.lamports();
+    **destination.try_borrow_mut_lamports()? = destination
+        .lamports()
+        .checked_add(rent)
+        .ok_or(PredictionMarketError::ArithmeticOverflow)?;
+    **account.try_borrow_mut_lamports()? = 0;
+
+    account.assign(&anchor_lang::system_program::ID);
+    account.realloc(0, false)?;
+    Ok(())
+}
+
+/// Token accounts a claim's platform fee moves between. Fees are paid out of
+/// the market vault, signed for by the market.
+pub struct PlatformFeeAccounts<'a, 'info> {
+    pub vault: &'a Account<'info, TokenAccount>,
+    pub market: &'a AccountInfo<'info>,
+    pub treasury: &'a Account<'info, TokenAccount>,
+    pub referral_vault: Option<&'a Account<'info, TokenAccount>>,
+    pub token_program: &'a Program<'info, Token>,
+}
+
+/// Pays out the platform fee charged on a claim. A position referred by
+/// `attributed_referrer` passes the referral cut on to its referrer, who must
+/// then be passed in along with the referral vault; the rest goes to the
+/// treasury. Returns the treasury portion.
+pub fn distribute_platform_fee<'info>(
+    accounts: PlatformFeeAccounts<'_, 'info>,
+    global_state: &mut GlobalState,
+    referrer: Option<&mut Account<'info, Referrer>>,
+    attributed_referrer: Pubkey,
+    user: Pubkey,
+    platform_fee: u64,
+    signer_seeds: &[&[&[u8]]],
+) -> Result<u64> {
+    if platform_fee == 0 {
+        return Ok(0);
+    }
+
+    let referral_reward = if attributed_referrer == Pubkey::default() {
+        0
+    } else {
+        let (Some(referrer), Some(referral_vault)) = (referrer, accounts.referral_vault) else {
+            return err!(PredictionMarketError::ReferrerRequired);
+        };
+        let reward = global_state.referral_cut(platform_fee)?;
+        transfer_tokens_with_signer(
+            accounts.vault,
+            referral_vault,
+            accounts.market,
+            accounts.token_program,
+            reward,
+            signer_seeds,
+        )?;
+        referrer.credit_reward(reward)?;
+
+        emit!(ReferralRewardEvent {
+            referrer: referrer.key(),
+            user,
+            market: accounts.market.key(),
+            platform_fee,
+            reward,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+        reward
+    };
+
+    let treasury_fee = platform_fee
+        .checked_sub(referral_reward)
+        .ok_or(PredictionMarketError::ArithmeticUnderflow)?;
+    transfer_tokens_with_signer(
+        accounts.vault,
+        accounts.treasury,
+        accounts.market,
+        accounts.token_program,
+        treasury_fee,
+        signer_seeds,
+    )?;
+    global_state.total_fees_collected = global_state.total_fees_collected
+        .checked_add(treasury_fee)
+        .ok_or(PredictionMarketError::ArithmeticOverflow)?;
+
+    Ok(treasury_fee)
+}
+
 pub fn calculate_market_odds(
     yes_amount: u64,
     no_amount: u64,
 ) -> Result<(u64, u64)> {
     let total = yes_amount
         .checked_add(no_amount)
-        .ok_or(ErrorCode::MathOverflow)?;
+        .ok_or(PredictionMarketError::ArithmeticOverflow)?;
 
     if total == 0 {
         return Ok((5000, 5000)); // 50/50 odds
@@ -165,13 +291,13 @@ pub fn calculate_market_odds(
 
     let yes_odds = yes_amount
         .checked_mul(10000)
-        .ok_or(ErrorCode::MathOverflow)?
+        .ok_or(PredictionMarketError::ArithmeticOverflow)?
         .checked_div(total)
-        .ok_or(ErrorCode::DivisionByZero)?;
+        .ok_or(PredictionMarketError::DivisionByZero)?;
 
-    let no_odds = 10000
+    let no_odds = 10000u64
         .checked_sub(yes_odds)
-        .ok_or(ErrorCode::MathOverflow)?;
+        .ok_or(PredictionMarketError::ArithmeticOverflow)?;
 
     Ok((yes_odds, no_odds))
 }
@@ -185,50 +311,52 @@ pub fn validate_market_parameters(
     let duration = expiry_timestamp - current_timestamp;
 
     if duration < minimum_duration {
-        return Err(ErrorCode::MarketDurationTooShort.into());
+        return Err(PredictionMarketError::MarketDurationTooShort.into());
     }
 
     if duration > maximum_duration {
-        return Err(ErrorCode::MarketDurationTooLong.into());
+        return Err(PredictionMarketError::MarketDurationTooLong.into());
     }
 
     Ok(())
 }
 
-pub fn get_market_status(
-    current_timestamp: i64,
-    expiry_timestamp: i64,
-    resolution_timestamp: i64,
-) -> MarketStatus {
-    if resolution_timestamp > 0 {
-        MarketStatus::Resolved
-    } else if current_timestamp >= expiry_timestamp {
-        MarketStatus::Expired
-    } else {
-        MarketStatus::Active
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn scalar_ratio_is_linear_between_bounds() {
+        assert_eq!(calculate_scalar_long_ratio(100, 200, 150).unwrap(), PRICE_PRECISION / 2);
+        assert_eq!(calculate_scalar_long_ratio(100, 200, 125).unwrap(), PRICE_PRECISION / 4);
+        assert_eq!(calculate_scalar_long_ratio(0, 10, 7).unwrap(), PRICE_PRECISION * 7 / 10);
     }
-}
 
-#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, PartialEq)]
-pub enum MarketStatus {
-    Active,
-    Expired,
-    Resolved,
-}
-
-#[error_code]
-pub enum ErrorCode {
-    #[msg("Math overflow")]
-    MathOverflow,
-    #[msg("Division by zero")]
-    DivisionByZero,
-    #[msg("Price data is stale")]
-    StalePrice,
-    #[msg("Invalid price confidence")]
-    InvalidPriceConfidence,
-    #[msg("Market duration too short")]
-    MarketDurationTooShort,
-    #[msg("Market duration too long")]
-    MarketDurationTooLong,
-}
-```
\ No newline at end of file
+    #[test]
+    fn scalar_ratio_clamps_at_bounds() {
+        assert_eq!(calculate_scalar_long_ratio(100, 200, 100).unwrap(), 0);
+        assert_eq!(calculate_scalar_long_ratio(100, 200, 0).unwrap(), 0);
+        assert_eq!(calculate_scalar_long_ratio(100, 200, 200).unwrap(), PRICE_PRECISION);
+        assert_eq!(calculate_scalar_long_ratio(100, 200, u64::MAX).unwrap(), PRICE_PRECISION);
+    }
+
+    #[test]
+    fn scalar_ratio_rounds_down() {
+        // 1/3 of the range leaves the remainder with SHORT
+        assert_eq!(calculate_scalar_long_ratio(0, 3, 1).unwrap(), PRICE_PRECISION / 3);
+        assert_eq!(calculate_scalar_long_ratio(0, 3, 2).unwrap(), PRICE_PRECISION * 2 / 3);
+    }
+
+    #[test]
+    fn scalar_ratio_handles_full_u64_range() {
+        assert_eq!(calculate_scalar_long_ratio(0, u64::MAX, u64::MAX).unwrap(), PRICE_PRECISION);
+        assert_eq!(calculate_scalar_long_ratio(0, u64::MAX, u64::MAX / 2).unwrap(), PRICE_PRECISION / 2 - 1);
+        assert_eq!(calculate_scalar_long_ratio(0, u64::MAX, 0).unwrap(), 0);
+    }
+
+    #[test]
+    fn scalar_ratio_rejects_empty_or_inverted_range() {
+        assert!(calculate_scalar_long_ratio(100, 100, 100).is_err());
+        assert!(calculate_scalar_long_ratio(200, 100, 150).is_err());
+    }
+}